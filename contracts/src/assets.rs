@@ -0,0 +1,140 @@
+use crate::config;
+use crate::errors::SavingsError;
+use crate::governance;
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+/// Storage keys for the asset whitelist.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AssetKey {
+    /// Maps an asset address to whether it currently accepts new deposits
+    Supported(Address),
+    /// Every asset that has ever been whitelisted (kept after removal so
+    /// balances held in it stay visible in views)
+    AllAssets,
+}
+
+// ========== Base Asset ==========
+
+/// Returns the asset used by the single-asset entry points.
+///
+/// This is the underlying asset from `set_underlying_asset`. Before one is
+/// configured the contract runs in accounting-only mode and its own address
+/// stands in as the unit of account, so no token transfers take place.
+pub fn base_asset(env: &Env) -> Address {
+    config::get_underlying_asset(env).unwrap_or_else(|| env.current_contract_address())
+}
+
+/// Returns true if `asset` is the accounting-only placeholder rather than a token.
+pub fn is_accounting_unit(env: &Env, asset: &Address) -> bool {
+    *asset == env.current_contract_address()
+}
+
+// ========== Whitelist Management ==========
+
+/// Adds an asset to the whitelist (admin or governance).
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+/// * `InvalidPlanConfig` - If the asset is already supported
+pub fn add_supported_asset(env: &Env, caller: Address, asset: Address) -> Result<(), SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+
+    if is_supported(env, &asset) {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+
+    let key = AssetKey::Supported(asset.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    let mut all = get_all_assets(env);
+    if !all.contains(&asset) {
+        all.push_back(asset.clone());
+        env.storage().persistent().set(&AssetKey::AllAssets, &all);
+    }
+    env.storage()
+        .persistent()
+        .extend_ttl(&AssetKey::AllAssets, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    env.events()
+        .publish((symbol_short!("asset"), symbol_short!("add")), asset);
+
+    Ok(())
+}
+
+/// Removes an asset from the whitelist (admin or governance).
+///
+/// Existing plans in the asset can still be withdrawn; only new deposits
+/// are rejected. The base asset cannot be removed.
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+/// * `UnsupportedAsset` - If the asset is not currently supported
+pub fn remove_supported_asset(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+) -> Result<(), SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+
+    let key = AssetKey::Supported(asset.clone());
+    if asset == base_asset(env) || !env.storage().persistent().get(&key).unwrap_or(false) {
+        return Err(SavingsError::UnsupportedAsset);
+    }
+
+    env.storage().persistent().set(&key, &false);
+
+    env.events()
+        .publish((symbol_short!("asset"), symbol_short!("remove")), asset);
+
+    Ok(())
+}
+
+/// Returns true if `asset` currently accepts deposits. The base asset always does.
+pub fn is_supported(env: &Env, asset: &Address) -> bool {
+    if *asset == base_asset(env) {
+        return true;
+    }
+    env.storage()
+        .persistent()
+        .get(&AssetKey::Supported(asset.clone()))
+        .unwrap_or(false)
+}
+
+/// Validates that `asset` accepts deposits.
+///
+/// # Errors
+/// * `UnsupportedAsset` - If the asset is not whitelisted
+pub fn require_supported(env: &Env, asset: &Address) -> Result<(), SavingsError> {
+    if !is_supported(env, asset) {
+        return Err(SavingsError::UnsupportedAsset);
+    }
+    Ok(())
+}
+
+/// Returns every asset that has been whitelisted, including removed ones.
+pub fn get_all_assets(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&AssetKey::AllAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Returns the base asset followed by every asset currently accepting deposits.
+pub fn get_supported_assets(env: &Env) -> Vec<Address> {
+    let base = base_asset(env);
+    let mut assets = Vec::new(env);
+    assets.push_back(base.clone());
+    for asset in get_all_assets(env).iter() {
+        if asset != base && is_supported(env, &asset) {
+            assets.push_back(asset);
+        }
+    }
+    assets
+}
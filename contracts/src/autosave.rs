@@ -1,9 +1,34 @@
+use crate::assets;
+use crate::custody::{self, Funding};
 use crate::errors::SavingsError;
 use crate::flexi;
-use crate::storage_types::{AutoSave, DataKey};
+use crate::goal;
+use crate::group;
+use crate::lock;
+use crate::rates;
+use crate::rosca;
+use crate::storage_types::{decode_versioned, AutoSave, AutoSaveTarget, DataKey, LegacyAutoSave};
 use crate::ttl;
 use crate::users;
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Val, Vec};
+
+/// Width of each due-index bucket in seconds
+pub const DUE_BUCKET_SECONDS: u64 = 3_600;
+
+/// Buckets grouped under one page of the due index (one day)
+pub const BUCKETS_PER_PAGE: u64 = 24;
+
+/// Storage keys for the AutoSave due index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AutoSaveKey {
+    /// Maps a bucket (`next_execution_time / DUE_BUCKET_SECONDS`) to schedule IDs due in it
+    DueBucket(u64),
+    /// Sorted buckets of one page (`bucket / BUCKETS_PER_PAGE`) that hold schedules
+    BucketPage(u64),
+    /// Sorted list of pages that currently hold buckets
+    Pages,
+}
 
 /// Creates a new AutoSave schedule for recurring Flexi deposits
 ///
@@ -23,6 +48,40 @@ pub fn create_autosave(
     amount: i128,
     interval_seconds: u64,
     start_time: u64,
+) -> Result<u64, SavingsError> {
+    create_targeted_autosave(
+        env,
+        user,
+        AutoSaveTarget::Flexi,
+        amount,
+        interval_seconds,
+        start_time,
+    )
+}
+
+/// Creates a new AutoSave schedule that deposits into the given target
+///
+/// The target is validated up front so a schedule can't be created against
+/// a plan the user doesn't own or can't contribute to.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `user` - The user creating the schedule
+/// * `target` - The Flexi, Lock, Goal or Group destination for each run
+/// * `amount` - The amount to deposit on each execution (must be > 0)
+/// * `interval_seconds` - How often the schedule runs in seconds (must be > 0)
+/// * `start_time` - Unix timestamp for the first execution
+///
+/// # Returns
+/// * `Ok(u64)` - The unique schedule ID
+/// * `Err(SavingsError)` - If validation fails
+pub fn create_targeted_autosave(
+    env: &Env,
+    user: Address,
+    target: AutoSaveTarget,
+    amount: i128,
+    interval_seconds: u64,
+    start_time: u64,
 ) -> Result<u64, SavingsError> {
     user.require_auth();
 
@@ -41,6 +100,8 @@ pub fn create_autosave(
         return Err(SavingsError::UserNotFound);
     }
 
+    validate_target(env, &user, &target, amount)?;
+
     // Generate unique schedule ID
     let schedule_id = get_next_schedule_id(env);
 
@@ -48,10 +109,18 @@ pub fn create_autosave(
     let schedule = AutoSave {
         id: schedule_id,
         user: user.clone(),
+        target,
         amount,
         interval_seconds,
         next_execution_time: start_time,
         is_active: true,
+        is_paused: false,
+        end_time: 0,
+        max_executions: 0,
+        executions: 0,
+        catch_up: false,
+        keeper_fee: 0,
+        keeper_balance: 0,
     };
 
     // Store the schedule
@@ -59,8 +128,9 @@ pub fn create_autosave(
         .persistent()
         .set(&DataKey::AutoSave(schedule_id), &schedule);
 
-    // Link schedule to user
+    // Link schedule to user and the due index
     add_schedule_to_user(env, &user, schedule_id);
+    index_schedule(env, schedule_id, start_time);
 
     // Increment the next schedule ID
     increment_next_schedule_id(env);
@@ -83,14 +153,10 @@ pub fn create_autosave(
 /// * `Err(SavingsError)` - If the schedule is not found, inactive, or not yet due
 pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError> {
     // Fetch the schedule
-    let mut schedule: AutoSave = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AutoSave(schedule_id))
-        .ok_or(SavingsError::PlanNotFound)?;
+    let mut schedule = get_autosave(env, schedule_id).ok_or(SavingsError::PlanNotFound)?;
 
-    // Ensure schedule is active
-    if !schedule.is_active {
+    // Ensure schedule is active and not paused
+    if !schedule.is_active || schedule.is_paused {
         return Err(SavingsError::InvalidPlanConfig);
     }
 
@@ -100,19 +166,11 @@ pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError>
         return Err(SavingsError::InvalidTimestamp);
     }
 
-    // Perform Flexi deposit
-    flexi::flexi_deposit(env.clone(), schedule.user.clone(), schedule.amount)?;
-
-    // Update next execution time
-    schedule.next_execution_time += schedule.interval_seconds;
-
-    // Save updated schedule
-    env.storage()
-        .persistent()
-        .set(&DataKey::AutoSave(schedule_id), &schedule);
+    // Deposit into the schedule's target
+    deposit_to_target(env, &schedule)?;
 
-    // Extend TTL on execution (active schedule gets full extension)
-    ttl::extend_autosave_ttl(env, schedule_id);
+    // Update next execution time and persist
+    advance_schedule(env, &mut schedule);
 
     Ok(())
 }
@@ -131,27 +189,60 @@ pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError>
 /// A `Vec<bool>` where each element corresponds to the schedule at the same
 /// index in `schedule_ids`:
 /// - `true`  — the schedule was due and executed successfully
-/// - `false` — the schedule was skipped (not found, inactive, not yet due, or deposit failed)
+/// - `false` — the schedule was skipped (not found, inactive, not yet due, underfunded,
+///   or deposit failed)
 ///
 /// # Guarantees
 /// - One failed or skipped schedule does **not** revert the entire batch.
 /// - Only schedules whose `next_execution_time <= current_ledger_timestamp` are executed.
-/// - For each executed schedule, a Flexi deposit is performed and `next_execution_time` is
-///   advanced by `interval_seconds`.
+/// - For each executed schedule, a deposit is routed to its target (Flexi, Lock, Goal or
+///   Group) and `next_execution_time` is advanced by `interval_seconds`. Unless the
+///   schedule opts into `catch_up`, intervals that were missed are skipped so a stalled
+///   schedule runs once and then waits for its next future slot.
+/// - Schedules that reach their `end_time` or `max_executions` are deactivated.
+/// - Funds are pulled through the allowance each saver granted the contract, so
+///   anyone can run the batch. Keeper fees are left untouched.
 pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
+    run_due_autosaves(env, &schedule_ids, false).0
+}
+
+/// Batch-executes due schedules on behalf of `keeper` and pays it their keeper fees.
+///
+/// Behaves like [`execute_due_autosaves`]; additionally, every executed schedule
+/// whose prepaid `keeper_balance` covers its `keeper_fee` pays that fee to the
+/// keeper. Fees are transferred once, after all schedules have run.
+pub fn execute_autosaves_as_keeper(
+    env: &Env,
+    keeper: Address,
+    schedule_ids: Vec<u64>,
+) -> Result<Vec<bool>, SavingsError> {
+    keeper.require_auth();
+
+    let (results, earned) = run_due_autosaves(env, &schedule_ids, true);
+
+    if earned > 0 {
+        env.events()
+            .publish((symbol_short!("as_keep"), keeper.clone()), earned);
+        custody::send(env, &assets::base_asset(env), &keeper, earned)?;
+    }
+
+    Ok(results)
+}
+
+/// Runs every due schedule in `schedule_ids`, returning per-schedule results and
+/// the keeper fees drawn from the executed schedules (only when `pay_keeper`).
+fn run_due_autosaves(env: &Env, schedule_ids: &Vec<u64>, pay_keeper: bool) -> (Vec<bool>, i128) {
     let current_time = env.ledger().timestamp();
     let mut results = Vec::new(env);
+    let mut earned: i128 = 0;
 
     for i in 0..schedule_ids.len() {
         let schedule_id = schedule_ids.get(i).unwrap();
 
         // Attempt to fetch the schedule; skip if not found
-        let maybe_schedule: Option<AutoSave> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::AutoSave(schedule_id));
+        let maybe_schedule = get_autosave(env, schedule_id);
 
-        let schedule = match maybe_schedule {
+        let mut schedule = match maybe_schedule {
             Some(s) => s,
             None => {
                 results.push_back(false);
@@ -159,8 +250,8 @@ pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
             }
         };
 
-        // Skip inactive schedules
-        if !schedule.is_active {
+        // Skip inactive and paused schedules
+        if !schedule.is_active || schedule.is_paused {
             results.push_back(false);
             continue;
         }
@@ -171,26 +262,302 @@ pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
             continue;
         }
 
-        // Attempt the Flexi deposit; if it fails, mark as false and continue
-        let deposit_result =
-            flexi::flexi_deposit(env.clone(), schedule.user.clone(), schedule.amount);
+        // Skip savers who can't fund this run; a failed token transfer would trap the batch
+        let funded = match target_asset(env, &schedule.target) {
+            Some(asset) => {
+                custody::can_receive_scheduled(env, &asset, &schedule.user, schedule.amount)
+            }
+            None => false,
+        };
+        if !funded {
+            results.push_back(false);
+            continue;
+        }
 
-        if deposit_result.is_err() {
+        // Attempt the deposit into the target; if it fails, mark as false and continue
+        if deposit_to_target(env, &schedule).is_err() {
             results.push_back(false);
             continue;
         }
 
+        // Draw the keeper fee while the prepaid balance covers it
+        if pay_keeper && schedule.keeper_fee > 0 && schedule.keeper_balance >= schedule.keeper_fee {
+            schedule.keeper_balance -= schedule.keeper_fee;
+            earned += schedule.keeper_fee;
+        }
+
         // Update next execution time and persist
-        let mut updated_schedule = schedule.clone();
-        updated_schedule.next_execution_time += updated_schedule.interval_seconds;
-        env.storage()
-            .persistent()
-            .set(&DataKey::AutoSave(schedule_id), &updated_schedule);
+        advance_schedule(env, &mut schedule);
 
         results.push_back(true);
     }
 
-    results
+    (results, earned)
+}
+
+/// Sets the fee paid to keepers for each execution of a schedule
+///
+/// # Errors
+/// * `InvalidAmount` - If `fee` is negative
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+pub fn set_keeper_fee(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    fee: i128,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    if fee < 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    schedule.keeper_fee = fee;
+    save_schedule(env, &schedule);
+
+    Ok(())
+}
+
+/// Tops up the prepaid keeper balance of a schedule in the base asset
+///
+/// # Errors
+/// * `InvalidAmount` - If `amount` is not positive
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+/// * `InvalidPlanConfig` - If the schedule has been cancelled
+pub fn fund_keeper(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    user.require_auth();
+
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if !schedule.is_active {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+
+    custody::receive(env, &assets::base_asset(env), &user, amount)?;
+
+    schedule.keeper_balance = schedule
+        .keeper_balance
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    save_schedule(env, &schedule);
+
+    Ok(schedule.keeper_balance)
+}
+
+/// Pauses a schedule without cancelling it
+///
+/// # Errors
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+/// * `InvalidPlanConfig` - If the schedule is cancelled, finished or already paused
+pub fn pause_autosave(env: &Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if !schedule.is_active || schedule.is_paused {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+
+    schedule.is_paused = true;
+    unindex_schedule(env, schedule_id, schedule.next_execution_time);
+    save_schedule(env, &schedule);
+
+    env.events()
+        .publish((symbol_short!("as_pause"), user, schedule_id), true);
+
+    Ok(())
+}
+
+/// Resumes a paused schedule
+///
+/// Unless the schedule uses `catch_up`, runs missed while paused are skipped
+/// and the next run moves to the first interval at or after now.
+///
+/// # Errors
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+/// * `InvalidPlanConfig` - If the schedule is not paused
+pub fn resume_autosave(env: &Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if !schedule.is_active || !schedule.is_paused {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+
+    schedule.is_paused = false;
+    let now = env.ledger().timestamp();
+    if !schedule.catch_up && schedule.next_execution_time < now {
+        let missed = (now - schedule.next_execution_time).div_ceil(schedule.interval_seconds);
+        schedule.next_execution_time = schedule
+            .next_execution_time
+            .saturating_add(missed.saturating_mul(schedule.interval_seconds));
+    }
+
+    if is_exhausted(&schedule) {
+        finish_schedule(env, &mut schedule);
+    } else {
+        index_schedule(env, schedule_id, schedule.next_execution_time);
+    }
+    save_schedule(env, &schedule);
+
+    env.events()
+        .publish((symbol_short!("as_pause"), user, schedule_id), false);
+
+    Ok(())
+}
+
+/// Changes the amount and interval of a schedule
+///
+/// The next run keeps its current time; the new interval applies from then on.
+///
+/// # Errors
+/// * `InvalidAmount` - If `amount` is not positive or doesn't suit the target
+/// * `InvalidTimestamp` - If `interval_seconds` is 0
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+/// * `InvalidPlanConfig` - If the schedule is cancelled or finished
+pub fn update_autosave(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    amount: i128,
+    interval_seconds: u64,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if interval_seconds == 0 {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if !schedule.is_active {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    validate_target(env, &user, &schedule.target, amount)?;
+
+    schedule.amount = amount;
+    schedule.interval_seconds = interval_seconds;
+    save_schedule(env, &schedule);
+
+    Ok(())
+}
+
+/// Sets when a schedule finishes: an end date, a run count, or both
+///
+/// # Arguments
+/// * `end_time` - Last timestamp a run may be scheduled at (0 = no end date)
+/// * `max_executions` - Total runs allowed, counting runs already made (0 = unlimited)
+///
+/// # Errors
+/// * `InvalidTimestamp` - If `end_time` is before the next scheduled run
+/// * `InvalidAmount` - If `max_executions` is not above the runs already made
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+/// * `InvalidPlanConfig` - If the schedule is cancelled or finished
+pub fn set_autosave_limits(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    end_time: u64,
+    max_executions: u32,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if !schedule.is_active {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    if end_time != 0 && end_time < schedule.next_execution_time {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+    if max_executions != 0 && max_executions <= schedule.executions {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    schedule.end_time = end_time;
+    schedule.max_executions = max_executions;
+    save_schedule(env, &schedule);
+
+    Ok(())
+}
+
+/// Chooses whether missed intervals are run one by one (`true`) or skipped (`false`)
+///
+/// # Errors
+/// * `PlanNotFound` - If the schedule does not exist
+/// * `Unauthorized` - If the caller does not own the schedule
+pub fn set_autosave_catch_up(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    catch_up: bool,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    schedule.catch_up = catch_up;
+    save_schedule(env, &schedule);
+
+    Ok(())
+}
+
+/// Lists schedules that are due now, oldest bucket first.
+///
+/// Only buckets up to the current one are scanned, so the cost is bounded by
+/// the number of schedules that are actually due (plus those later in the
+/// current bucket).
+///
+/// # Arguments
+/// * `cursor` - Number of due schedules to skip, for paging through a large backlog
+/// * `limit` - Maximum number of schedule IDs to return
+pub fn get_due_autosaves(env: &Env, cursor: u32, limit: u32) -> Vec<u64> {
+    let now = env.ledger().timestamp();
+    let now_bucket = now / DUE_BUCKET_SECONDS;
+    let mut due = Vec::new(env);
+    let mut skipped: u32 = 0;
+
+    for page in get_pages(env).iter() {
+        if page > now_bucket / BUCKETS_PER_PAGE || due.len() >= limit {
+            break;
+        }
+        for bucket in get_bucket_page(env, page).iter() {
+            if bucket > now_bucket || due.len() >= limit {
+                break;
+            }
+            for schedule_id in get_bucket(env, bucket).iter() {
+                if due.len() >= limit {
+                    break;
+                }
+                let is_due = get_autosave(env, schedule_id)
+                    .is_some_and(|s| s.is_active && !s.is_paused && s.next_execution_time <= now);
+                if !is_due {
+                    continue;
+                }
+                if skipped < cursor {
+                    skipped += 1;
+                    continue;
+                }
+                due.push_back(schedule_id);
+            }
+        }
+    }
+
+    due
 }
 
 /// Cancels an AutoSave schedule
@@ -207,41 +574,62 @@ pub fn cancel_autosave(env: &Env, user: Address, schedule_id: u64) -> Result<(),
     user.require_auth();
 
     // Fetch the schedule
-    let mut schedule: AutoSave = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AutoSave(schedule_id))
-        .ok_or(SavingsError::PlanNotFound)?;
+    let mut schedule = get_autosave(env, schedule_id).ok_or(SavingsError::PlanNotFound)?;
 
     // Ensure caller owns the schedule
     if schedule.user != user {
         return Err(SavingsError::Unauthorized);
     }
 
-    // Deactivate the schedule
+    // Deactivate the schedule and drop it from the due index
     schedule.is_active = false;
+    unindex_schedule(env, schedule_id, schedule.next_execution_time);
+
+    // Refund any unspent keeper balance
+    let refund = schedule.keeper_balance;
+    schedule.keeper_balance = 0;
 
     // Save updated schedule
     env.storage()
         .persistent()
         .set(&DataKey::AutoSave(schedule_id), &schedule);
 
+    if refund > 0 {
+        custody::send(env, &assets::base_asset(env), &user, refund)?;
+    }
+
     Ok(())
 }
 
 /// Gets an AutoSave schedule by ID
+///
+/// Schedules stored by earlier versions are read as open-ended Flexi
+/// schedules without a keeper fee.
 pub fn get_autosave(env: &Env, schedule_id: u64) -> Option<AutoSave> {
-    let schedule = env
+    let raw: Map<Symbol, Val> = env
         .storage()
         .persistent()
-        .get(&DataKey::AutoSave(schedule_id));
+        .get(&DataKey::AutoSave(schedule_id))?;
 
-    if schedule.is_some() {
-        // Extend TTL on read
-        ttl::extend_autosave_ttl(env, schedule_id);
-    }
+    // Extend TTL on read
+    ttl::extend_autosave_ttl(env, schedule_id);
 
-    schedule
+    decode_versioned(env, raw, "target", |legacy: LegacyAutoSave| AutoSave {
+        id: legacy.id,
+        user: legacy.user,
+        target: AutoSaveTarget::Flexi,
+        amount: legacy.amount,
+        interval_seconds: legacy.interval_seconds,
+        next_execution_time: legacy.next_execution_time,
+        is_active: legacy.is_active,
+        is_paused: false,
+        end_time: 0,
+        max_executions: 0,
+        executions: 0,
+        catch_up: false,
+        keeper_fee: 0,
+        keeper_balance: 0,
+    })
 }
 
 /// Gets all AutoSave schedule IDs for a user
@@ -263,6 +651,280 @@ pub fn get_user_autosaves(env: &Env, user: &Address) -> Vec<u64> {
 
 // ========== Helper Functions ==========
 
+fn get_owned_schedule(
+    env: &Env,
+    user: &Address,
+    schedule_id: u64,
+) -> Result<AutoSave, SavingsError> {
+    let schedule = get_autosave(env, schedule_id).ok_or(SavingsError::PlanNotFound)?;
+    if schedule.user != *user {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(schedule)
+}
+
+fn save_schedule(env: &Env, schedule: &AutoSave) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AutoSave(schedule.id), schedule);
+    ttl::extend_autosave_ttl(env, schedule.id);
+}
+
+/// Records a completed run and moves `schedule` to its next run, re-indexing it
+/// under the new due time or finishing it once its limits are reached.
+fn advance_schedule(env: &Env, schedule: &mut AutoSave) {
+    unindex_schedule(env, schedule.id, schedule.next_execution_time);
+    schedule.executions = schedule.executions.saturating_add(1);
+
+    let mut next = schedule
+        .next_execution_time
+        .saturating_add(schedule.interval_seconds);
+    let now = env.ledger().timestamp();
+    if !schedule.catch_up && next <= now {
+        // Skip every interval that was missed while the schedule stalled
+        let missed = (now - next) / schedule.interval_seconds + 1;
+        next = next.saturating_add(missed.saturating_mul(schedule.interval_seconds));
+    }
+    schedule.next_execution_time = next;
+
+    if is_exhausted(schedule) {
+        finish_schedule(env, schedule);
+    } else {
+        index_schedule(env, schedule.id, schedule.next_execution_time);
+    }
+    save_schedule(env, schedule);
+}
+
+/// Whether the schedule has used up its run count or passed its end date
+fn is_exhausted(schedule: &AutoSave) -> bool {
+    (schedule.max_executions > 0 && schedule.executions >= schedule.max_executions)
+        || (schedule.end_time > 0 && schedule.next_execution_time > schedule.end_time)
+}
+
+/// Deactivates a schedule that ran to completion. Any unspent keeper balance
+/// stays on the schedule and is refunded by `cancel_autosave`.
+fn finish_schedule(env: &Env, schedule: &mut AutoSave) {
+    schedule.is_active = false;
+    env.events().publish(
+        (symbol_short!("as_done"), schedule.user.clone(), schedule.id),
+        schedule.executions,
+    );
+}
+
+fn get_pages(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AutoSaveKey::Pages)
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_bucket_page(env: &Env, page: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AutoSaveKey::BucketPage(page))
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_bucket(env: &Env, bucket: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AutoSaveKey::DueBucket(bucket))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Inserts `value` into the sorted list stored under `key`. Returns true if
+/// the list was empty before.
+fn insert_sorted(env: &Env, key: &AutoSaveKey, value: u64) -> bool {
+    let mut list: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    let was_empty = list.is_empty();
+    let mut pos = list.len();
+    for (i, existing) in list.iter().enumerate() {
+        if existing > value {
+            pos = i as u32;
+            break;
+        }
+    }
+    list.insert(pos, value);
+    env.storage().persistent().set(key, &list);
+    extend_index_ttl(env, key);
+    was_empty
+}
+
+/// Removes `value` from the sorted list stored under `key`. Returns true if
+/// the list is empty afterwards.
+fn remove_sorted(env: &Env, key: &AutoSaveKey, value: u64) -> bool {
+    let mut list: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    if let Some(index) = list.first_index_of(value) {
+        list.remove(index);
+    }
+    if list.is_empty() {
+        env.storage().persistent().remove(key);
+        return true;
+    }
+    env.storage().persistent().set(key, &list);
+    false
+}
+
+/// Adds a schedule to the bucket of its due time. A new bucket is listed in
+/// its page, and a new page in the page list, each kept sorted so scans can
+/// stop at the current bucket. Every list stays small: a bucket holds the
+/// schedules due within the hour, a page at most `BUCKETS_PER_PAGE` buckets.
+fn index_schedule(env: &Env, schedule_id: u64, due_time: u64) {
+    let bucket = due_time / DUE_BUCKET_SECONDS;
+    let key = AutoSaveKey::DueBucket(bucket);
+    let mut ids = get_bucket(env, bucket);
+
+    if ids.is_empty() {
+        let page = bucket / BUCKETS_PER_PAGE;
+        if insert_sorted(env, &AutoSaveKey::BucketPage(page), bucket) {
+            insert_sorted(env, &AutoSaveKey::Pages, page);
+        }
+    }
+
+    ids.push_back(schedule_id);
+    env.storage().persistent().set(&key, &ids);
+    extend_index_ttl(env, &key);
+}
+
+fn unindex_schedule(env: &Env, schedule_id: u64, due_time: u64) {
+    let bucket = due_time / DUE_BUCKET_SECONDS;
+    let key = AutoSaveKey::DueBucket(bucket);
+    let mut ids = get_bucket(env, bucket);
+    let Some(index) = ids.first_index_of(schedule_id) else {
+        return;
+    };
+    ids.remove(index);
+
+    if !ids.is_empty() {
+        env.storage().persistent().set(&key, &ids);
+        return;
+    }
+
+    env.storage().persistent().remove(&key);
+    let page = bucket / BUCKETS_PER_PAGE;
+    if remove_sorted(env, &AutoSaveKey::BucketPage(page), bucket) {
+        remove_sorted(env, &AutoSaveKey::Pages, page);
+    }
+}
+
+fn extend_index_ttl(env: &Env, key: &AutoSaveKey) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    }
+}
+
+/// Checks that `user` can direct recurring deposits of `amount` into `target`,
+/// in the asset each run will pull.
+fn validate_target(
+    env: &Env,
+    user: &Address,
+    target: &AutoSaveTarget,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    let asset = target_asset(env, target).ok_or(SavingsError::PlanNotFound)?;
+    assets::require_supported(env, &asset)?;
+
+    match target {
+        AutoSaveTarget::Flexi => Ok(()),
+        AutoSaveTarget::Lock(duration) => {
+            if *duration == 0 {
+                return Err(SavingsError::InvalidTimestamp);
+            }
+            rates::resolve_lock_rate(env, &asset, *duration)?;
+            Ok(())
+        }
+        AutoSaveTarget::Goal(goal_id) => {
+            let goal_save = goal::get_goal_save(env, *goal_id).ok_or(SavingsError::PlanNotFound)?;
+            if goal_save.owner != *user {
+                return Err(SavingsError::Unauthorized);
+            }
+            if goal_save.is_completed || goal_save.is_withdrawn {
+                return Err(SavingsError::PlanCompleted);
+            }
+            Ok(())
+        }
+        AutoSaveTarget::Group(group_id) => {
+            let group_save =
+                group::get_group_save(env, *group_id).ok_or(SavingsError::PlanNotFound)?;
+            if group_save.is_completed || group::is_settled(env, *group_id) {
+                return Err(SavingsError::PlanCompleted);
+            }
+            if !group::get_group_members(env, *group_id).contains(user) {
+                return Err(SavingsError::NotGroupMember);
+            }
+            // Rotation rounds always collect the fixed round amount
+            if let Some(rotation) = rosca::get_rotation(env, *group_id) {
+                if rotation.is_finished {
+                    return Err(SavingsError::PlanCompleted);
+                }
+                if amount != rotation.contribution_amount {
+                    return Err(SavingsError::InvalidAmount);
+                }
+                return Ok(());
+            }
+            // Percentage groups charge what is due, so any amount can cover it
+            match group_save.contribution_type {
+                group::CONTRIBUTION_FIXED if amount != group_save.contribution_amount => {
+                    Err(SavingsError::InvalidAmount)
+                }
+                group::CONTRIBUTION_FLEXIBLE if amount < group_save.contribution_amount => {
+                    Err(SavingsError::AmountBelowMinimum)
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+/// Routes one execution of `schedule` to the module that owns its target.
+///
+/// The saver authorized the schedule when creating it, so runs don't need
+/// their signature: funds are pulled through the allowance they granted the
+/// contract, and anyone can trigger a due run.
+fn deposit_to_target(env: &Env, schedule: &AutoSave) -> Result<(), SavingsError> {
+    let user = schedule.user.clone();
+    let funding = Funding::Scheduled;
+    match schedule.target {
+        AutoSaveTarget::Flexi => flexi::flexi_deposit_funded(
+            env.clone(),
+            user,
+            assets::base_asset(env),
+            schedule.amount,
+            funding,
+        ),
+        AutoSaveTarget::Lock(duration) => lock::open_lock_save(
+            env,
+            user,
+            assets::base_asset(env),
+            schedule.amount,
+            duration,
+            funding,
+        )
+        .map(|_| ()),
+        AutoSaveTarget::Goal(goal_id) => {
+            goal::deposit_to_goal_save_funded(env, user, goal_id, schedule.amount, funding)
+        }
+        AutoSaveTarget::Group(group_id) => {
+            if rosca::is_rotating(env, group_id) {
+                rosca::contribute_to_round_funded(env, user, group_id, funding).map(|_| ())
+            } else {
+                group::contribute_funded(env, user, group_id, schedule.amount, funding)
+            }
+        }
+    }
+}
+
+/// Asset pulled from the saver when `target` executes, if the target still exists.
+fn target_asset(env: &Env, target: &AutoSaveTarget) -> Option<Address> {
+    match target {
+        AutoSaveTarget::Flexi | AutoSaveTarget::Lock(_) => Some(assets::base_asset(env)),
+        AutoSaveTarget::Goal(goal_id) => goal::get_goal_save(env, *goal_id).map(|g| g.asset),
+        AutoSaveTarget::Group(group_id) => group::get_group_save(env, *group_id).map(|g| g.asset),
+    }
+}
+
 fn get_next_schedule_id(env: &Env) -> u64 {
     let counter_key = DataKey::NextAutoSaveId;
     let id = env.storage().persistent().get(&counter_key).unwrap_or(1);
@@ -1,5 +1,6 @@
 use crate::errors::SavingsError;
 use crate::storage_types::DataKey;
+use crate::ttl;
 use soroban_sdk::{contracttype, symbol_short, Address, Env};
 
 /// Maximum fee in basis points (100% = 10000 bps)
@@ -18,6 +19,8 @@ pub struct Config {
     pub withdrawal_fee_bps: u32,
     pub performance_fee_bps: u32,
     pub paused: bool,
+    /// SEP-41 token held in custody for all savings plans (`None` until configured)
+    pub underlying_asset: Option<Address>,
 }
 
 // ========== Admin Verification ==========
@@ -159,6 +162,8 @@ pub fn get_config(env: &Env) -> Result<Config, SavingsError> {
         .get(&DataKey::Paused)
         .unwrap_or(false);
 
+    let underlying_asset = get_underlying_asset(env);
+
     Ok(Config {
         admin,
         treasury,
@@ -166,9 +171,50 @@ pub fn get_config(env: &Env) -> Result<Config, SavingsError> {
         withdrawal_fee_bps,
         performance_fee_bps,
         paused,
+        underlying_asset,
     })
 }
 
+/// Sets the SEP-41 token that backs every savings plan.
+///
+/// Once set, deposits pull this token from the saver and withdrawals pay it
+/// back out. The asset can only be configured once, and only before any
+/// deposit has been credited in accounting-only mode: those balances were
+/// never backed by tokens, and keyed by the contract's own address they would
+/// be stranded once the base asset changes.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `admin` - The admin calling this function
+/// * `asset` - Address of the token contract (e.g. a Stellar Asset Contract)
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not the admin
+/// * `SavingsError::ConfigAlreadyInitialized` - If an asset is already
+///   configured, or accounting-only balances exist
+pub fn set_underlying_asset(env: &Env, admin: Address, asset: Address) -> Result<(), SavingsError> {
+    require_admin(env, &admin)?;
+
+    if env.storage().instance().has(&DataKey::UnderlyingAsset)
+        || crate::custody::accounting_used(env)
+    {
+        return Err(SavingsError::ConfigAlreadyInitialized);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::UnderlyingAsset, &asset);
+
+    env.events().publish((symbol_short!("set_asset"),), asset);
+
+    Ok(())
+}
+
+/// Returns the configured underlying asset, if any.
+pub fn get_underlying_asset(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::UnderlyingAsset)
+}
+
 /// Updates the protocol treasury address.
 ///
 /// # Arguments
@@ -180,15 +226,18 @@ pub fn get_config(env: &Env) -> Result<Config, SavingsError> {
 /// * `SavingsError::Unauthorized` - If caller is not the admin
 pub fn set_treasury(env: &Env, admin: Address, new_treasury: Address) -> Result<(), SavingsError> {
     require_admin(env, &admin)?;
+    write_treasury(env, new_treasury);
+    Ok(())
+}
 
+/// Stores the treasury address; callers are responsible for authorization.
+pub(crate) fn write_treasury(env: &Env, new_treasury: Address) {
     env.storage()
         .instance()
         .set(&DataKey::TreasuryAddress, &new_treasury);
 
     env.events()
         .publish((symbol_short!("set_trs"),), new_treasury);
-
-    Ok(())
 }
 
 /// Updates the protocol fee in basis points.
@@ -209,7 +258,16 @@ pub fn set_fees(
     performance_fee: u32,
 ) -> Result<(), SavingsError> {
     require_admin(env, &admin)?;
+    write_fees(env, deposit_fee, withdrawal_fee, performance_fee)
+}
 
+/// Validates and stores the protocol fees; callers are responsible for authorization.
+pub(crate) fn write_fees(
+    env: &Env,
+    deposit_fee: u32,
+    withdrawal_fee: u32,
+    performance_fee: u32,
+) -> Result<(), SavingsError> {
     if deposit_fee > MAX_FEE_BPS || withdrawal_fee > MAX_FEE_BPS || performance_fee > MAX_FEE_BPS {
         return Err(SavingsError::InvalidFeeBps);
     }
@@ -240,12 +298,17 @@ pub fn set_fees(
 /// * `SavingsError::Unauthorized` - If caller is not the admin
 pub fn pause_contract(env: &Env, admin: Address) -> Result<(), SavingsError> {
     require_admin(env, &admin)?;
+    set_paused(env, admin);
+    Ok(())
+}
 
+/// Pauses the contract on behalf of `by`; callers are responsible for
+/// authorization.
+pub(crate) fn set_paused(env: &Env, by: Address) {
     env.storage().persistent().set(&DataKey::Paused, &true);
+    ttl::extend_config_ttl(env, &DataKey::Paused);
 
-    env.events().publish((symbol_short!("pause"),), admin);
-
-    Ok(())
+    env.events().publish((symbol_short!("pause"),), by);
 }
 
 /// Unpauses the contract, restoring all state-changing operations.
@@ -0,0 +1,148 @@
+use crate::assets;
+use crate::errors::SavingsError;
+use crate::storage_types::DataKey;
+use soroban_sdk::{contracttype, token, Address, Env};
+
+/// Storage keys for custody bookkeeping
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CustodyKey {
+    /// Set once funds have been credited in the accounting-only unit
+    AccountingUsed,
+}
+
+/// How a deposit is authorized and its funds pulled from the saver.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Funding {
+    /// The saver authorizes the call and the token transfer
+    Signed,
+    /// A scheduled deposit that anyone may run on the saver's behalf. The
+    /// saver authorized the schedule itself; funds are pulled through the
+    /// allowance the saver granted the contract.
+    Scheduled,
+}
+
+impl Funding {
+    /// Requires `user`'s authorization unless the deposit is scheduled.
+    pub fn authorize(self, user: &Address) {
+        if self == Funding::Signed {
+            user.require_auth();
+        }
+    }
+}
+
+/// Pulls `amount` of `asset` from `from` into the contract.
+///
+/// Requires `from` to have authorized the transfer. When `asset` is the
+/// accounting-only unit (no underlying asset configured) this is a no-op.
+///
+/// # Errors
+/// * `InvalidAmount` - If amount is negative
+pub fn receive(
+    env: &Env,
+    asset: &Address,
+    from: &Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    if amount < 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+    if assets::is_accounting_unit(env, asset) {
+        mark_accounting_used(env);
+        return Ok(());
+    }
+
+    token::Client::new(env, asset).transfer(from, env.current_contract_address(), &amount);
+
+    Ok(())
+}
+
+/// Pulls `amount` of `asset` from `from` as `funding` allows: a signed
+/// transfer, or a draw on the allowance `from` granted the contract.
+///
+/// # Errors
+/// * `InvalidAmount` - If amount is negative
+pub fn receive_funded(
+    env: &Env,
+    asset: &Address,
+    from: &Address,
+    amount: i128,
+    funding: Funding,
+) -> Result<(), SavingsError> {
+    if funding == Funding::Signed {
+        return receive(env, asset, from, amount);
+    }
+    if amount < 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+    if assets::is_accounting_unit(env, asset) {
+        mark_accounting_used(env);
+        return Ok(());
+    }
+
+    let contract = env.current_contract_address();
+    token::Client::new(env, asset).transfer_from(&contract, from, &contract, &amount);
+
+    Ok(())
+}
+
+/// Pays `amount` of `asset` out of the contract to `to`.
+///
+/// Callers must persist all state changes before invoking this so that the
+/// token call is the last interaction (CEI). No-op for the accounting-only unit.
+///
+/// # Errors
+/// * `InvalidAmount` - If amount is negative
+pub fn send(env: &Env, asset: &Address, to: &Address, amount: i128) -> Result<(), SavingsError> {
+    if amount < 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if amount == 0 || assets::is_accounting_unit(env, asset) {
+        return Ok(());
+    }
+
+    token::Client::new(env, asset).transfer(&env.current_contract_address(), to, &amount);
+
+    Ok(())
+}
+
+/// Returns true if a scheduled pull of `amount` of `asset` from `from` would
+/// succeed: `from` holds it and has allowed the contract to draw it.
+///
+/// Batch paths use this to skip an underfunded saver instead of letting the
+/// token transfer trap the whole invocation. Always true in accounting-only mode.
+pub fn can_receive_scheduled(env: &Env, asset: &Address, from: &Address, amount: i128) -> bool {
+    if assets::is_accounting_unit(env, asset) {
+        return true;
+    }
+    let token = token::Client::new(env, asset);
+    token.balance(from) >= amount
+        && token.allowance(from, &env.current_contract_address()) >= amount
+}
+
+/// Returns true if balances may have been credited in the accounting-only unit.
+///
+/// Plans created before this was tracked are detected through the plan ID
+/// counters, which only exist once a plan has been opened.
+pub fn accounting_used(env: &Env) -> bool {
+    let instance = env.storage().instance();
+    let persistent = env.storage().persistent();
+    instance.has(&CustodyKey::AccountingUsed)
+        || persistent.has(&DataKey::NextLockId)
+        || persistent.has(&DataKey::NextGoalId)
+        || persistent.has(&DataKey::NextGroupId)
+}
+
+fn mark_accounting_used(env: &Env) {
+    if !env.storage().instance().has(&CustodyKey::AccountingUsed) {
+        env.storage()
+            .instance()
+            .set(&CustodyKey::AccountingUsed, &true);
+    }
+}
@@ -239,6 +239,10 @@ pub enum SavingsError {
     ///
     /// E.g. the actual returned amount is 0 or negative when a positive value was expected.
     InvalidStrategyResponse = 98,
+
+    /// Returned when a member contributes to a rotating group round they
+    /// have already paid.
+    RoundAlreadyPaid = 99,
 }
 
 #[cfg(test)]
@@ -289,6 +293,7 @@ mod tests {
             SavingsError::StrategyDisabled as u32,
             SavingsError::ReentrancyDetected as u32,
             SavingsError::InvalidStrategyResponse as u32,
+            SavingsError::RoundAlreadyPaid as u32,
         ];
 
         let mut sorted = errors.clone();
@@ -2,10 +2,10 @@
 mod execution_tests {
     use crate::governance::ProposalAction;
     use crate::rewards::storage_types::RewardsConfig;
-    use crate::{NesteraContract, NesteraContractClient, PlanType};
+    use crate::{NesteraContract, NesteraContractClient, PlanType, SavingsError};
     use soroban_sdk::{
         testutils::{Address as _, Ledger},
-        Address, BytesN, Env, String,
+        vec, Address, BytesN, Env, String,
     };
 
     fn setup_contract() -> (Env, NesteraContractClient<'static>, Address) {
@@ -317,4 +317,123 @@ mod execution_tests {
 
         assert!(client.is_paused());
     }
+
+    /// Creates `action` as a proposal, votes it through and queues it past the timelock
+    fn pass_action(
+        env: &Env,
+        client: &NesteraContractClient<'static>,
+        action: &ProposalAction,
+    ) -> u64 {
+        let creator = Address::generate(env);
+        client.initialize_user(&creator);
+        let _ = client.create_savings_plan(&creator, &PlanType::Flexi, &1000);
+
+        let description = String::from_str(env, "Governed change");
+        let proposal_id = client.create_action_proposal(&creator, &description, action);
+        client.vote(&proposal_id, &1, &creator);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += 604800 + 1;
+        });
+        client.queue_proposal(&proposal_id);
+        env.ledger().with_mut(|li| {
+            li.timestamp += 86400 + 1;
+        });
+
+        proposal_id
+    }
+
+    #[test]
+    fn test_execute_batch_admin_actions() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+
+        let treasury = Address::generate(&env);
+        let strategy = Address::generate(&env);
+        let action = ProposalAction::Batch(vec![
+            &env,
+            ProposalAction::SetFees(50, 25, 1000),
+            ProposalAction::SetTreasury(treasury.clone()),
+            ProposalAction::RegisterStrategy(strategy.clone(), 2),
+            ProposalAction::SetEarlyBreakFeeBps(300),
+        ]);
+        let proposal_id = pass_action(&env, &client, &action);
+        client.execute_proposal(&proposal_id);
+
+        let config = client.get_config();
+        assert_eq!(config.deposit_fee_bps, 50);
+        assert_eq!(config.withdrawal_fee_bps, 25);
+        assert_eq!(config.performance_fee_bps, 1000);
+        assert_eq!(config.treasury, treasury);
+        assert!(client.get_strategy(&strategy).enabled);
+    }
+
+    #[test]
+    fn test_failing_batch_rolls_back_every_action() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+
+        // The allocation split does not sum to 100%
+        let action = ProposalAction::Batch(vec![
+            &env,
+            ProposalAction::SetFees(50, 25, 1000),
+            ProposalAction::AllocateTreasury(5000, 1000, 1000),
+        ]);
+        let proposal_id = pass_action(&env, &client, &action);
+
+        assert_eq!(
+            client.try_execute_proposal(&proposal_id),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        assert_eq!(client.get_config().deposit_fee_bps, 0);
+        assert!(!client.get_action_proposal(&proposal_id).unwrap().executed);
+    }
+
+    #[test]
+    fn test_execute_strategy_disable_action() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+
+        let strategy = Address::generate(&env);
+        client.register_strategy(&admin, &strategy, &1);
+
+        let proposal_id = pass_action(
+            &env,
+            &client,
+            &ProposalAction::DisableStrategy(strategy.clone()),
+        );
+        client.execute_proposal(&proposal_id);
+
+        assert!(!client.get_strategy(&strategy).enabled);
+    }
+
+    #[test]
+    fn test_malformed_batch_is_rejected() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+
+        let creator = Address::generate(&env);
+        client.initialize_user(&creator);
+        let _ = client.create_savings_plan(&creator, &PlanType::Flexi, &1000);
+        let description = String::from_str(&env, "Bad batch");
+
+        let empty = ProposalAction::Batch(vec![&env]);
+        assert_eq!(
+            client
+                .try_create_action_proposal(&creator, &description, &empty)
+                .unwrap_err(),
+            Ok(SavingsError::InvalidPlanConfig)
+        );
+
+        let nested = ProposalAction::Batch(vec![
+            &env,
+            ProposalAction::Batch(vec![&env, ProposalAction::PauseContract]),
+        ]);
+        assert_eq!(
+            client
+                .try_create_action_proposal(&creator, &description, &nested)
+                .unwrap_err(),
+            Ok(SavingsError::InvalidPlanConfig)
+        );
+    }
 }
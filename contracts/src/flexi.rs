@@ -1,24 +1,94 @@
 // New/Correct
+use crate::assets;
 use crate::calculate_fee;
+use crate::custody::{self, Funding};
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
+use crate::governance;
 use crate::invariants;
+use crate::rates;
 use crate::rewards;
 use crate::storage_types::{DataKey, User};
+use crate::treasury;
 use crate::ttl;
-use soroban_sdk::{symbol_short, Address, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+/// Fixed-point scale of the Flexi interest index (1e18 = one unit of principal)
+pub const INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Storage keys for Flexi Save interest accounting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FlexiKey {
+    /// Global interest index per asset
+    Index(Address),
+    /// Maps (user, asset) to the user's accrual checkpoint
+    Checkpoint(Address, Address),
+    /// Whether a user compounds Flexi interest into principal
+    AutoCompound(Address),
+}
+
+/// Flexi balance key as stored before balances were kept per asset; it has
+/// the same encoding as the old `DataKey::FlexiBalance(user)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum LegacyFlexiKey {
+    FlexiBalance(Address),
+}
+
+/// Cumulative interest earned by one unit of principal (scaled by `INDEX_SCALE`)
+/// since the index was created.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlexiIndex {
+    pub value: i128,
+    pub last_update: u64,
+}
+
+/// A user's position against the global index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlexiCheckpoint {
+    /// Index value at the user's last accrual
+    pub index: i128,
+    /// Interest accrued but not yet claimed
+    pub accrued: i128,
+}
 
 /// Handles depositing funds into the Flexi Save pool.
 pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), SavingsError> {
+    let asset = assets::base_asset(&env);
+    flexi_deposit_asset(env, user, asset, amount)
+}
+
+/// Handles depositing funds of a whitelisted asset into the Flexi Save pool.
+pub fn flexi_deposit_asset(
+    env: Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    flexi_deposit_funded(env, user, asset, amount, Funding::Signed)
+}
+
+/// Deposits into the Flexi Save pool, authorized and pulled as `funding` says.
+pub(crate) fn flexi_deposit_funded(
+    env: Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    funding: Funding,
+) -> Result<(), SavingsError> {
     ensure_not_paused(&env)?;
 
-    // 1. Verify the caller is the user
-    user.require_auth();
+    // 1. Verify the caller is the user (or runs the user's schedule)
+    funding.authorize(&user);
 
-    // 2. Validate the amount
+    // 2. Validate the amount and asset
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
     }
+    assets::require_supported(&env, &asset)?;
 
     // 3. Calculate protocol fee
     let fee_bps: u32 = env
@@ -32,8 +102,9 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
-    // 4. Update the specific Flexi balance with net amount
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+    // 4. Settle interest at the old balance, then add the net amount
+    accrue_interest(&env, &user, &asset)?;
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
     let current_flexi_balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
 
     let new_flexi_balance = current_flexi_balance
@@ -55,8 +126,12 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
         return Err(SavingsError::UserNotFound);
     }
 
+    // Pull the gross amount (net + fee) into contract custody
+    custody::receive_funded(&env, &asset, &user, amount, funding)?;
+
     // Extend TTL on user interaction
     ttl::extend_user_ttl(&env, &user);
+    ttl::extend_flexi_ttl(&env, &user, &asset);
 
     // 6. Award deposit points (streak, rewards)
     rewards::storage::award_deposit_points(&env, user.clone(), amount)?;
@@ -82,7 +157,12 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
                 .publish((symbol_short!("dep_fee"), fee_recipient), fee_amount);
         }
         // Record fee in treasury struct
-        crate::treasury::record_fee(&env, fee_amount, soroban_sdk::Symbol::new(&env, "deposit"));
+        crate::treasury::record_fee(
+            &env,
+            &asset,
+            fee_amount,
+            soroban_sdk::Symbol::new(&env, "deposit"),
+        );
     }
 
     Ok(())
@@ -90,6 +170,19 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
 
 /// Handles withdrawing funds from the Flexi Save pool.
 pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), SavingsError> {
+    let asset = assets::base_asset(&env);
+    flexi_withdraw_asset(env, user, asset, amount)
+}
+
+/// Handles withdrawing funds of a given asset from the Flexi Save pool.
+///
+/// Withdrawals stay open for assets that were removed from the whitelist.
+pub fn flexi_withdraw_asset(
+    env: Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
     ensure_not_paused(&env)?;
 
     // 1. Verify the caller is the user
@@ -100,8 +193,9 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
         return Err(SavingsError::InvalidAmount);
     }
 
-    // 1. Fetch the balance first
-    let current_balance = get_flexi_balance(&env, user.clone()).unwrap_or(0);
+    // 1. Settle interest, then fetch the balance
+    accrue_interest(&env, &user, &asset)?;
+    let current_balance = get_flexi_asset_balance(&env, user.clone(), asset.clone()).unwrap_or(0);
 
     // 2. Now the variable 'current_balance' exists in this scope
     invariants::assert_sufficient_balance(current_balance, amount)?;
@@ -114,12 +208,12 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
         .unwrap_or(0);
 
     let fee_amount = calculate_fee(amount, fee_bps)?;
-    let _net_amount = amount
+    let net_amount = amount
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
     // 4. Check and update the specific Flexi balance
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
     let current_flexi_balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
 
     if current_flexi_balance < amount {
@@ -145,6 +239,7 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
 
     // Extend TTL on user interaction
     ttl::extend_user_ttl(&env, &user);
+    ttl::extend_flexi_ttl(&env, &user, &asset);
 
     // 6. Transfer fee to treasury if fee > 0
     if fee_amount > 0 {
@@ -167,14 +262,34 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
                 .publish((symbol_short!("wth_fee"), fee_recipient), fee_amount);
         }
         // Record fee in treasury struct
-        crate::treasury::record_fee(&env, fee_amount, soroban_sdk::Symbol::new(&env, "withdraw"));
+        crate::treasury::record_fee(
+            &env,
+            &asset,
+            fee_amount,
+            soroban_sdk::Symbol::new(&env, "withdraw"),
+        );
     }
 
+    governance::record_withdrawal_power(&env, &user, amount)?;
+
+    // 7. Pay out the net amount; the fee stays in custody for the fee recipient
+    custody::send(&env, &asset, &user, net_amount)?;
+
     Ok(())
 }
 /// Returns the user's Flexi Save balance.
 /// This is a read-only (view) function.
 pub fn get_flexi_balance(env: &Env, user: Address) -> Result<i128, SavingsError> {
+    get_flexi_asset_balance(env, user, assets::base_asset(env))
+}
+
+/// Returns the user's Flexi Save balance in `asset`.
+/// This is a read-only (view) function.
+pub fn get_flexi_asset_balance(
+    env: &Env,
+    user: Address,
+    asset: Address,
+) -> Result<i128, SavingsError> {
     // 1. Ensure user exists
     let user_key = DataKey::User(user.clone());
     let _user: User = env
@@ -183,12 +298,18 @@ pub fn get_flexi_balance(env: &Env, user: Address) -> Result<i128, SavingsError>
         .get(&user_key)
         .ok_or(SavingsError::UserNotFound)?;
 
-    // 2. Read flexi balance (default to 0)
-    let flexi_key = DataKey::FlexiBalance(user.clone());
-    let balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
+    // 2. Read flexi balance (default to 0), including interest that will
+    //    be compounded at the next accrual
+    let mut balance = stored_balance(env, &user, &asset);
+    if is_auto_compound(env, &user) {
+        let pending = pending_interest(env, &user, &asset, balance)?
+            .min(treasury::reserve_available_in(env, &asset));
+        balance = balance.checked_add(pending).ok_or(SavingsError::Overflow)?;
+    }
 
     // Extend TTL on read
     ttl::extend_user_ttl(env, &user);
+    ttl::extend_flexi_ttl(env, &user, &asset);
 
     Ok(balance)
 }
@@ -196,8 +317,7 @@ pub fn get_flexi_balance(env: &Env, user: Address) -> Result<i128, SavingsError>
 /// Returns true if the user has a non-zero Flexi Save balance.
 /// This function does not mutate storage.
 pub fn has_flexi_balance(env: &Env, user: Address) -> bool {
-    let flexi_key = DataKey::FlexiBalance(user.clone());
-    let balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
+    let balance = stored_balance(env, &user, &assets::base_asset(env));
 
     // Extend TTL on read
     ttl::extend_user_ttl(env, &user);
@@ -205,10 +325,302 @@ pub fn has_flexi_balance(env: &Env, user: Address) -> bool {
     balance > 0
 }
 
+/// A user's Flexi balance in `asset` as stored, counting a base-asset
+/// balance stored by earlier versions that has not been migrated yet.
+pub(crate) fn stored_balance(env: &Env, user: &Address, asset: &Address) -> i128 {
+    let balance: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::FlexiBalance(user.clone(), asset.clone()))
+        .unwrap_or(0);
+    if *asset != assets::base_asset(env) {
+        return balance;
+    }
+    let legacy: i128 = env
+        .storage()
+        .persistent()
+        .get(&LegacyFlexiKey::FlexiBalance(user.clone()))
+        .unwrap_or(0);
+    balance.saturating_add(legacy)
+}
+
+/// Moves a base-asset balance stored by earlier versions under the user
+/// alone to its per-asset key. Does nothing for other assets or when there
+/// is no such balance.
+fn migrate_legacy_balance(env: &Env, user: &Address, asset: &Address) -> Result<(), SavingsError> {
+    if *asset != assets::base_asset(env) {
+        return Ok(());
+    }
+    let legacy_key = LegacyFlexiKey::FlexiBalance(user.clone());
+    let legacy: i128 = match env.storage().persistent().get(&legacy_key) {
+        Some(balance) => balance,
+        None => return Ok(()),
+    };
+    env.storage().persistent().remove(&legacy_key);
+
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
+    let balance: i128 = env.storage().persistent().get(&flexi_key).unwrap_or(0);
+    env.storage().persistent().set(
+        &flexi_key,
+        &balance.checked_add(legacy).ok_or(SavingsError::Overflow)?,
+    );
+    Ok(())
+}
+
+// ========== Interest Accrual ==========
+
+/// Returns the interest index for `asset` brought forward to the current ledger time.
+fn projected_index(env: &Env, asset: &Address) -> FlexiIndex {
+    let now = env.ledger().timestamp();
+    let mut index: FlexiIndex = env
+        .storage()
+        .persistent()
+        .get(&FlexiKey::Index(asset.clone()))
+        .unwrap_or(FlexiIndex {
+            value: 0,
+            last_update: now,
+        });
+
+    let elapsed = now.saturating_sub(index.last_update);
+    if elapsed > 0 {
+        let rate = rates::get_flexi_rate_for(env, asset);
+        index.value =
+            index
+                .value
+                .saturating_add(rates::calculate_flexi_interest(INDEX_SCALE, rate, elapsed));
+        index.last_update = now;
+    }
+    index
+}
+
+/// Advances and stores the interest index for `asset` at the current rate.
+///
+/// Must run before the Flexi rate changes so that the new rate only applies
+/// from this point on.
+pub fn update_index(env: &Env, asset: &Address) -> i128 {
+    let index = projected_index(env, asset);
+    let key = FlexiKey::Index(asset.clone());
+    env.storage().persistent().set(&key, &index);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    index.value
+}
+
+fn get_checkpoint(env: &Env, user: &Address, asset: &Address) -> FlexiCheckpoint {
+    env.storage()
+        .persistent()
+        .get(&FlexiKey::Checkpoint(user.clone(), asset.clone()))
+        .unwrap_or(FlexiCheckpoint {
+            index: 0,
+            accrued: 0,
+        })
+}
+
+fn interest_between(balance: i128, from_index: i128, to_index: i128) -> Result<i128, SavingsError> {
+    if balance <= 0 || to_index <= from_index {
+        return Ok(0);
+    }
+    Ok(balance
+        .checked_mul(to_index - from_index)
+        .ok_or(SavingsError::Overflow)?
+        / INDEX_SCALE)
+}
+
+/// Interest earned on `balance` since the user's last checkpoint, not yet recorded.
+fn pending_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    balance: i128,
+) -> Result<i128, SavingsError> {
+    let checkpoint = get_checkpoint(env, user, asset);
+    interest_between(balance, checkpoint.index, projected_index(env, asset).value)
+}
+
+/// Settles a user's Flexi interest in `asset` up to now.
+///
+/// Must run before every change to the user's Flexi balance. Earned interest
+/// is either added to principal (auto-compound) or held as claimable.
+/// Compounded interest is paid from the treasury reserve; whatever the
+/// reserve cannot cover is held as claimable instead.
+pub fn accrue_interest(env: &Env, user: &Address, asset: &Address) -> Result<(), SavingsError> {
+    migrate_legacy_balance(env, user, asset)?;
+    let index = update_index(env, asset);
+    let mut checkpoint = get_checkpoint(env, user, asset);
+    if checkpoint.index == index {
+        return Ok(());
+    }
+
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
+    let balance: i128 = env.storage().persistent().get(&flexi_key).unwrap_or(0);
+    let mut earned = interest_between(balance, checkpoint.index, index)?;
+    checkpoint.index = index;
+
+    if earned > 0 && is_auto_compound(env, user) {
+        let compounded = treasury::draw_reserve_in(env, asset, earned);
+        earned -= compounded;
+        if compounded > 0 {
+            treasury::record_yield(env, asset, compounded);
+            let new_balance = balance
+                .checked_add(compounded)
+                .ok_or(SavingsError::Overflow)?;
+            env.storage().persistent().set(&flexi_key, &new_balance);
+
+            let user_key = DataKey::User(user.clone());
+            if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key)
+            {
+                user_data.total_balance = user_data
+                    .total_balance
+                    .checked_add(compounded)
+                    .ok_or(SavingsError::Overflow)?;
+                env.storage().persistent().set(&user_key, &user_data);
+            }
+
+            env.events().publish(
+                (symbol_short!("flx_cmp"), user.clone(), asset.clone()),
+                compounded,
+            );
+        }
+    }
+    if earned > 0 {
+        checkpoint.accrued = checkpoint
+            .accrued
+            .checked_add(earned)
+            .ok_or(SavingsError::Overflow)?;
+    }
+
+    let key = FlexiKey::Checkpoint(user.clone(), asset.clone());
+    env.storage().persistent().set(&key, &checkpoint);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    Ok(())
+}
+
+/// Returns the Flexi interest the user has earned in `asset` and not yet
+/// received, including interest earned since their last checkpoint. Claims
+/// pay out as much of it as the treasury reserve covers.
+///
+/// # Errors
+/// * `Overflow` - If the interest does not fit in an `i128`
+pub fn get_flexi_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+) -> Result<i128, SavingsError> {
+    let checkpoint = get_checkpoint(env, user, asset);
+    let balance = stored_balance(env, user, asset);
+    let mut pending = pending_interest(env, user, asset, balance)?;
+    if is_auto_compound(env, user) {
+        // The part the reserve covers is compounded instead
+        pending -= pending.min(treasury::reserve_available_in(env, asset));
+    }
+    checkpoint
+        .accrued
+        .checked_add(pending)
+        .ok_or(SavingsError::Overflow)
+}
+
+/// Pays out the user's accrued Flexi interest in `asset`, as far as the
+/// treasury reserve covers it. The rest stays claimable.
+///
+/// # Errors
+/// * `UserNotFound` - If the user is not registered
+pub fn claim_flexi_interest(
+    env: &Env,
+    user: Address,
+    asset: Address,
+) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    if !env.storage().persistent().has(&DataKey::User(user.clone())) {
+        return Err(SavingsError::UserNotFound);
+    }
+
+    accrue_interest(env, &user, &asset)?;
+
+    let key = FlexiKey::Checkpoint(user.clone(), asset.clone());
+    let mut checkpoint = get_checkpoint(env, &user, &asset);
+    let amount = treasury::draw_reserve_in(env, &asset, checkpoint.accrued);
+    if amount == 0 {
+        return Ok(0);
+    }
+    checkpoint.accrued -= amount;
+    env.storage().persistent().set(&key, &checkpoint);
+    treasury::record_yield(env, &asset, amount);
+
+    ttl::extend_user_ttl(env, &user);
+
+    env.events().publish(
+        (symbol_short!("flx_int"), user.clone(), asset.clone()),
+        amount,
+    );
+
+    custody::send(env, &asset, &user, amount)?;
+
+    Ok(amount)
+}
+
+/// Returns true if the user compounds Flexi interest into principal.
+pub fn is_auto_compound(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&FlexiKey::AutoCompound(user.clone()))
+        .unwrap_or(false)
+}
+
+/// Switches between claiming Flexi interest and compounding it into principal.
+///
+/// Interest earned so far is settled under the previous mode in every asset.
+/// Already accrued claimable interest stays claimable.
+///
+/// # Errors
+/// * `UserNotFound` - If the user is not registered
+pub fn set_flexi_auto_compound(
+    env: &Env,
+    user: Address,
+    enabled: bool,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    if !env.storage().persistent().has(&DataKey::User(user.clone())) {
+        return Err(SavingsError::UserNotFound);
+    }
+
+    let base = assets::base_asset(env);
+    accrue_interest(env, &user, &base)?;
+    for asset in assets::get_all_assets(env).iter() {
+        if asset != base {
+            accrue_interest(env, &user, &asset)?;
+        }
+    }
+
+    let key = FlexiKey::AutoCompound(user.clone());
+    env.storage().persistent().set(&key, &enabled);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{NesteraContract, NesteraContractClient};
-    use soroban_sdk::{testutils::Address as _, Address, Env};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        Address, Env,
+    };
+
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+
+    fn advance(env: &Env, seconds: u64) {
+        env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
 
     fn setup_admin_env() -> (Env, NesteraContractClient<'static>, Address) {
         let env = Env::default();
@@ -332,4 +744,140 @@ mod tests {
         assert_eq!(client.get_flexi_balance(&user), 50);
         assert_eq!(client.get_protocol_fee_balance(&treasury), 0);
     }
+
+    #[test]
+    fn test_flexi_interest_accrues_at_rate() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.set_flexi_rate(&admin, &500); // 5% APY
+
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        let asset = client.get_supported_assets().get(0).unwrap();
+        assert_eq!(client.get_flexi_interest(&user, &asset), 500);
+        // Principal is unchanged in claim mode
+        assert_eq!(client.get_flexi_balance(&user), 10_000);
+    }
+
+    #[test]
+    fn test_flexi_rate_change_applies_only_going_forward() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.set_flexi_rate(&admin, &500);
+        client.deposit_flexi(&user, &10_000);
+
+        advance(&env, YEAR / 2);
+        client.set_flexi_rate(&admin, &1_000);
+        advance(&env, YEAR / 2);
+
+        // 250 at 5% for half a year + 500 at 10% for half a year
+        let asset = client.get_supported_assets().get(0).unwrap();
+        assert_eq!(client.get_flexi_interest(&user, &asset), 750);
+    }
+
+    #[test]
+    fn test_claim_flexi_interest_resets_accrual() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.set_flexi_rate(&admin, &500);
+        client.fund_reserve(&admin, &1_000);
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        assert_eq!(client.claim_flexi_interest(&user), 500);
+        assert_eq!(client.claim_flexi_interest(&user), 0);
+        assert_eq!(client.get_flexi_balance(&user), 10_000);
+        assert_eq!(client.get_reserve_balance(), 500);
+    }
+
+    #[test]
+    fn test_claim_flexi_interest_is_capped_at_reserve() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.set_flexi_rate(&admin, &500);
+        client.fund_reserve(&admin, &200);
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        // Only the funded part is paid; the rest stays claimable
+        let asset = client.get_supported_assets().get(0).unwrap();
+        assert_eq!(client.claim_flexi_interest(&user), 200);
+        assert_eq!(client.get_flexi_interest(&user, &asset), 300);
+        assert_eq!(client.get_reserve_balance(), 0);
+
+        client.fund_reserve(&admin, &300);
+        assert_eq!(client.claim_flexi_interest(&user), 300);
+        assert_eq!(client.get_flexi_interest(&user, &asset), 0);
+    }
+
+    #[test]
+    fn test_deposit_does_not_earn_backdated_interest() {
+        let (env, client, admin) = setup_admin_env();
+        let early = Address::generate(&env);
+        let late = Address::generate(&env);
+        client.initialize_user(&early);
+        client.initialize_user(&late);
+        client.set_flexi_rate(&admin, &500);
+
+        client.deposit_flexi(&early, &10_000);
+        advance(&env, YEAR);
+        client.deposit_flexi(&late, &10_000);
+
+        let asset = client.get_supported_assets().get(0).unwrap();
+        assert_eq!(client.get_flexi_interest(&early, &asset), 500);
+        assert_eq!(client.get_flexi_interest(&late, &asset), 0);
+    }
+
+    #[test]
+    fn test_flexi_auto_compound_adds_interest_to_principal() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.set_flexi_rate(&admin, &500);
+        client.set_flexi_auto_compound(&user, &true);
+        client.fund_reserve(&admin, &1_000);
+
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        assert_eq!(client.get_flexi_balance(&user), 10_500);
+        client.withdraw_flexi(&user, &10_500);
+        assert_eq!(client.get_flexi_balance(&user), 0);
+        assert_eq!(client.claim_flexi_interest(&user), 0);
+        assert_eq!(client.get_reserve_balance(), 500);
+    }
+
+    #[test]
+    fn test_unfunded_auto_compound_interest_stays_claimable() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.set_flexi_rate(&admin, &500);
+        client.set_flexi_auto_compound(&user, &true);
+
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        // Nothing in the reserve to compound from
+        let asset = client.get_supported_assets().get(0).unwrap();
+        assert_eq!(client.get_flexi_balance(&user), 10_000);
+        assert_eq!(client.get_flexi_interest(&user, &asset), 500);
+        client.withdraw_flexi(&user, &10_000);
+
+        client.fund_reserve(&admin, &500);
+        assert_eq!(client.claim_flexi_interest(&user), 500);
+    }
+
+    #[test]
+    fn test_interest_overflow_is_an_error() {
+        assert_eq!(
+            super::interest_between(i128::MAX, 0, 2),
+            Err(crate::errors::SavingsError::Overflow)
+        );
+    }
 }
@@ -1,19 +1,57 @@
-use soroban_sdk::{symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Val, Vec};
 
+use crate::assets;
 use crate::calculate_fee;
+use crate::custody::{self, Funding};
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
+use crate::governance;
+use crate::rates;
 use crate::rewards::storage;
-use crate::storage_types::{DataKey, GoalSave, User};
+use crate::storage_types::{decode_versioned, DataKey, GoalSave, LegacyGoalSave, User};
+use crate::treasury;
 use crate::ttl;
 use crate::users;
 
+/// Progress thresholds, in percent of target, that award milestone points
+const MILESTONES: [u32; 3] = [25, 50, 75];
+
+/// Storage keys for Goal Save bookkeeping
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GoalKey {
+    /// Maps goal ID to the milestone points it has awarded, revoked if the
+    /// goal is abandoned
+    MilestonePoints(u64),
+    /// Interest of a closed goal the treasury reserve could not cover yet
+    UnpaidInterest(u64),
+}
+
 pub fn create_goal_save(
     env: &Env,
     user: Address,
     goal_name: soroban_sdk::Symbol,
     target_amount: i128,
     initial_deposit: i128,
+) -> Result<u64, SavingsError> {
+    create_asset_goal_save(
+        env,
+        user,
+        assets::base_asset(env),
+        goal_name,
+        target_amount,
+        initial_deposit,
+    )
+}
+
+/// Creates a Goal Save plan denominated in a whitelisted asset
+pub fn create_asset_goal_save(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    goal_name: soroban_sdk::Symbol,
+    target_amount: i128,
+    initial_deposit: i128,
 ) -> Result<u64, SavingsError> {
     ensure_not_paused(env)?;
     user.require_auth();
@@ -26,10 +64,14 @@ pub fn create_goal_save(
         return Err(SavingsError::InvalidAmount);
     }
 
+    assets::require_supported(env, &asset)?;
+
     if !users::user_exists(env, &user) {
         return Err(SavingsError::UserNotFound);
     }
 
+    custody::receive(env, &asset, &user, initial_deposit)?;
+
     // Calculate protocol fee on initial deposit
     let fee_bps: u32 = env
         .storage()
@@ -44,22 +86,32 @@ pub fn create_goal_save(
 
     let current_time = env.ledger().timestamp();
     let goal_id = get_next_goal_id(env);
+    let interest_rate = u32::try_from(rates::get_goal_rate_for(env, &asset))
+        .map_err(|_| SavingsError::InvalidInterestRate)?;
 
-    let goal_save = GoalSave {
+    let mut goal_save = GoalSave {
         id: goal_id,
         owner: user.clone(),
+        asset,
         goal_name: goal_name.clone(),
         target_amount,
         current_amount: net_initial_deposit,
-        interest_rate: 500,
+        interest_rate,
+        accrued_interest: 0,
+        last_accrual: current_time,
         start_time: current_time,
+        deadline: 0,
+        milestones_reached: 0,
+        auto_payout: false,
         is_completed: net_initial_deposit >= target_amount,
         is_withdrawn: false,
     };
+    check_milestones(env, &mut goal_save)?;
 
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
+    adjust_total_balance(env, &user, net_initial_deposit)?;
 
     if goal_save.is_completed {
         storage::award_goal_completion_bonus(env, user.clone())?;
@@ -88,7 +140,12 @@ pub fn create_goal_save(
             );
         }
         // Record fee in treasury struct
-        crate::treasury::record_fee(env, fee_amount, soroban_sdk::Symbol::new(env, "deposit"));
+        crate::treasury::record_fee(
+            env,
+            &goal_save.asset,
+            fee_amount,
+            soroban_sdk::Symbol::new(env, "deposit"),
+        );
     }
 
     add_goal_to_user(env, &user, goal_id);
@@ -109,9 +166,20 @@ pub fn deposit_to_goal_save(
     user: Address,
     goal_id: u64,
     amount: i128,
+) -> Result<(), SavingsError> {
+    deposit_to_goal_save_funded(env, user, goal_id, amount, Funding::Signed)
+}
+
+/// Deposits into a goal, authorized and pulled as `funding` says.
+pub(crate) fn deposit_to_goal_save_funded(
+    env: &Env,
+    user: Address,
+    goal_id: u64,
+    amount: i128,
+    funding: Funding,
 ) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
-    user.require_auth();
+    funding.authorize(&user);
 
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
@@ -127,6 +195,9 @@ pub fn deposit_to_goal_save(
         return Err(SavingsError::PlanCompleted);
     }
 
+    assets::require_supported(env, &goal_save.asset)?;
+    custody::receive_funded(env, &goal_save.asset, &user, amount, funding)?;
+
     // Calculate protocol fee
     let fee_bps: u32 = env
         .storage()
@@ -139,6 +210,9 @@ pub fn deposit_to_goal_save(
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
+    // Interest up to now accrues on the balance before this deposit
+    accrue_interest(env, &mut goal_save)?;
+
     goal_save.current_amount = goal_save
         .current_amount
         .checked_add(net_amount)
@@ -148,10 +222,12 @@ pub fn deposit_to_goal_save(
     if goal_save.current_amount >= goal_save.target_amount {
         goal_save.is_completed = true;
     }
+    check_milestones(env, &mut goal_save)?;
 
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
+    adjust_total_balance(env, &user, net_amount)?;
 
     if !was_completed && goal_save.is_completed {
         storage::award_goal_completion_bonus(env, user.clone())?;
@@ -184,10 +260,19 @@ pub fn deposit_to_goal_save(
             );
         }
         // Record fee in treasury struct
-        crate::treasury::record_fee(env, fee_amount, soroban_sdk::Symbol::new(env, "deposit"));
+        crate::treasury::record_fee(
+            env,
+            &goal_save.asset,
+            fee_amount,
+            soroban_sdk::Symbol::new(env, "deposit"),
+        );
     }
     storage::award_deposit_points(env, user.clone(), amount)?;
 
+    if goal_save.is_completed && goal_save.auto_payout {
+        pay_out_completed(env, &user, goal_save)?;
+    }
+
     Ok(())
 }
 
@@ -203,7 +288,7 @@ pub fn withdraw_completed_goal_save(
         return Err(SavingsError::UserNotFound);
     }
 
-    let mut goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    let goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
 
     if goal_save.owner != user {
         return Err(SavingsError::Unauthorized);
@@ -217,6 +302,22 @@ pub fn withdraw_completed_goal_save(
         return Err(SavingsError::PlanCompleted);
     }
 
+    pay_out_completed(env, &user, goal_save)
+}
+
+/// Pays a completed goal and its accrued interest out to its owner, net of
+/// the withdrawal fee.
+fn pay_out_completed(
+    env: &Env,
+    user: &Address,
+    mut goal_save: GoalSave,
+) -> Result<i128, SavingsError> {
+    let goal_id = goal_save.id;
+    let gross_amount = goal_save
+        .current_amount
+        .checked_add(fund_interest(env, &goal_save, goal_save.accrued_interest)?)
+        .ok_or(SavingsError::Overflow)?;
+
     // Calculate protocol fee on withdrawal
     let fee_bps: u32 = env
         .storage()
@@ -224,9 +325,8 @@ pub fn withdraw_completed_goal_save(
         .get(&DataKey::WithdrawalFeeBps)
         .unwrap_or(0);
 
-    let fee_amount = calculate_fee(goal_save.current_amount, fee_bps)?;
-    let net_amount = goal_save
-        .current_amount
+    let fee_amount = calculate_fee(gross_amount, fee_bps)?;
+    let net_amount = gross_amount
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
@@ -235,19 +335,14 @@ pub fn withdraw_completed_goal_save(
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
+    governance::record_withdrawal_power(env, user, goal_save.current_amount)?;
 
-    let user_key = DataKey::User(user.clone());
-    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
-        user_data.total_balance = user_data
-            .total_balance
-            .checked_add(net_amount)
-            .ok_or(SavingsError::Overflow)?;
-        env.storage().persistent().set(&user_key, &user_data);
-    }
+    // The principal leaves the user's savings
+    adjust_total_balance(env, &goal_save.owner, -goal_save.current_amount)?;
 
     // Extend TTL (withdrawn goals get shorter extension)
     ttl::extend_goal_ttl(env, goal_id);
-    ttl::extend_user_ttl(env, &user);
+    ttl::extend_user_ttl(env, user);
 
     // Transfer fee to treasury if fee > 0
     if fee_amount > 0 {
@@ -272,9 +367,16 @@ pub fn withdraw_completed_goal_save(
             );
         }
         // Record fee in treasury struct
-        crate::treasury::record_fee(env, fee_amount, soroban_sdk::Symbol::new(env, "withdraw"));
+        crate::treasury::record_fee(
+            env,
+            &goal_save.asset,
+            fee_amount,
+            soroban_sdk::Symbol::new(env, "withdraw"),
+        );
     }
 
+    custody::send(env, &goal_save.asset, user, net_amount)?;
+
     Ok(net_amount)
 }
 
@@ -320,25 +422,26 @@ pub fn break_goal_save(env: &Env, user: Address, goal_id: u64) -> Result<i128, S
             / 10_000
     };
 
+    accrue_interest(env, &mut goal_save)?;
+    let kept_interest = fund_interest(env, &goal_save, forfeit_interest(env, &goal_save)?)?;
+
     let net_amount = goal_save
         .current_amount
         .checked_sub(fee_amount)
-        .ok_or(SavingsError::Underflow)?;
+        .ok_or(SavingsError::Underflow)?
+        .checked_add(kept_interest)
+        .ok_or(SavingsError::Overflow)?;
 
     goal_save.is_withdrawn = true;
 
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
+    revoke_milestone_points(env, &goal_save);
+    governance::record_withdrawal_power(env, &user, goal_save.current_amount)?;
 
-    let user_key = DataKey::User(user.clone());
-    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
-        user_data.total_balance = user_data
-            .total_balance
-            .checked_add(net_amount)
-            .ok_or(SavingsError::Overflow)?;
-        env.storage().persistent().set(&user_key, &user_data);
-    }
+    // The principal leaves the user's savings
+    adjust_total_balance(env, &goal_save.owner, -goal_save.current_amount)?;
 
     if fee_amount > 0 {
         if let Some(fee_recipient) = env
@@ -378,16 +481,419 @@ pub fn break_goal_save(env: &Env, user: Address, goal_id: u64) -> Result<i128, S
     ttl::extend_goal_ttl(env, goal_id);
     ttl::extend_user_ttl(env, &user);
 
+    custody::send(env, &goal_save.asset, &user, net_amount)?;
+
     Ok(net_amount)
 }
 
-pub fn get_goal_save(env: &Env, goal_id: u64) -> Option<GoalSave> {
-    let goal_save = env.storage().persistent().get(&DataKey::GoalSave(goal_id));
-    if goal_save.is_some() {
-        // Extend TTL on read
-        ttl::extend_goal_ttl(env, goal_id);
+/// Sets, moves or clears (`deadline = 0`) a goal's target date.
+///
+/// Once a deadline has been missed this is how the owner extends it.
+///
+/// # Errors
+/// * `PlanNotFound` - If the goal does not exist
+/// * `Unauthorized` - If the caller does not own the goal
+/// * `PlanCompleted` - If the goal is completed or withdrawn
+/// * `InvalidTimestamp` - If a non-zero deadline is not in the future
+pub fn set_goal_deadline(
+    env: &Env,
+    user: Address,
+    goal_id: u64,
+    deadline: u64,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let mut goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    if goal_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+    if goal_save.is_completed || goal_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+    if deadline != 0 && deadline <= env.ledger().timestamp() {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+
+    let was_missed = is_deadline_missed(env, &goal_save);
+    goal_save.deadline = deadline;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalSave(goal_id), &goal_save);
+    ttl::extend_goal_ttl(env, goal_id);
+
+    env.events().publish(
+        (symbol_short!("goal_ddl"), user, goal_id),
+        (deadline, was_missed),
+    );
+
+    Ok(())
+}
+
+/// Releases a goal whose deadline passed before the target was reached.
+///
+/// The full balance is returned without the `EarlyBreakFeeBps` penalty.
+/// Accrued interest is forfeited as on `break_goal_save`.
+///
+/// # Errors
+/// * `PlanNotFound` - If the goal does not exist
+/// * `Unauthorized` - If the caller does not own the goal
+/// * `PlanCompleted` - If the goal was already withdrawn
+/// * `TooEarly` - If the goal has no missed deadline
+pub fn release_missed_goal_save(
+    env: &Env,
+    user: Address,
+    goal_id: u64,
+) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let mut goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    if goal_save.owner != user {
+        return Err(SavingsError::Unauthorized);
     }
+    if goal_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+    if !is_deadline_missed(env, &goal_save) {
+        return Err(SavingsError::TooEarly);
+    }
+
+    accrue_interest(env, &mut goal_save)?;
+    let amount = goal_save
+        .current_amount
+        .checked_add(fund_interest(
+            env,
+            &goal_save,
+            forfeit_interest(env, &goal_save)?,
+        )?)
+        .ok_or(SavingsError::Overflow)?;
+    goal_save.is_withdrawn = true;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalSave(goal_id), &goal_save);
+    revoke_milestone_points(env, &goal_save);
+    governance::record_withdrawal_power(env, &user, goal_save.current_amount)?;
+    adjust_total_balance(env, &user, -goal_save.current_amount)?;
+
+    env.events()
+        .publish((symbol_short!("goal_rls"), user.clone(), goal_id), amount);
+
+    remove_goal_from_user(env, &user, goal_id);
+
+    // Extend TTL (withdrawn goals get shorter extension)
+    ttl::extend_goal_ttl(env, goal_id);
+    ttl::extend_user_ttl(env, &user);
+
+    custody::send(env, &goal_save.asset, &user, amount)?;
+
+    Ok(amount)
+}
+
+/// Opts a goal in or out of paying out automatically once its target is reached.
+///
+/// # Errors
+/// * `PlanNotFound` - If the goal does not exist
+/// * `Unauthorized` - If the caller does not own the goal
+/// * `PlanCompleted` - If the goal was already withdrawn
+pub fn set_goal_auto_payout(
+    env: &Env,
+    user: Address,
+    goal_id: u64,
+    enabled: bool,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let mut goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    if goal_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+    if goal_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    goal_save.auto_payout = enabled;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalSave(goal_id), &goal_save);
+    ttl::extend_goal_ttl(env, goal_id);
+
+    env.events()
+        .publish((symbol_short!("goal_auto"), user, goal_id), enabled);
+
+    Ok(())
+}
+
+/// Returns true if the goal's deadline passed before it reached its target.
+pub fn is_deadline_missed(env: &Env, goal_save: &GoalSave) -> bool {
+    goal_save.deadline != 0
+        && env.ledger().timestamp() > goal_save.deadline
+        && !goal_save.is_completed
+        && !goal_save.is_withdrawn
+}
+
+/// Projects when the goal will reach its target at the average deposit rate
+/// since it was created.
+///
+/// Returns the current time for completed goals and `None` when there is no
+/// deposit history to extrapolate from yet.
+pub fn get_projected_completion(env: &Env, goal_id: u64) -> Result<Option<u64>, SavingsError> {
+    let goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    let now = env.ledger().timestamp();
+    if goal_save.is_completed {
+        return Ok(Some(now));
+    }
+
+    let elapsed = now.saturating_sub(goal_save.start_time);
+    if elapsed == 0 || goal_save.current_amount <= 0 {
+        return Ok(None);
+    }
+
+    // remaining / (current / elapsed), rounded up
+    let remaining = goal_save.target_amount - goal_save.current_amount;
+    let numerator = remaining
+        .checked_mul(elapsed as i128)
+        .ok_or(SavingsError::Overflow)?;
+    let seconds_left = (numerator + goal_save.current_amount - 1) / goal_save.current_amount;
+    let seconds_left = u64::try_from(seconds_left).map_err(|_| SavingsError::Overflow)?;
+
+    Ok(Some(now.saturating_add(seconds_left)))
+}
+
+/// Returns the interest a goal has earned so far, including the amount
+/// accrued since the last deposit.
+pub fn get_goal_interest(env: &Env, goal_id: u64) -> Result<i128, SavingsError> {
+    let mut goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    accrue_interest(env, &mut goal_save)?;
+    Ok(goal_save.accrued_interest)
+}
+
+/// Accrues simple interest on the goal balance since `last_accrual`.
+///
+/// Interest stops once the goal is completed or withdrawn.
+fn accrue_interest(env: &Env, goal_save: &mut GoalSave) -> Result<(), SavingsError> {
+    let now = env.ledger().timestamp();
+    if !goal_save.is_completed && !goal_save.is_withdrawn {
+        let elapsed = now.saturating_sub(goal_save.last_accrual);
+        let interest = rates::calculate_flexi_interest(
+            goal_save.current_amount,
+            goal_save.interest_rate as i128,
+            elapsed,
+        );
+        goal_save.accrued_interest = goal_save
+            .accrued_interest
+            .checked_add(interest)
+            .ok_or(SavingsError::Overflow)?;
+    }
+    goal_save.last_accrual = now;
+    Ok(())
+}
+
+/// Applies `GoalInterestForfeitBps` to the accrued interest, emitting the
+/// forfeited part, and returns the interest the owner keeps.
+fn forfeit_interest(env: &Env, goal_save: &GoalSave) -> Result<i128, SavingsError> {
+    let forfeit_bps: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::GoalInterestForfeitBps)
+        .unwrap_or(10_000);
+
+    let forfeited = calculate_fee(goal_save.accrued_interest, forfeit_bps)?;
+    if forfeited > 0 {
+        env.events().publish(
+            (
+                symbol_short!("gint_lost"),
+                goal_save.owner.clone(),
+                goal_save.id,
+            ),
+            forfeited,
+        );
+    }
+
     goal_save
+        .accrued_interest
+        .checked_sub(forfeited)
+        .ok_or(SavingsError::Underflow)
+}
+
+/// Moves the owner's `total_balance` by a change in goal principal.
+///
+/// Goals opened before goal principal was counted were never added, so
+/// removals stop at zero.
+fn adjust_total_balance(env: &Env, owner: &Address, delta: i128) -> Result<(), SavingsError> {
+    let user_key = DataKey::User(owner.clone());
+    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
+        user_data.total_balance = user_data
+            .total_balance
+            .checked_add(delta)
+            .ok_or(SavingsError::Overflow)?
+            .max(0);
+        env.storage().persistent().set(&user_key, &user_data);
+    }
+    Ok(())
+}
+
+/// Draws a goal's interest from the treasury reserve. Whatever the reserve
+/// cannot cover is held as unpaid interest of the goal, claimable later via
+/// `claim_goal_interest`.
+///
+/// # Returns
+/// The interest funded now.
+fn fund_interest(env: &Env, goal_save: &GoalSave, interest: i128) -> Result<i128, SavingsError> {
+    if interest <= 0 {
+        return Ok(0);
+    }
+    let funded = treasury::draw_reserve_in(env, &goal_save.asset, interest);
+    treasury::record_yield(env, &goal_save.asset, funded);
+    if funded < interest {
+        let unpaid = get_unpaid_interest(env, goal_save.id)
+            .checked_add(interest - funded)
+            .ok_or(SavingsError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&GoalKey::UnpaidInterest(goal_save.id), &unpaid);
+    }
+    Ok(funded)
+}
+
+/// Returns the interest of a closed goal still waiting on the reserve.
+pub fn get_unpaid_interest(env: &Env, goal_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&GoalKey::UnpaidInterest(goal_id))
+        .unwrap_or(0)
+}
+
+/// Pays out a closed goal's unpaid interest as far as the treasury reserve
+/// covers it. The rest stays claimable.
+///
+/// # Errors
+/// * `PlanNotFound` - If the goal does not exist
+/// * `Unauthorized` - If the caller does not own the goal
+pub fn claim_goal_interest(env: &Env, user: Address, goal_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    if goal_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    let unpaid = get_unpaid_interest(env, goal_id);
+    let amount = treasury::draw_reserve_in(env, &goal_save.asset, unpaid);
+    if amount == 0 {
+        return Ok(0);
+    }
+    let key = GoalKey::UnpaidInterest(goal_id);
+    if amount == unpaid {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &(unpaid - amount));
+    }
+    treasury::record_yield(env, &goal_save.asset, amount);
+
+    env.events()
+        .publish((symbol_short!("goal_int"), user.clone(), goal_id), amount);
+
+    custody::send(env, &goal_save.asset, &user, amount)?;
+
+    Ok(amount)
+}
+
+/// Records newly crossed milestones, emitting an event and awarding points
+/// for each one. Completed goals earn the completion bonus instead. The
+/// points are tallied per goal so abandoning the goal can revoke them.
+fn check_milestones(env: &Env, goal_save: &mut GoalSave) -> Result<(), SavingsError> {
+    if goal_save.is_completed {
+        return Ok(());
+    }
+
+    let progress = goal_save
+        .current_amount
+        .checked_mul(100)
+        .ok_or(SavingsError::Overflow)?
+        / goal_save.target_amount;
+
+    for milestone in MILESTONES {
+        if goal_save.milestones_reached < milestone && progress >= milestone as i128 {
+            goal_save.milestones_reached = milestone;
+            env.events().publish(
+                (
+                    symbol_short!("goal_ms"),
+                    goal_save.owner.clone(),
+                    goal_save.id,
+                ),
+                milestone,
+            );
+            let points = storage::award_goal_milestone_bonus(env, goal_save.owner.clone())?;
+            if points > 0 {
+                let key = GoalKey::MilestonePoints(goal_save.id);
+                let total: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+                env.storage().persistent().set(
+                    &key,
+                    &total.checked_add(points).ok_or(SavingsError::Overflow)?,
+                );
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Takes back the milestone points of a goal that was broken or released
+/// without completing.
+fn revoke_milestone_points(env: &Env, goal_save: &GoalSave) {
+    let key = GoalKey::MilestonePoints(goal_save.id);
+    if let Some(points) = env.storage().persistent().get::<_, u128>(&key) {
+        env.storage().persistent().remove(&key);
+        storage::revoke_goal_milestone_bonus(env, goal_save.owner.clone(), points);
+    }
+}
+
+/// Reads a Goal Save, upgrading one stored by earlier versions (see
+/// `LegacyGoalSave`) to a base-asset goal without a deadline that accrues
+/// interest from its start and has already passed the milestones it reached.
+pub fn get_goal_save(env: &Env, goal_id: u64) -> Option<GoalSave> {
+    let raw: Map<Symbol, Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::GoalSave(goal_id))?;
+    // Extend TTL on read
+    ttl::extend_goal_ttl(env, goal_id);
+    decode_versioned(env, raw, "asset", |legacy: LegacyGoalSave| {
+        let progress = if legacy.target_amount > 0 {
+            legacy.current_amount.saturating_mul(100) / legacy.target_amount
+        } else {
+            0
+        };
+        let milestones_reached = MILESTONES
+            .iter()
+            .rev()
+            .copied()
+            .find(|milestone| progress >= *milestone as i128)
+            .unwrap_or(0);
+        GoalSave {
+            id: legacy.id,
+            owner: legacy.owner,
+            asset: assets::base_asset(env),
+            goal_name: legacy.goal_name,
+            target_amount: legacy.target_amount,
+            current_amount: legacy.current_amount,
+            interest_rate: legacy.interest_rate,
+            accrued_interest: 0,
+            last_accrual: legacy.start_time,
+            start_time: legacy.start_time,
+            deadline: 0,
+            milestones_reached,
+            auto_payout: false,
+            is_completed: legacy.is_completed,
+            is_withdrawn: legacy.is_withdrawn,
+        }
+    })
 }
 
 pub fn get_user_goal_saves(env: &Env, user: &Address) -> Vec<u64> {
@@ -457,7 +963,7 @@ mod tests {
     use crate::rewards::storage_types::RewardsConfig;
     use crate::{NesteraContract, NesteraContractClient};
     use soroban_sdk::{
-        testutils::{Address as _, Events},
+        testutils::{Address as _, Events, Ledger},
         Address, BytesN, Env, IntoVal, Symbol,
     };
 
@@ -993,12 +1499,13 @@ mod tests {
         client.deposit_to_goal_save(&user, &goal_id, &1_000);
         let rewards_after_completion = client.get_user_rewards(&user);
         // Base points: (4000 + 1000) * 10 = 50000
+        // Milestones 25/50/75 on create: 3 * (250 / 4) = 186
         // Completion bonus: 250
-        assert_eq!(rewards_after_completion.total_points, 50250);
+        assert_eq!(rewards_after_completion.total_points, 50436);
 
         let _ = client.withdraw_completed_goal_save(&user, &goal_id);
         let rewards_after_withdraw = client.get_user_rewards(&user);
-        assert_eq!(rewards_after_withdraw.total_points, 50250);
+        assert_eq!(rewards_after_withdraw.total_points, 50436);
     }
 
     #[test]
@@ -1017,7 +1524,8 @@ mod tests {
 
         let rewards = client.get_user_rewards(&user);
         // Base points: 4999 * 10 = 49990
-        assert_eq!(rewards.total_points, 49990);
+        // Milestones 25/50/75: 3 * (250 / 4) = 186
+        assert_eq!(rewards.total_points, 50176);
     }
 
     #[test]
@@ -1073,4 +1581,240 @@ mod tests {
         // Base points: 2000 * 10 = 20000
         assert_eq!(rewards.total_points, 20000);
     }
+
+    #[test]
+    fn test_goal_milestones_award_points_once() {
+        let (env, client) = setup_test_env();
+        setup_rewards(&client, &env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "trip"), &10_000, &0);
+
+        client.deposit_to_goal_save(&user, &goal_id, &2_500);
+        assert_eq!(client.get_goal_save_detail(&goal_id).milestones_reached, 25);
+        // Base points: 2500 * 10 = 25000, milestone: 250 / 4
+        assert_eq!(client.get_user_rewards(&user).total_points, 25_062);
+
+        // One deposit crossing 50% and 75%
+        client.deposit_to_goal_save(&user, &goal_id, &5_000);
+        assert_eq!(client.get_goal_save_detail(&goal_id).milestones_reached, 75);
+
+        let rewards = client.get_user_rewards(&user);
+        // Base points: 7500 * 10 = 75000, milestones: 3 * 62
+        assert_eq!(rewards.total_points, 75_186);
+    }
+
+    #[test]
+    fn test_breaking_goal_revokes_milestone_points() {
+        let (env, client) = setup_test_env();
+        setup_rewards(&client, &env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "car"), &10_000, &0);
+        client.deposit_to_goal_save(&user, &goal_id, &5_000);
+        // Base points: 5000 * 10 = 50000, milestones: 2 * 62
+        assert_eq!(client.get_user_rewards(&user).total_points, 50_124);
+
+        client.break_goal_save(&user, &goal_id);
+        assert_eq!(client.get_user_rewards(&user).total_points, 50_000);
+    }
+
+    #[test]
+    fn test_releasing_missed_goal_revokes_milestone_points() {
+        let (env, client) = setup_test_env();
+        setup_rewards(&client, &env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "gym"), &10_000, &2_500);
+        client.set_goal_deadline(&user, &goal_id, &1_000);
+        assert_eq!(client.get_user_rewards(&user).total_points, 25_062);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_001);
+        client.release_missed_goal_save(&user, &goal_id);
+        assert_eq!(client.get_user_rewards(&user).total_points, 25_000);
+    }
+
+    #[test]
+    fn test_missed_deadline_releases_without_break_fee() {
+        let (env, client, _admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        assert!(client.try_set_fee_recipient(&treasury).is_ok());
+        assert!(client.try_set_early_break_fee_bps(&500).is_ok());
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "phone"), &10_000, &2_000);
+        client.set_goal_deadline(&user, &goal_id, &1_000);
+
+        // Not missed yet
+        assert!(client
+            .try_release_missed_goal_save(&user, &goal_id)
+            .is_err());
+
+        env.ledger().with_mut(|li| li.timestamp = 1_001);
+        assert!(client.is_goal_deadline_missed(&goal_id));
+
+        assert_eq!(client.release_missed_goal_save(&user, &goal_id), 2_000);
+        assert_eq!(client.get_protocol_fee_balance(&treasury), 0);
+        assert!(client.get_goal_save_detail(&goal_id).is_withdrawn);
+    }
+
+    #[test]
+    fn test_extending_missed_deadline_reopens_goal() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "course"), &10_000, &500);
+        client.set_goal_deadline(&user, &goal_id, &100);
+        env.ledger().with_mut(|li| li.timestamp = 200);
+        assert!(client.is_goal_deadline_missed(&goal_id));
+
+        // Deadlines must be in the future
+        assert!(client.try_set_goal_deadline(&user, &goal_id, &150).is_err());
+
+        client.set_goal_deadline(&user, &goal_id, &10_000);
+        assert!(!client.is_goal_deadline_missed(&goal_id));
+        assert!(client
+            .try_release_missed_goal_save(&user, &goal_id)
+            .is_err());
+    }
+
+    #[test]
+    fn test_projected_completion_uses_average_deposit_rate() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "bike"), &10_000, &1_000);
+        assert_eq!(client.get_goal_projected_completion(&goal_id), None);
+
+        // 1,000 saved in 100s: 9,000 left takes another 900s
+        env.ledger().with_mut(|li| li.timestamp += 100);
+        let start = client.get_goal_save_detail(&goal_id).start_time;
+        assert_eq!(
+            client.get_goal_projected_completion(&goal_id),
+            Some(start + 1_000)
+        );
+    }
+
+    #[test]
+    fn test_auto_payout_withdraws_on_completion() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "sofa"), &3_000, &1_000);
+        client.set_goal_auto_payout(&user, &goal_id, &true);
+        client.deposit_to_goal_save(&user, &goal_id, &2_000);
+
+        let goal_save = client.get_goal_save_detail(&goal_id);
+        assert!(goal_save.is_completed);
+        assert!(goal_save.is_withdrawn);
+    }
+
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+
+    #[test]
+    fn test_goal_interest_rolls_into_completion_payout() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        client.initialize_user(&user);
+        client.set_goal_rate(&admin, &1_000); // 10%
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "roof"), &10_000, &5_000);
+        assert_eq!(client.get_goal_save_detail(&goal_id).interest_rate, 1_000);
+
+        env.ledger().with_mut(|li| li.timestamp += YEAR);
+        assert_eq!(client.get_goal_interest(&goal_id), 500);
+
+        client.deposit_to_goal_save(&user, &goal_id, &5_000);
+        assert_eq!(client.get_user(&user).total_balance, 10_000);
+        // Completed goals stop accruing
+        env.ledger().with_mut(|li| li.timestamp += YEAR);
+        assert_eq!(client.get_goal_interest(&goal_id), 500);
+
+        // Interest is paid as far as the reserve covers it, the rest later
+        client.fund_reserve(&user, &200);
+        assert_eq!(client.withdraw_completed_goal_save(&user, &goal_id), 10_200);
+        assert_eq!(client.get_unpaid_goal_interest(&goal_id), 300);
+        assert_eq!(client.claim_goal_interest(&user, &goal_id), 0);
+        // The principal paid out leaves the user's balance
+        assert_eq!(client.get_user(&user).total_balance, 0);
+
+        client.fund_reserve(&user, &1_000);
+        assert_eq!(client.claim_goal_interest(&user, &goal_id), 300);
+        assert_eq!(client.get_unpaid_goal_interest(&goal_id), 0);
+        assert_eq!(client.get_reserve_balance(), 700);
+    }
+
+    #[test]
+    fn test_goal_interest_is_time_weighted_across_deposits() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        client.initialize_user(&user);
+        client.set_goal_rate(&admin, &1_000);
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "van"), &100_000, &1_000);
+        env.ledger().with_mut(|li| li.timestamp += YEAR / 2);
+        client.deposit_to_goal_save(&user, &goal_id, &9_000);
+        env.ledger().with_mut(|li| li.timestamp += YEAR / 2);
+
+        // 1,000 for half a year plus 10,000 for half a year
+        assert_eq!(client.get_goal_interest(&goal_id), 50 + 500);
+    }
+
+    #[test]
+    fn test_break_goal_forfeits_interest() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        client.initialize_user(&user);
+        client.set_goal_rate(&admin, &1_000);
+
+        let full = client.create_goal_save(&user, &Symbol::new(&env, "full"), &10_000, &5_000);
+        let half = client.create_goal_save(&user, &Symbol::new(&env, "half"), &10_000, &5_000);
+        env.ledger().with_mut(|li| li.timestamp += YEAR);
+
+        // Everything is forfeited by default
+        assert_eq!(client.get_goal_interest_forfeit_bps(), 10_000);
+        assert_eq!(client.break_goal_save(&user, &full), 5_000);
+
+        assert!(client.try_set_goal_interest_forfeit_bps(&5_000).is_ok());
+        client.fund_reserve(&user, &200);
+        assert_eq!(client.break_goal_save(&user, &half), 5_200);
+        assert_eq!(client.get_unpaid_goal_interest(&half), 50);
+    }
+
+    #[test]
+    fn test_goal_keeps_rate_from_creation() {
+        let (env, client, admin) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        client.initialize_user(&user);
+        client.set_goal_rate(&admin, &800);
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "tv"), &10_000, &1_000);
+
+        client.set_goal_rate(&admin, &200);
+        assert_eq!(client.get_goal_save_detail(&goal_id).interest_rate, 800);
+    }
 }
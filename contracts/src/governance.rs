@@ -18,7 +18,7 @@ pub fn get_active_proposals(env: &Env) -> Vec<u64> {
     let all = list_proposals(env);
     for pid in all.iter() {
         if let Some(p) = get_proposal(env, pid.clone()) {
-            if !p.executed && now >= p.start_time && now <= p.end_time {
+            if !p.executed && !p.cancelled && now >= p.start_time && now <= p.end_time {
                 active.push_back(pid.clone());
             }
         }
@@ -37,8 +37,8 @@ pub fn get_proposal_votes(env: &Env, proposal_id: u64) -> (u128, u128, u128) {
 use crate::errors::SavingsError;
 use crate::governance_events::*;
 use crate::rewards::storage::get_user_rewards;
-use crate::storage_types::DataKey;
-use soroban_sdk::{contracttype, Address, Env, String, Vec};
+use crate::storage_types::{DataKey, LockSave};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, TryFromVal, Val, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -54,6 +54,7 @@ pub struct ActionProposal {
     pub abstain_votes: u128,
     pub action: ProposalAction,
     pub queued_time: u64,
+    pub cancelled: bool,
 }
 
 #[contracttype]
@@ -69,18 +70,70 @@ pub struct Proposal {
     pub against_votes: u128,
     pub abstain_votes: u128,
     pub queued_time: u64,
+    pub cancelled: bool,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VotingConfig {
+    /// Share of the total voting supply (bps) that must vote for a proposal to pass
     pub quorum: u32,
     pub voting_period: u64,
     pub timelock_duration: u64,
     pub proposal_threshold: u128,
     pub max_voting_power: u128,
+    /// How long a queued proposal stays executable once its timelock ends
+    pub grace_period: u64,
+    /// How snapshot power turns into vote weight
+    pub weighting: VoteWeighting,
 }
 
+/// Vote weighting modes
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteWeighting {
+    /// One unit of voting power is one vote
+    Linear,
+    /// Weight is the square root of voting power
+    Quadratic,
+    /// Voting power plus a bonus for lock plans, growing with lock duration
+    Conviction,
+}
+
+/// A voter's ballot on a proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteRecord {
+    /// 1 = for, 2 = against, 3 = abstain
+    pub choice: u32,
+    /// Weight added to the tally after weighting and capping
+    pub weight: u128,
+    /// Snapshot voting power, counted towards quorum
+    pub power: u128,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+/// Lock duration at which the conviction bonus stops growing (365 days)
+pub const MAX_CONVICTION_DURATION: u64 = 365 * 24 * 60 * 60;
+
+/// Where a proposal is in its lifecycle, derived from its timestamps and tally
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalState {
+    Pending,
+    Active,
+    Defeated,
+    Succeeded,
+    Queued,
+    Expired,
+    Executed,
+    Cancelled,
+}
+
+/// Default window for executing a proposal after its timelock (14 days)
+pub const DEFAULT_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum GovernanceKey {
@@ -91,6 +144,34 @@ pub enum GovernanceKey {
     AllProposals,
     GovernanceActive,
     VoterRecord(u64, Address),
+    /// Maps an account to the history of votes it controls
+    Checkpoints(Address),
+    /// History of the total voting power across all accounts
+    TotalSupply,
+    /// Maps proposal ID to the unweighted power of everyone who voted
+    Participation(u64),
+    /// Maps a delegator to the account voting with its power
+    Delegate(Address),
+    /// Maps proposal ID to the vote weighting in force when it was created
+    Weighting(u64),
+    /// Maps an account to the history of its conviction bonus
+    Conviction(Address),
+    /// Maps lock ID to the conviction bonus it added to its owner
+    LockConviction(u64),
+    /// Maps an account to the voting power its own deposits give, net of
+    /// withdrawals
+    OwnPower(Address),
+    /// Maps proposal ID to the last moment it can be executed, fixed when
+    /// it is queued
+    ExpiresAt(u64),
+}
+
+/// Votes controlled by an account from `timestamp` onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub timestamp: u64,
+    pub votes: u128,
 }
 
 #[contracttype]
@@ -102,12 +183,301 @@ pub enum ProposalAction {
     SetLockRate(u64, i128),
     PauseContract,
     UnpauseContract,
+    /// Deposit, withdrawal and performance fees in basis points
+    SetFees(u32, u32, u32),
+    SetTreasury(Address),
+    /// Strategy address and risk level
+    RegisterStrategy(Address, u32),
+    DisableStrategy(Address),
+    /// Reserve, rewards and operations split in basis points
+    AllocateTreasury(u32, u32, u32),
+    SetEarlyBreakFeeBps(u32),
+    Upgrade(BytesN<32>),
+    /// Runs every action in order; if any fails, none take effect
+    Batch(Vec<ProposalAction>),
+    /// Replaces the guardian council with these members and approval threshold
+    SetGuardians(Vec<Address>, u32),
+    /// Sunsets the guardian council
+    RemoveGuardians,
 }
 
-/// Calculates voting power for a user based on their lifetime deposited funds
+/// Returns the votes a user currently controls: their own deposits net of
+/// withdrawals (unless delegated away) plus any power delegated to them
 pub fn get_voting_power(env: &Env, user: &Address) -> u128 {
-    let rewards = get_user_rewards(env, user.clone());
-    rewards.lifetime_deposited.max(0) as u128
+    get_checkpoints(env, user)
+        .last()
+        .map(|c| c.votes)
+        .unwrap_or(0)
+}
+
+/// Returns the votes a user controlled at `timestamp`
+///
+/// Deposits made in the same second as `timestamp` are included.
+pub fn get_past_voting_power(env: &Env, user: &Address, timestamp: u64) -> u128 {
+    votes_at(&get_checkpoints(env, user), timestamp)
+}
+
+/// Votes recorded by the last checkpoint at or before `timestamp`
+fn votes_at(checkpoints: &Vec<Checkpoint>, timestamp: u64) -> u128 {
+    // Binary search for the last checkpoint at or before `timestamp`
+    let mut low = 0u32;
+    let mut high = checkpoints.len();
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get(mid).unwrap().timestamp > timestamp {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    if low == 0 {
+        0
+    } else {
+        checkpoints.get(low - 1).unwrap().votes
+    }
+}
+
+/// Returns the account voting with `user`'s power, if delegated
+pub fn get_delegate(env: &Env, user: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::Delegate(user.clone()))
+}
+
+/// Credits newly deposited funds to whoever votes with `user`'s power.
+///
+/// Called wherever `lifetime_deposited` grows, before the new total is saved.
+pub fn record_deposit_power(env: &Env, user: &Address, amount: i128) -> Result<(), SavingsError> {
+    seed_power(env, user)?;
+    if amount <= 0 {
+        return Ok(());
+    }
+    let amount = amount as u128;
+    let own = own_power(env, user)
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    write_own_power(env, user, own);
+
+    let holder = get_delegate(env, user).unwrap_or(user.clone());
+    move_votes(env, None, Some(&holder), amount)?;
+
+    let supply = get_total_voting_supply(env)
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    write_checkpoint(env, &GovernanceKey::TotalSupply, supply);
+    Ok(())
+}
+
+/// Takes withdrawn principal off whoever votes with `user`'s power.
+///
+/// Called wherever deposited principal leaves a plan. Power never drops
+/// below zero, so withdrawing yield or deposits made while rewards were off
+/// costs only the power the user has.
+pub fn record_withdrawal_power(
+    env: &Env,
+    user: &Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    seed_power(env, user)?;
+    let own = own_power(env, user);
+    let taken = (amount.max(0) as u128).min(own);
+    if taken == 0 {
+        return Ok(());
+    }
+    write_own_power(env, user, own - taken);
+
+    let holder = get_delegate(env, user).unwrap_or(user.clone());
+    move_votes(env, Some(&holder), None, taken)?;
+
+    let supply = get_total_voting_supply(env).saturating_sub(taken);
+    write_checkpoint(env, &GovernanceKey::TotalSupply, supply);
+    Ok(())
+}
+
+/// Gives an account that deposited before voting power was checkpointed
+/// the power of its `lifetime_deposited`, once.
+///
+/// An account with no history is credited from the start of time, so it
+/// can vote on proposals already open; the total supply only grows from
+/// now on.
+fn seed_power(env: &Env, user: &Address) -> Result<(), SavingsError> {
+    if env
+        .storage()
+        .persistent()
+        .has(&GovernanceKey::OwnPower(user.clone()))
+    {
+        return Ok(());
+    }
+    let seed = get_user_rewards(env, user.clone())
+        .lifetime_deposited
+        .max(0) as u128;
+    write_own_power(env, user, seed);
+    if seed == 0 {
+        return Ok(());
+    }
+
+    let key = GovernanceKey::Checkpoints(user.clone());
+    if load_checkpoints(env, &key).is_empty() {
+        let mut checkpoints = Vec::new(env);
+        checkpoints.push_back(Checkpoint {
+            timestamp: 0,
+            votes: seed,
+        });
+        env.storage().persistent().set(&key, &checkpoints);
+        env.storage().persistent().extend_ttl(
+            &key,
+            crate::ttl::LOW_THRESHOLD,
+            crate::ttl::EXTEND_TO,
+        );
+    } else {
+        move_votes(env, None, Some(user), seed)?;
+    }
+
+    let supply = get_total_voting_supply(env)
+        .checked_add(seed)
+        .ok_or(SavingsError::Overflow)?;
+    write_checkpoint(env, &GovernanceKey::TotalSupply, supply);
+    Ok(())
+}
+
+/// Returns the voting power held across all accounts
+pub fn get_total_voting_supply(env: &Env) -> u128 {
+    load_checkpoints(env, &GovernanceKey::TotalSupply)
+        .last()
+        .map(|c| c.votes)
+        .unwrap_or(0)
+}
+
+/// Returns the voting power held across all accounts at `timestamp`
+pub fn get_past_total_voting_supply(env: &Env, timestamp: u64) -> u128 {
+    votes_at(
+        &load_checkpoints(env, &GovernanceKey::TotalSupply),
+        timestamp,
+    )
+}
+
+/// Hands all of `delegator`'s voting power to `delegatee`
+pub fn delegate(env: &Env, delegator: Address, delegatee: Address) -> Result<(), SavingsError> {
+    delegator.require_auth();
+    seed_power(env, &delegator)?;
+    seed_power(env, &delegatee)?;
+
+    if delegator == delegatee {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+
+    let current = get_delegate(env, &delegator).unwrap_or(delegator.clone());
+    if current == delegatee {
+        return Err(SavingsError::DuplicatePlanId);
+    }
+
+    move_votes(
+        env,
+        Some(&current),
+        Some(&delegatee),
+        own_power(env, &delegator),
+    )?;
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::Delegate(delegator.clone()), &delegatee);
+
+    emit_delegate_changed(env, delegator, current, delegatee);
+
+    Ok(())
+}
+
+/// Takes `delegator`'s voting power back from its delegate
+pub fn undelegate(env: &Env, delegator: Address) -> Result<(), SavingsError> {
+    delegator.require_auth();
+    seed_power(env, &delegator)?;
+
+    let current = get_delegate(env, &delegator).ok_or(SavingsError::PlanNotFound)?;
+
+    move_votes(
+        env,
+        Some(&current),
+        Some(&delegator),
+        own_power(env, &delegator),
+    )?;
+    env.storage()
+        .persistent()
+        .remove(&GovernanceKey::Delegate(delegator.clone()));
+
+    emit_delegate_changed(env, delegator.clone(), current, delegator);
+
+    Ok(())
+}
+
+/// Voting power earned by `user`'s own deposits, net of withdrawals
+fn own_power(env: &Env, user: &Address) -> u128 {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::OwnPower(user.clone()))
+        .unwrap_or(0)
+}
+
+fn write_own_power(env: &Env, user: &Address, power: u128) {
+    let key = GovernanceKey::OwnPower(user.clone());
+    env.storage().persistent().set(&key, &power);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, crate::ttl::LOW_THRESHOLD, crate::ttl::EXTEND_TO);
+}
+
+fn get_checkpoints(env: &Env, account: &Address) -> Vec<Checkpoint> {
+    load_checkpoints(env, &GovernanceKey::Checkpoints(account.clone()))
+}
+
+fn load_checkpoints(env: &Env, key: &GovernanceKey) -> Vec<Checkpoint> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+/// Moves `amount` votes between accounts, checkpointing both sides
+fn move_votes(
+    env: &Env,
+    from: Option<&Address>,
+    to: Option<&Address>,
+    amount: u128,
+) -> Result<(), SavingsError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    if let Some(from) = from {
+        let votes = get_voting_power(env, from)
+            .checked_sub(amount)
+            .ok_or(SavingsError::Underflow)?;
+        write_checkpoint(env, &GovernanceKey::Checkpoints(from.clone()), votes);
+    }
+    if let Some(to) = to {
+        let votes = get_voting_power(env, to)
+            .checked_add(amount)
+            .ok_or(SavingsError::Overflow)?;
+        write_checkpoint(env, &GovernanceKey::Checkpoints(to.clone()), votes);
+    }
+    Ok(())
+}
+
+fn write_checkpoint(env: &Env, key: &GovernanceKey, votes: u128) {
+    let now = env.ledger().timestamp();
+    let mut checkpoints = load_checkpoints(env, key);
+
+    // Several changes within one ledger second share a checkpoint
+    let checkpoint = Checkpoint {
+        timestamp: now,
+        votes,
+    };
+    match checkpoints.last() {
+        Some(last) if last.timestamp == now => {
+            checkpoints.set(checkpoints.len() - 1, checkpoint);
+        }
+        _ => checkpoints.push_back(checkpoint),
+    }
+
+    env.storage().persistent().set(key, &checkpoints);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, crate::ttl::LOW_THRESHOLD, crate::ttl::EXTEND_TO);
 }
 
 /// Creates a new governance proposal
@@ -119,6 +489,10 @@ pub fn create_proposal(
     creator.require_auth();
 
     let config = get_voting_config(env)?;
+    if get_voting_power(env, &creator) < config.proposal_threshold {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
     let proposal_id = get_next_proposal_id(env);
     let now = env.ledger().timestamp();
 
@@ -133,11 +507,15 @@ pub fn create_proposal(
         against_votes: 0,
         abstain_votes: 0,
         queued_time: 0,
+        cancelled: false,
     };
 
     env.storage()
         .persistent()
         .set(&GovernanceKey::Proposal(proposal_id), &proposal);
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::Weighting(proposal_id), &config.weighting);
 
     let mut all_proposals: Vec<u64> = env
         .storage()
@@ -173,6 +551,8 @@ pub fn create_action_proposal(
         return Err(SavingsError::InsufficientBalance);
     }
 
+    validate_action(&action)?;
+
     let proposal_id = get_next_proposal_id(env);
     let now = env.ledger().timestamp();
 
@@ -188,11 +568,15 @@ pub fn create_action_proposal(
         abstain_votes: 0,
         action,
         queued_time: 0,
+        cancelled: false,
     };
 
     env.storage()
         .persistent()
         .set(&GovernanceKey::ActionProposal(proposal_id), &proposal);
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::Weighting(proposal_id), &config.weighting);
 
     let mut all_proposals: Vec<u64> = env
         .storage()
@@ -269,6 +653,9 @@ pub fn init_voting_config(
     if config.voting_period == 0 || config.timelock_duration == 0 || config.max_voting_power == 0 {
         return Err(SavingsError::InvalidAmount);
     }
+    if config.quorum > 10_000 || config.grace_period == 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
 
     env.storage()
         .persistent()
@@ -280,6 +667,60 @@ pub fn init_voting_config(
     Ok(())
 }
 
+/// Sets how long queued proposals stay executable after their timelock (admin only)
+pub fn set_grace_period(env: &Env, admin: Address, grace_period: u64) -> Result<(), SavingsError> {
+    admin.require_auth();
+
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SavingsError::Unauthorized)?;
+
+    if admin != stored_admin {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if grace_period == 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut config = get_voting_config(env)?;
+    config.grace_period = grace_period;
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::VotingConfig, &config);
+
+    Ok(())
+}
+
+/// Chooses how voting power is weighted on proposals created from now on (admin only)
+pub fn set_vote_weighting(
+    env: &Env,
+    admin: Address,
+    weighting: VoteWeighting,
+) -> Result<(), SavingsError> {
+    admin.require_auth();
+
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SavingsError::Unauthorized)?;
+
+    if admin != stored_admin {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    let mut config = get_voting_config(env)?;
+    config.weighting = weighting;
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::VotingConfig, &config);
+
+    Ok(())
+}
+
 fn get_next_proposal_id(env: &Env) -> u64 {
     env.storage()
         .persistent()
@@ -293,6 +734,62 @@ pub fn vote(
     proposal_id: u64,
     vote_type: u32,
     voter: Address,
+) -> Result<(), SavingsError> {
+    let reason = String::from_str(env, "");
+    cast_vote(env, proposal_id, vote_type, voter, reason, false)
+}
+
+/// Casts a weighted vote with a stored reason
+pub fn vote_with_reason(
+    env: &Env,
+    proposal_id: u64,
+    vote_type: u32,
+    voter: Address,
+    reason: String,
+) -> Result<(), SavingsError> {
+    cast_vote(env, proposal_id, vote_type, voter, reason, false)
+}
+
+/// Replaces a voter's earlier vote while voting is still open
+pub fn change_vote(
+    env: &Env,
+    proposal_id: u64,
+    vote_type: u32,
+    voter: Address,
+    reason: String,
+) -> Result<(), SavingsError> {
+    cast_vote(env, proposal_id, vote_type, voter, reason, true)
+}
+
+/// Gets the vote a user cast on a proposal
+///
+/// Votes cast before ballots were recorded were stored as a bare flag and
+/// have no record; `has_voted` still reports them.
+pub fn get_vote_record(env: &Env, proposal_id: u64, voter: &Address) -> Option<VoteRecord> {
+    let stored: Val = env
+        .storage()
+        .persistent()
+        .get(&GovernanceKey::VoterRecord(proposal_id, voter.clone()))?;
+    VoteRecord::try_from_val(env, &stored).ok()
+}
+
+/// Gets the vote weighting a proposal was created under
+///
+/// Proposals created before weighting was selectable are linear.
+pub fn get_proposal_weighting(env: &Env, proposal_id: u64) -> VoteWeighting {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::Weighting(proposal_id))
+        .unwrap_or(VoteWeighting::Linear)
+}
+
+fn cast_vote(
+    env: &Env,
+    proposal_id: u64,
+    vote_type: u32,
+    voter: Address,
+    reason: String,
+    replace: bool,
 ) -> Result<(), SavingsError> {
     voter.require_auth();
 
@@ -301,98 +798,188 @@ pub fn vote(
         return Err(SavingsError::InvalidAmount);
     }
 
-    let weight = get_voting_power(env, &voter);
-    if weight == 0 {
+    let mut proposal = load_summary(env, proposal_id).ok_or(SavingsError::PlanNotFound)?;
+    seed_power(env, &voter)?;
+
+    // Power is measured just before the proposal opened, so deposits made
+    // in the same ledger or afterwards can't sway the outcome
+    let snapshot = snapshot_time(&proposal);
+    let power = get_past_voting_power(env, &voter, snapshot);
+    if power == 0 {
         return Err(SavingsError::InsufficientBalance);
     }
 
-    let config = get_voting_config(env)?;
-    let capped_weight = weight.min(config.max_voting_power);
-
-    let voter_key = GovernanceKey::VoterRecord(proposal_id, voter.clone());
-    if env.storage().persistent().has(&voter_key) {
-        return Err(SavingsError::DuplicatePlanId);
+    let previous = get_vote_record(env, proposal_id, &voter);
+    match (&previous, replace) {
+        (Some(_), false) => return Err(SavingsError::DuplicatePlanId),
+        // A legacy vote has no recorded ballot to take back
+        (None, _) if has_voted(env, proposal_id, &voter) => {
+            return Err(SavingsError::DuplicatePlanId)
+        }
+        (None, true) => return Err(SavingsError::PlanNotFound),
+        _ => {}
     }
 
     let now = env.ledger().timestamp();
+    if proposal.cancelled || now < proposal.start_time || now > proposal.end_time {
+        return Err(SavingsError::TooLate);
+    }
 
-    // Regular proposal
-    if let Some(mut proposal) = get_proposal(env, proposal_id) {
-        if now < proposal.start_time || now > proposal.end_time {
-            return Err(SavingsError::TooLate);
-        }
+    let config = get_voting_config(env)?;
+    let weighting = get_proposal_weighting(env, proposal_id);
+    let weight = weigh(env, weighting, &voter, power, snapshot)?.min(config.max_voting_power);
 
-        match vote_type {
-            1 => {
-                proposal.for_votes = proposal
-                    .for_votes
-                    .checked_add(capped_weight)
-                    .ok_or(SavingsError::Overflow)?
-            }
-            2 => {
-                proposal.against_votes = proposal
-                    .against_votes
-                    .checked_add(capped_weight)
-                    .ok_or(SavingsError::Overflow)?
-            }
-            3 => {
-                proposal.abstain_votes = proposal
-                    .abstain_votes
-                    .checked_add(capped_weight)
-                    .ok_or(SavingsError::Overflow)?
-            }
-            _ => return Err(SavingsError::InvalidAmount),
-        }
+    let mut participation: u128 = env
+        .storage()
+        .persistent()
+        .get(&GovernanceKey::Participation(proposal_id))
+        .unwrap_or(0);
+    if let Some(old) = &previous {
+        adjust_tally(&mut proposal, old.choice, old.weight, false)?;
+        participation = participation.saturating_sub(old.power);
+    }
+    adjust_tally(&mut proposal, vote_type, weight, true)?;
+    participation = participation
+        .checked_add(power)
+        .ok_or(SavingsError::Overflow)?;
 
-        env.storage()
-            .persistent()
-            .set(&GovernanceKey::Proposal(proposal_id), &proposal);
-        env.storage().persistent().set(&voter_key, &true);
+    store_tally(env, &proposal);
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::Participation(proposal_id), &participation);
+    env.storage().persistent().set(
+        &GovernanceKey::VoterRecord(proposal_id, voter.clone()),
+        &VoteRecord {
+            choice: vote_type,
+            weight,
+            power,
+            reason,
+            timestamp: now,
+        },
+    );
+
+    emit_vote_cast(env, proposal_id, voter, vote_type, weight);
 
-        emit_vote_cast(env, proposal_id, voter, vote_type, weight);
+    Ok(())
+}
 
-        return Ok(());
+/// Applies a proposal's weighting to a voter's snapshot power
+fn weigh(
+    env: &Env,
+    weighting: VoteWeighting,
+    voter: &Address,
+    power: u128,
+    snapshot: u64,
+) -> Result<u128, SavingsError> {
+    match weighting {
+        VoteWeighting::Linear => Ok(power),
+        VoteWeighting::Quadratic => Ok(isqrt(power)),
+        VoteWeighting::Conviction => power
+            .checked_add(get_past_conviction_bonus(env, voter, snapshot))
+            .ok_or(SavingsError::Overflow),
     }
+}
 
-    // Action proposal
-    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
-        if now < proposal.start_time || now > proposal.end_time {
-            return Err(SavingsError::TooLate);
-        }
+/// Returns the conviction bonus `user` held at `timestamp`: the sum over the
+/// locks open then of each lock's amount scaled by its duration, so a
+/// year-long lock counts double
+pub fn get_past_conviction_bonus(env: &Env, user: &Address, timestamp: u64) -> u128 {
+    votes_at(
+        &load_checkpoints(env, &GovernanceKey::Conviction(user.clone())),
+        timestamp,
+    )
+}
 
-        match vote_type {
-            1 => {
-                proposal.for_votes = proposal
-                    .for_votes
-                    .checked_add(capped_weight)
-                    .ok_or(SavingsError::Overflow)?
-            }
-            2 => {
-                proposal.against_votes = proposal
-                    .against_votes
-                    .checked_add(capped_weight)
-                    .ok_or(SavingsError::Overflow)?
-            }
-            3 => {
-                proposal.abstain_votes = proposal
-                    .abstain_votes
-                    .checked_add(capped_weight)
-                    .ok_or(SavingsError::Overflow)?
-            }
-            _ => return Err(SavingsError::InvalidAmount),
-        }
+/// Adds a newly opened lock's conviction bonus to its owner's checkpoints.
+pub(crate) fn record_lock_conviction(env: &Env, lock: &LockSave) -> Result<(), SavingsError> {
+    let duration = lock
+        .maturity_time
+        .saturating_sub(lock.start_time)
+        .min(MAX_CONVICTION_DURATION);
+    let bonus = (lock.amount.max(0) as u128)
+        .checked_mul(duration as u128)
+        .ok_or(SavingsError::Overflow)?
+        / MAX_CONVICTION_DURATION as u128;
+    if bonus == 0 {
+        return Ok(());
+    }
 
-        env.storage()
-            .persistent()
-            .set(&GovernanceKey::ActionProposal(proposal_id), &proposal);
-        env.storage().persistent().set(&voter_key, &true);
+    let key = GovernanceKey::Conviction(lock.owner.clone());
+    let total = get_past_conviction_bonus(env, &lock.owner, u64::MAX)
+        .checked_add(bonus)
+        .ok_or(SavingsError::Overflow)?;
+    write_checkpoint(env, &key, total);
+    let lock_key = GovernanceKey::LockConviction(lock.id);
+    env.storage().persistent().set(&lock_key, &bonus);
+    env.storage().persistent().extend_ttl(
+        &lock_key,
+        crate::ttl::LOW_THRESHOLD,
+        crate::ttl::EXTEND_TO,
+    );
+    Ok(())
+}
+
+/// Takes a closed lock's conviction bonus off its owner's checkpoints. Locks
+/// opened before conviction was tracked added nothing and remove nothing.
+pub(crate) fn release_lock_conviction(env: &Env, lock: &LockSave) {
+    let lock_key = GovernanceKey::LockConviction(lock.id);
+    let Some(bonus) = env.storage().persistent().get::<_, u128>(&lock_key) else {
+        return;
+    };
+    env.storage().persistent().remove(&lock_key);
 
-        emit_vote_cast(env, proposal_id, voter, vote_type, weight);
+    let total = get_past_conviction_bonus(env, &lock.owner, u64::MAX).saturating_sub(bonus);
+    write_checkpoint(env, &GovernanceKey::Conviction(lock.owner.clone()), total);
+}
 
-        return Ok(());
+/// Integer square root, rounded down
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
     }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
 
-    Err(SavingsError::PlanNotFound)
+fn adjust_tally(
+    proposal: &mut Proposal,
+    choice: u32,
+    weight: u128,
+    add: bool,
+) -> Result<(), SavingsError> {
+    let tally = match choice {
+        1 => &mut proposal.for_votes,
+        2 => &mut proposal.against_votes,
+        3 => &mut proposal.abstain_votes,
+        _ => return Err(SavingsError::InvalidAmount),
+    };
+    *tally = if add {
+        tally.checked_add(weight).ok_or(SavingsError::Overflow)?
+    } else {
+        tally.checked_sub(weight).ok_or(SavingsError::Underflow)?
+    };
+    Ok(())
+}
+
+/// Writes the vote counts of `summary` back to whichever proposal kind it is
+fn store_tally(env: &Env, summary: &Proposal) {
+    if let Some(mut proposal) = get_action_proposal(env, summary.id) {
+        proposal.for_votes = summary.for_votes;
+        proposal.against_votes = summary.against_votes;
+        proposal.abstain_votes = summary.abstain_votes;
+        env.storage()
+            .persistent()
+            .set(&GovernanceKey::ActionProposal(summary.id), &proposal);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&GovernanceKey::Proposal(summary.id), summary);
+    }
 }
 
 /// Checks if a user has already voted on a proposal
@@ -401,165 +988,215 @@ pub fn has_voted(env: &Env, proposal_id: u64, voter: &Address) -> bool {
     env.storage().persistent().has(&voter_key)
 }
 
-/// Queues a proposal for execution after timelock
-pub fn queue_proposal(env: &Env, proposal_id: u64) -> Result<(), SavingsError> {
-    let now = env.ledger().timestamp();
+/// Computes the lifecycle state of a proposal
+pub fn get_proposal_state(env: &Env, proposal_id: u64) -> Result<ProposalState, SavingsError> {
+    let proposal = load_summary(env, proposal_id).ok_or(SavingsError::PlanNotFound)?;
+    let config = get_voting_config(env)?;
+    state_of(env, &config, &proposal)
+}
 
-    if let Some(mut proposal) = get_proposal(env, proposal_id) {
-        if now <= proposal.end_time {
-            return Err(SavingsError::TooEarly);
-        }
+fn state_of(
+    env: &Env,
+    config: &VotingConfig,
+    proposal: &Proposal,
+) -> Result<ProposalState, SavingsError> {
+    if proposal.cancelled {
+        return Ok(ProposalState::Cancelled);
+    }
+    if proposal.executed {
+        return Ok(ProposalState::Executed);
+    }
 
-        if proposal.queued_time > 0 {
-            return Err(SavingsError::DuplicatePlanId);
-        }
+    let now = env.ledger().timestamp();
+    if now < proposal.start_time {
+        return Ok(ProposalState::Pending);
+    }
+    if now <= proposal.end_time {
+        return Ok(ProposalState::Active);
+    }
+    if proposal.for_votes <= proposal.against_votes || !quorum_reached(env, config, proposal)? {
+        return Ok(ProposalState::Defeated);
+    }
+    if proposal.queued_time == 0 {
+        return Ok(ProposalState::Succeeded);
+    }
 
-        if proposal.executed {
-            return Err(SavingsError::PlanCompleted);
-        }
+    // Proposals queued before deadlines were stored follow the current config
+    let expires_at = match env
+        .storage()
+        .persistent()
+        .get(&GovernanceKey::ExpiresAt(proposal.id))
+    {
+        Some(expires_at) => expires_at,
+        None => expiry_from(config, proposal.queued_time)?,
+    };
+    if now > expires_at {
+        Ok(ProposalState::Expired)
+    } else {
+        Ok(ProposalState::Queued)
+    }
+}
 
-        if proposal.for_votes <= proposal.against_votes {
-            return Err(SavingsError::InsufficientBalance);
-        }
+/// Last moment a proposal queued at `queued_time` can be executed
+fn expiry_from(config: &VotingConfig, queued_time: u64) -> Result<u64, SavingsError> {
+    queued_time
+        .checked_add(config.timelock_duration)
+        .and_then(|t| t.checked_add(config.grace_period))
+        .ok_or(SavingsError::Overflow)
+}
 
-        proposal.queued_time = now;
-        env.storage()
-            .persistent()
-            .set(&GovernanceKey::Proposal(proposal_id), &proposal);
+/// Whether enough of the voting supply at the proposal's start took part
+fn quorum_reached(
+    env: &Env,
+    config: &VotingConfig,
+    proposal: &Proposal,
+) -> Result<bool, SavingsError> {
+    let supply = get_past_total_voting_supply(env, snapshot_time(proposal));
+    let required = supply
+        .checked_mul(config.quorum as u128)
+        .ok_or(SavingsError::Overflow)?
+        / 10_000;
+    // Participation is counted in unweighted power so it compares with supply
+    let cast: u128 = env
+        .storage()
+        .persistent()
+        .get(&GovernanceKey::Participation(proposal.id))
+        .unwrap_or(0);
+    Ok(cast >= required)
+}
 
-        emit_proposal_queued(env, proposal_id, now);
+/// When voting power is read for a proposal: the second before it opened
+fn snapshot_time(proposal: &Proposal) -> u64 {
+    proposal.start_time.saturating_sub(1)
+}
 
-        return Ok(());
+/// Loads a regular or action proposal as a plain `Proposal` for state checks
+pub(crate) fn load_summary(env: &Env, proposal_id: u64) -> Option<Proposal> {
+    if let Some(p) = get_proposal(env, proposal_id) {
+        return Some(p);
     }
+    get_action_proposal(env, proposal_id).map(|p| Proposal {
+        id: p.id,
+        creator: p.creator,
+        description: p.description,
+        start_time: p.start_time,
+        end_time: p.end_time,
+        executed: p.executed,
+        for_votes: p.for_votes,
+        against_votes: p.against_votes,
+        abstain_votes: p.abstain_votes,
+        queued_time: p.queued_time,
+        cancelled: p.cancelled,
+    })
+}
 
-    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
-        if now <= proposal.end_time {
-            return Err(SavingsError::TooEarly);
-        }
-
-        if proposal.queued_time > 0 {
-            return Err(SavingsError::DuplicatePlanId);
+/// Queues a succeeded proposal for execution after timelock
+pub fn queue_proposal(env: &Env, proposal_id: u64) -> Result<(), SavingsError> {
+    let now = env.ledger().timestamp();
+    let config = get_voting_config(env)?;
+    let summary = load_summary(env, proposal_id).ok_or(SavingsError::PlanNotFound)?;
+
+    match state_of(env, &config, &summary)? {
+        ProposalState::Succeeded => {}
+        ProposalState::Pending | ProposalState::Active => return Err(SavingsError::TooEarly),
+        ProposalState::Defeated => return Err(SavingsError::InsufficientBalance),
+        ProposalState::Queued | ProposalState::Expired => {
+            return Err(SavingsError::DuplicatePlanId)
         }
-
-        if proposal.executed {
-            return Err(SavingsError::PlanCompleted);
+        ProposalState::Executed | ProposalState::Cancelled => {
+            return Err(SavingsError::PlanCompleted)
         }
+    }
 
-        if proposal.for_votes <= proposal.against_votes {
-            return Err(SavingsError::InsufficientBalance);
-        }
+    // Later changes to the grace period don't move this proposal's deadline
+    let expiry_key = GovernanceKey::ExpiresAt(proposal_id);
+    env.storage()
+        .persistent()
+        .set(&expiry_key, &expiry_from(&config, now)?);
+    env.storage().persistent().extend_ttl(
+        &expiry_key,
+        crate::ttl::LOW_THRESHOLD,
+        crate::ttl::EXTEND_TO,
+    );
 
+    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
         proposal.queued_time = now;
         env.storage()
             .persistent()
             .set(&GovernanceKey::ActionProposal(proposal_id), &proposal);
-
-        emit_proposal_queued(env, proposal_id, now);
-
-        return Ok(());
+    } else {
+        let mut proposal = summary;
+        proposal.queued_time = now;
+        env.storage()
+            .persistent()
+            .set(&GovernanceKey::Proposal(proposal_id), &proposal);
     }
 
-    Err(SavingsError::PlanNotFound)
+    emit_proposal_queued(env, proposal_id, now);
+
+    Ok(())
 }
 
-/// Executes a queued proposal after timelock period
+/// Executes a queued proposal after its timelock and before it expires
 pub fn execute_proposal(env: &Env, proposal_id: u64) -> Result<(), SavingsError> {
     let now = env.ledger().timestamp();
     let config = get_voting_config(env)?;
+    let summary = load_summary(env, proposal_id).ok_or(SavingsError::PlanNotFound)?;
 
-    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
-        if proposal.queued_time == 0 {
-            return Err(SavingsError::TooEarly);
-        }
-
-        if proposal.executed {
-            return Err(SavingsError::PlanCompleted);
+    match state_of(env, &config, &summary)? {
+        ProposalState::Queued => {}
+        ProposalState::Expired => return Err(SavingsError::TooLate),
+        ProposalState::Executed | ProposalState::Cancelled => {
+            return Err(SavingsError::PlanCompleted)
         }
+        _ => return Err(SavingsError::TooEarly),
+    }
 
-        let execution_time = proposal
-            .queued_time
-            .checked_add(config.timelock_duration)
-            .ok_or(SavingsError::Overflow)?;
-
-        if now < execution_time {
-            return Err(SavingsError::TooEarly);
-        }
+    let execution_time = summary
+        .queued_time
+        .checked_add(config.timelock_duration)
+        .ok_or(SavingsError::Overflow)?;
+    if now < execution_time {
+        return Err(SavingsError::TooEarly);
+    }
 
+    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
         proposal.executed = true;
         env.storage()
             .persistent()
             .set(&GovernanceKey::ActionProposal(proposal_id), &proposal);
 
         execute_action(env, &proposal.action)?;
-
-        emit_proposal_executed(env, proposal_id, now);
-
-        return Ok(());
-    }
-
-    if let Some(mut proposal) = get_proposal(env, proposal_id) {
-        if proposal.queued_time == 0 {
-            return Err(SavingsError::TooEarly);
-        }
-
-        if proposal.executed {
-            return Err(SavingsError::PlanCompleted);
-        }
-
-        let execution_time = proposal
-            .queued_time
-            .checked_add(config.timelock_duration)
-            .ok_or(SavingsError::Overflow)?;
-
-        if now < execution_time {
-            return Err(SavingsError::TooEarly);
-        }
-
+    } else {
+        let mut proposal = summary;
         proposal.executed = true;
         env.storage()
             .persistent()
             .set(&GovernanceKey::Proposal(proposal_id), &proposal);
-
-        emit_proposal_executed(env, proposal_id, now);
-
-        return Ok(());
     }
 
-    Err(SavingsError::PlanNotFound)
+    emit_proposal_executed(env, proposal_id, now);
+
+    Ok(())
 }
 
 /// Executes a proposal action
 fn execute_action(env: &Env, action: &ProposalAction) -> Result<(), SavingsError> {
     match action {
         ProposalAction::SetFlexiRate(rate) => {
-            if *rate < 0 {
-                return Err(SavingsError::InvalidInterestRate);
-            }
-            env.storage().instance().set(&DataKey::FlexiRate, rate);
-            Ok(())
+            crate::rates::write_flexi_rate(env, &crate::assets::base_asset(env), *rate)
         }
         ProposalAction::SetGoalRate(rate) => {
-            if *rate < 0 {
-                return Err(SavingsError::InvalidInterestRate);
-            }
-            env.storage().instance().set(&DataKey::GoalRate, rate);
-            Ok(())
+            let key = DataKey::GoalRate(crate::assets::base_asset(env));
+            crate::rates::write_rate(env, &key, *rate)
         }
         ProposalAction::SetGroupRate(rate) => {
-            if *rate < 0 {
-                return Err(SavingsError::InvalidInterestRate);
-            }
-            env.storage().instance().set(&DataKey::GroupRate, rate);
-            Ok(())
+            let key = DataKey::GroupRate(crate::assets::base_asset(env));
+            crate::rates::write_rate(env, &key, *rate)
         }
         ProposalAction::SetLockRate(duration, rate) => {
-            if *rate < 0 {
-                return Err(SavingsError::InvalidInterestRate);
-            }
-            env.storage()
-                .instance()
-                .set(&DataKey::LockRate(*duration), rate);
-            Ok(())
+            let asset = crate::assets::base_asset(env);
+            crate::rates::write_lock_rate(env, &asset, *duration, *rate)
         }
         ProposalAction::PauseContract => {
             env.storage().persistent().set(&DataKey::Paused, &true);
@@ -571,55 +1208,113 @@ fn execute_action(env: &Env, action: &ProposalAction) -> Result<(), SavingsError
             crate::ttl::extend_config_ttl(env, &DataKey::Paused);
             Ok(())
         }
+        ProposalAction::SetFees(deposit_fee, withdrawal_fee, performance_fee) => {
+            crate::config::write_fees(env, *deposit_fee, *withdrawal_fee, *performance_fee)
+        }
+        ProposalAction::SetTreasury(treasury) => {
+            crate::config::write_treasury(env, treasury.clone());
+            Ok(())
+        }
+        ProposalAction::RegisterStrategy(strategy, risk_level) => {
+            crate::strategy::registry::store_strategy(env, strategy.clone(), *risk_level)
+        }
+        ProposalAction::DisableStrategy(strategy) => {
+            crate::strategy::registry::mark_strategy_disabled(env, strategy.clone())
+        }
+        ProposalAction::AllocateTreasury(reserve, rewards, operations) => {
+            crate::treasury::apply_allocation(env, *reserve, *rewards, *operations).map(|_| ())
+        }
+        ProposalAction::SetEarlyBreakFeeBps(bps) => {
+            if *bps > 10_000 {
+                return Err(SavingsError::InvalidAmount);
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::EarlyBreakFeeBps, bps);
+            env.events().publish((symbol_short!("set_brk"),), *bps);
+            Ok(())
+        }
+        ProposalAction::Upgrade(wasm_hash) => {
+            crate::upgrade::apply_upgrade(env, wasm_hash.clone());
+            Ok(())
+        }
+        ProposalAction::SetGuardians(members, threshold) => {
+            crate::guardian::write_council(env, members.clone(), *threshold)
+        }
+        ProposalAction::RemoveGuardians => {
+            crate::guardian::remove_council(env);
+            Ok(())
+        }
+        ProposalAction::Batch(actions) => {
+            // An error here fails execute_proposal, which rolls back every
+            // action that already ran
+            for inner in actions.iter() {
+                execute_action(env, &inner)?;
+            }
+            Ok(())
+        }
     }
 }
 
-/// Cancels a proposal (creator or admin only)
-pub fn cancel_proposal(env: &Env, proposal_id: u64, caller: Address) -> Result<(), SavingsError> {
-    caller.require_auth();
-
-    // Try regular proposal
-    if let Some(mut proposal) = get_proposal(env, proposal_id) {
-        if proposal.creator != caller {
-            return Err(SavingsError::Unauthorized);
+/// Rejects malformed actions before they reach a vote
+fn validate_action(action: &ProposalAction) -> Result<(), SavingsError> {
+    match action {
+        ProposalAction::SetGuardians(members, threshold) => {
+            crate::guardian::validate_council(members, *threshold)
         }
-
-        if proposal.executed || proposal.queued_time > 0 {
-            return Err(SavingsError::TooLate);
+        ProposalAction::Batch(actions) => {
+            if actions.is_empty() {
+                return Err(SavingsError::InvalidPlanConfig);
+            }
+            for inner in actions.iter() {
+                if let ProposalAction::Batch(_) = inner {
+                    return Err(SavingsError::InvalidPlanConfig);
+                }
+                validate_action(&inner)?;
+            }
+            Ok(())
         }
+        _ => Ok(()),
+    }
+}
 
-        // Mark as canceled (you may want a separate canceled field later)
-        proposal.executed = true;
-        env.storage()
-            .persistent()
-            .set(&GovernanceKey::Proposal(proposal_id), &proposal);
+/// Cancels a proposal that has not been queued yet (creator or admin only)
+pub fn cancel_proposal(env: &Env, proposal_id: u64, caller: Address) -> Result<(), SavingsError> {
+    caller.require_auth();
 
-        emit_proposal_canceled(env, proposal_id, env.ledger().timestamp());
+    let summary = load_summary(env, proposal_id).ok_or(SavingsError::PlanNotFound)?;
 
-        return Ok(());
+    let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+    if summary.creator != caller && admin.as_ref() != Some(&caller) {
+        return Err(SavingsError::Unauthorized);
     }
 
-    // Try action proposal
-    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
-        if proposal.creator != caller {
-            return Err(SavingsError::Unauthorized);
-        }
+    if summary.executed || summary.cancelled || summary.queued_time > 0 {
+        return Err(SavingsError::TooLate);
+    }
 
-        if proposal.executed || proposal.queued_time > 0 {
-            return Err(SavingsError::TooLate);
-        }
+    mark_cancelled(env, summary);
 
-        proposal.executed = true;
+    Ok(())
+}
+
+/// Flags a proposal as cancelled so it can never be queued or executed
+pub(crate) fn mark_cancelled(env: &Env, summary: Proposal) {
+    let proposal_id = summary.id;
+    if let Some(mut proposal) = get_action_proposal(env, proposal_id) {
+        proposal.cancelled = true;
         env.storage()
             .persistent()
             .set(&GovernanceKey::ActionProposal(proposal_id), &proposal);
-
-        emit_proposal_canceled(env, proposal_id, env.ledger().timestamp());
-
-        return Ok(());
+    } else {
+        let mut proposal = summary;
+        proposal.cancelled = true;
+        env.storage()
+            .persistent()
+            .set(&GovernanceKey::Proposal(proposal_id), &proposal);
     }
 
-    Err(SavingsError::PlanNotFound)
+    emit_proposal_canceled(env, proposal_id, env.ledger().timestamp());
 }
 
 /// Checks if governance is active
@@ -7,7 +7,7 @@ mod governance_tests {
         env.mock_all_auths();
         client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let voter1 = Address::generate(&env);
         let voter2 = Address::generate(&env);
         client.initialize_user(&voter1);
@@ -36,7 +36,7 @@ mod governance_tests {
         env.mock_all_auths();
         client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let attacker = Address::generate(&env);
         client.initialize_user(&attacker);
         client.create_savings_plan(&attacker, &PlanType::Flexi, &50); // Not enough power
@@ -44,6 +44,9 @@ mod governance_tests {
         let desc = String::from_str(&env, "Attack proposal");
         let proposal_id = client.create_proposal(&creator, &desc);
 
+        // Creator and attacker together meet quorum
+        client.vote(&proposal_id, &1, &creator);
+
         // Attacker tries to vote multiple times
         client.vote(&proposal_id, &1, &attacker);
         let result = client.try_vote(&proposal_id, &1, &attacker);
@@ -60,9 +63,10 @@ mod governance_tests {
         assert!(early_exec.is_err());
     }
 
+    use crate::governance::ProposalState;
     use crate::governance_events::{ProposalCreated, VoteCast};
     use crate::rewards::storage_types::RewardsConfig;
-    use crate::{NesteraContract, NesteraContractClient, PlanType};
+    use crate::{NesteraContract, NesteraContractClient, PlanType, SavingsError};
     use soroban_sdk::symbol_short;
     use soroban_sdk::IntoVal;
     use soroban_sdk::{
@@ -96,6 +100,14 @@ mod governance_tests {
         (env, client, admin)
     }
 
+    /// A user with enough deposits to meet the proposal threshold
+    fn funded_creator(env: &Env, client: &NesteraContractClient<'static>) -> Address {
+        let creator = Address::generate(env);
+        client.initialize_user(&creator);
+        let _ = client.create_savings_plan(&creator, &PlanType::Flexi, &100);
+        creator
+    }
+
     // ────────────────────────────────────────────────────────────────────────────────
     // Existing tests (kept + fixed unwrap usage)
     // ────────────────────────────────────────────────────────────────────────────────
@@ -157,7 +169,7 @@ mod governance_tests {
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let description = String::from_str(&env, "Test proposal");
 
         let proposal_id = client.create_proposal(&creator, &description);
@@ -172,7 +184,7 @@ mod governance_tests {
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let description = String::from_str(&env, "Test proposal");
         let proposal_id = client.create_proposal(&creator, &description);
 
@@ -194,7 +206,7 @@ mod governance_tests {
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let desc1 = String::from_str(&env, "Proposal 1");
         let desc2 = String::from_str(&env, "Proposal 2");
 
@@ -214,7 +226,7 @@ mod governance_tests {
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let description = String::from_str(&env, "Store test");
         let proposal_id = client.create_proposal(&creator, &description);
 
@@ -237,7 +249,7 @@ mod governance_tests {
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let description = String::from_str(&env, "Test proposal description");
 
         let proposal_id = client.create_proposal(&creator, &description);
@@ -273,7 +285,7 @@ mod governance_tests {
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let voter = Address::generate(&env);
 
         client.initialize_user(&voter);
@@ -299,4 +311,156 @@ mod governance_tests {
         assert_eq!(event_data.vote_type, 1);
         assert!(event_data.weight > 0);
     }
+
+    // ────────────────────────────────────────────────────────────────────────────────
+    // Proposal lifecycle states
+    // ────────────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_proposal_state_progression() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
+
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "States"));
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Active
+        );
+
+        client.vote(&proposal_id, &1, &creator);
+        env.ledger().with_mut(|li| li.timestamp += 11);
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Succeeded
+        );
+
+        client.queue_proposal(&proposal_id);
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Queued
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 6);
+        client.execute_proposal(&proposal_id);
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Executed
+        );
+    }
+
+    #[test]
+    fn test_queue_requires_quorum_of_total_supply() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
+
+        let creator = funded_creator(&env, &client);
+        let whale = Address::generate(&env);
+        client.initialize_user(&whale);
+        client.create_savings_plan(&whale, &PlanType::Flexi, &1000);
+        assert_eq!(client.get_total_voting_supply(), 1100);
+
+        // Only 100 of 1,100 votes are cast; quorum needs 550
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Quorum"));
+        client.vote(&proposal_id, &1, &creator);
+        env.ledger().with_mut(|li| li.timestamp += 11);
+
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Defeated
+        );
+        assert_eq!(
+            client.try_queue_proposal(&proposal_id).unwrap_err(),
+            Ok(SavingsError::InsufficientBalance)
+        );
+    }
+
+    #[test]
+    fn test_queued_proposal_expires_after_grace_period() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
+        client.set_proposal_grace_period(&admin, &20);
+
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Expiry"));
+        client.vote(&proposal_id, &1, &creator);
+        env.ledger().with_mut(|li| li.timestamp += 11);
+        client.queue_proposal(&proposal_id);
+
+        // Timelock (5) + grace (20) has passed
+        env.ledger().with_mut(|li| li.timestamp += 26);
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Expired
+        );
+        assert_eq!(
+            client.try_execute_proposal(&proposal_id).unwrap_err(),
+            Ok(SavingsError::TooLate)
+        );
+    }
+
+    #[test]
+    fn test_grace_period_is_fixed_when_queued() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
+        client.set_proposal_grace_period(&admin, &20);
+
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Fixed"));
+        client.vote(&proposal_id, &1, &creator);
+        env.ledger().with_mut(|li| li.timestamp += 11);
+        client.queue_proposal(&proposal_id);
+
+        // Extending the grace period afterwards does not revive the proposal
+        client.set_proposal_grace_period(&admin, &1_000);
+        env.ledger().with_mut(|li| li.timestamp += 26);
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Expired
+        );
+    }
+
+    #[test]
+    fn test_cancelled_proposal_cannot_be_queued() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
+
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Cancel"));
+        client.vote(&proposal_id, &1, &creator);
+
+        let stranger = Address::generate(&env);
+        assert_eq!(
+            client
+                .try_cancel_proposal(&proposal_id, &stranger)
+                .unwrap_err(),
+            Ok(SavingsError::Unauthorized)
+        );
+
+        client.cancel_proposal(&proposal_id, &admin);
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Cancelled
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 11);
+        assert_eq!(
+            client.try_queue_proposal(&proposal_id).unwrap_err(),
+            Ok(SavingsError::PlanCompleted)
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_requires_threshold() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &10, &5, &100, &10_000);
+
+        let creator = Address::generate(&env);
+        assert_eq!(
+            client
+                .try_create_proposal(&creator, &String::from_str(&env, "No power"))
+                .unwrap_err(),
+            Ok(SavingsError::InsufficientBalance)
+        );
+    }
 }
@@ -1,9 +1,90 @@
+use crate::assets;
+use crate::custody::{self, Funding};
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
-use crate::storage_types::{DataKey, GroupSave};
+use crate::governance;
+use crate::rates;
+use crate::storage_types::{decode_versioned, DataKey, GroupSave, LegacyGroupSave};
+use crate::strategy::routing::{self, StrategyPositionKey};
+use crate::treasury;
 use crate::ttl;
 use crate::users;
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{contracttype, Address, Env, Map, String, Symbol, Val, Vec};
+
+/// Storage keys for group membership management
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GroupKey {
+    /// Marks an unused creator invite for (group_id, user)
+    Invite(u64, Address),
+    /// Maps group ID to users waiting for creator approval
+    JoinRequests(u64),
+    /// Maps group ID to the timestamp it was settled at
+    Settled(u64),
+    /// Maps (group_id, member) to their PeriodRecord
+    MemberPeriods(u64, Address),
+    /// Maps (group_id, member) to the sum of each contribution times the
+    /// time it starts earning yield, for time-weighting settlement yield
+    ContributionTime(u64, Address),
+    /// Maps group ID to what its strategy positions lost net of gains, which
+    /// members' contributions bear pro rata
+    PoolLoss(u64),
+    /// Maps group ID to the progress of paying it out
+    Settlement(u64),
+}
+
+/// Fixed groups take exactly `contribution_amount` per contribution
+pub const CONTRIBUTION_FIXED: u32 = 0;
+/// Flexible groups take any amount of at least `contribution_amount`
+pub const CONTRIBUTION_FLEXIBLE: u32 = 1;
+/// Percentage groups take `contribution_amount` bps of the remaining target
+pub const CONTRIBUTION_PERCENTAGE: u32 = 2;
+
+/// Tracks which contribution periods a member has paid into
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodRecord {
+    pub last_period: u64,
+    pub periods_paid: u32,
+}
+
+/// Most members a single `settle_group_save` call tallies, and most it pays out
+pub const SETTLE_BATCH: u32 = 25;
+
+/// Progress of paying a group out across `settle_group_save` calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupSettlement {
+    /// Members tallied, or paid once `tallied` is set, so far
+    pub next_member: u32,
+    /// Set once every member's yield has been tallied
+    pub tallied: bool,
+    /// Yield earned by the members tallied so far
+    pub earned_total: i128,
+    /// Part of `earned_total` drawn from the reserve to pay yield with
+    pub funded: i128,
+    /// Total paid to members so far
+    pub total_paid: i128,
+    /// Yield paid to members so far
+    pub total_yield: i128,
+    /// Set once every member has been paid
+    pub completed: bool,
+}
+
+/// A member's standing against the group's contribution schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupCompliance {
+    pub member: Address,
+    pub total_contributed: i128,
+    /// Periods opened so far, including the current one (0 without periods)
+    pub periods_elapsed: u64,
+    pub periods_paid: u32,
+    /// Closed periods the member did not contribute in
+    pub periods_missed: u64,
+    pub paid_current_period: bool,
+    pub is_compliant: bool,
+}
 
 /// Creates a new group savings plan.
 ///
@@ -17,8 +98,9 @@ use soroban_sdk::{Address, Env, String, Vec};
 /// * `description` - Description of the group savings goal
 /// * `category` - Category of the group savings (e.g., "education", "emergency")
 /// * `target_amount` - Target amount to save (must be > 0)
-/// * `contribution_type` - Type of contribution (0 = fixed, 1 = flexible, etc.)
-/// * `contribution_amount` - Contribution amount or minimum (must be > 0)
+/// * `contribution_type` - Type of contribution (0 = fixed, 1 = flexible, 2 = percentage)
+/// * `contribution_amount` - Fixed amount, flexible minimum, or basis points of
+///   the remaining target for percentage groups (must be > 0)
 /// * `is_public` - Whether the group is public or private
 /// * `start_time` - Unix timestamp when the group starts
 /// * `end_time` - Unix timestamp when the group ends (must be > start_time)
@@ -65,6 +147,11 @@ pub fn create_group_save(
         return Err(SavingsError::InvalidGroupConfig);
     }
 
+    // Percentage groups express the contribution in basis points
+    if contribution_type == CONTRIBUTION_PERCENTAGE && contribution_amount > 10_000 {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
     // Validate title and description are not empty
     if title.is_empty() || description.is_empty() {
         return Err(SavingsError::InvalidGroupConfig);
@@ -83,6 +170,7 @@ pub fn create_group_save(
     let new_group = GroupSave {
         id: group_id,
         creator: creator.clone(),
+        asset: assets::base_asset(env),
         title,
         description,
         category,
@@ -92,6 +180,8 @@ pub fn create_group_save(
         contribution_amount,
         is_public,
         member_count: 1, // Creator is the first member
+        max_members: 0,
+        period_duration: 0,
         start_time,
         end_time,
         is_completed: false,
@@ -154,6 +244,49 @@ pub fn create_group_save(
     Ok(group_id)
 }
 
+/// Sets the asset a group collects contributions in.
+///
+/// Groups are created in the base asset. The creator may switch to another
+/// whitelisted asset until the first contribution arrives.
+///
+/// # Errors
+/// Returns `Err(SavingsError)` if:
+/// - Group doesn't exist
+/// - Caller is not the group creator
+/// - Group already holds contributions or has started rotating
+/// - Asset is not whitelisted
+pub fn set_group_asset(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    asset: Address,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let group_key = DataKey::GroupSave(group_id);
+    let mut group = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if group.current_amount != 0 || crate::rosca::is_rotating(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    assets::require_supported(env, &asset)?;
+
+    group.asset = asset.clone();
+    env.storage().persistent().set(&group_key, &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events()
+        .publish((soroban_sdk::symbol_short!("grp_asset"), group_id), asset);
+
+    Ok(())
+}
+
 /// Retrieves a group savings plan by ID.
 ///
 /// # Arguments
@@ -163,13 +296,116 @@ pub fn create_group_save(
 /// # Returns
 /// `Some(GroupSave)` if the group exists, `None` otherwise
 pub fn get_group_save(env: &Env, group_id: u64) -> Option<GroupSave> {
-    let key = DataKey::GroupSave(group_id);
-    let group = env.storage().persistent().get(&key);
-    if group.is_some() {
-        // Extend TTL on read
-        ttl::extend_group_ttl(env, group_id);
+    let raw: Map<Symbol, Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::GroupSave(group_id))?;
+    // Extend TTL on read
+    ttl::extend_group_ttl(env, group_id);
+    // Groups stored by earlier versions are uncapped base-asset groups
+    // without contribution periods
+    decode_versioned(env, raw, "asset", |legacy: LegacyGroupSave| GroupSave {
+        id: legacy.id,
+        creator: legacy.creator,
+        asset: assets::base_asset(env),
+        title: legacy.title,
+        description: legacy.description,
+        category: legacy.category,
+        target_amount: legacy.target_amount,
+        current_amount: legacy.current_amount,
+        contribution_type: legacy.contribution_type,
+        contribution_amount: legacy.contribution_amount,
+        is_public: legacy.is_public,
+        member_count: legacy.member_count,
+        max_members: 0,
+        period_duration: 0,
+        start_time: legacy.start_time,
+        end_time: legacy.end_time,
+        is_completed: legacy.is_completed,
+    })
+}
+
+/// Returns what a group's strategy positions have cost its pool net of
+/// gains; negative when they returned more than was routed.
+pub fn get_pool_loss(env: &Env, group_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&GroupKey::PoolLoss(group_id))
+        .unwrap_or(0)
+}
+
+fn set_pool_loss(env: &Env, group_id: u64, loss: i128) {
+    let key = GroupKey::PoolLoss(group_id);
+    if loss == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &loss);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
     }
-    group
+}
+
+/// Moves a group's pool by a strategy gain (positive) or loss (negative).
+///
+/// Contributions are left as they are; members get their contribution's
+/// share of the pool when they are paid out (see `release_contribution`).
+pub(crate) fn adjust_pool(env: &Env, group_id: u64, delta: i128) -> Result<(), SavingsError> {
+    let mut group = load_group(env, group_id)?;
+    let pool = group
+        .current_amount
+        .checked_add(delta)
+        .ok_or(SavingsError::Overflow)?
+        .max(0);
+    let loss = get_pool_loss(env, group_id)
+        .checked_add(group.current_amount - pool)
+        .ok_or(SavingsError::Overflow)?;
+    group.current_amount = pool;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    set_pool_loss(env, group_id, loss);
+    Ok(())
+}
+
+/// What `contribution` is worth after the pool's strategy gains and losses:
+/// its share of the pool, whose contributions add up to the pool plus its
+/// loss.
+fn contribution_value(
+    env: &Env,
+    group: &GroupSave,
+    contribution: i128,
+) -> Result<i128, SavingsError> {
+    let loss = get_pool_loss(env, group.id);
+    let contributed = group
+        .current_amount
+        .checked_add(loss)
+        .ok_or(SavingsError::Overflow)?;
+    if loss == 0 || contributed <= 0 {
+        return Ok(contribution.min(group.current_amount));
+    }
+    Ok((contribution
+        .checked_mul(group.current_amount)
+        .ok_or(SavingsError::Overflow)?
+        / contributed)
+        .min(group.current_amount))
+}
+
+/// Takes a member's contribution out of the pool, returning what it is worth
+/// (see `contribution_value`). Updates `group` without saving it.
+pub(crate) fn release_contribution(
+    env: &Env,
+    group: &mut GroupSave,
+    contribution: i128,
+) -> Result<i128, SavingsError> {
+    let value = contribution_value(env, group, contribution)?;
+    group.current_amount -= value;
+    set_pool_loss(
+        env,
+        group.id,
+        get_pool_loss(env, group.id) - (contribution - value),
+    );
+    Ok(value)
 }
 
 /// Checks if a group exists.
@@ -240,7 +476,10 @@ fn add_group_to_user_list(env: &Env, user: &Address, group_id: u64) -> Result<()
     Ok(())
 }
 
-/// Allows a user to join a public group savings plan.
+/// Allows a user to join a group savings plan.
+///
+/// Public groups are open to anyone. Private groups only admit users the
+/// creator has invited; the invite is consumed on joining.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -252,43 +491,352 @@ fn add_group_to_user_list(env: &Env, user: &Address, group_id: u64) -> Result<()
 /// `Err(SavingsError)` if:
 /// - User doesn't exist
 /// - Group doesn't exist
-/// - Group is not public
+/// - Group is private and the user has no invite
 /// - User is already a member
+/// - Group has reached `max_members` (`GroupFull`)
 pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
+    user.require_auth();
+
     // Ensure user exists
     if !users::user_exists(env, &user) {
         return Err(SavingsError::UserNotFound);
     }
 
     // Fetch the group
-    let group_key = DataKey::GroupSave(group_id);
-    let mut group: GroupSave = env
-        .storage()
-        .persistent()
-        .get(&group_key)
-        .ok_or(SavingsError::PlanNotFound)?;
+    let mut group = load_group(env, group_id)?;
 
-    // Validate that the group is public
+    // Private groups only admit invited users
     if !group.is_public {
+        let invite_key = GroupKey::Invite(group_id, user.clone());
+        if !env.storage().persistent().has(&invite_key) {
+            return Err(SavingsError::InvalidGroupConfig);
+        }
+        env.storage().persistent().remove(&invite_key);
+    }
+
+    add_member(env, &mut group, &user)
+}
+
+/// Invites a user to a private group. Only the creator may invite.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `UserNotFound` - If the invitee has no account
+/// * `InvalidGroupConfig` - If the invitee is already a member
+pub fn invite_to_group(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    invitee: Address,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    if !users::user_exists(env, &invitee) {
+        return Err(SavingsError::UserNotFound);
+    }
+    if get_group_members(env, group_id).contains(&invitee) {
         return Err(SavingsError::InvalidGroupConfig);
     }
 
-    // Check if user is already a member
-    let members_key = DataKey::GroupMembers(group_id);
-    let mut members: Vec<Address> = env
-        .storage()
+    let invite_key = GroupKey::Invite(group_id, invitee.clone());
+    env.storage().persistent().set(&invite_key, &true);
+    env.storage()
         .persistent()
-        .get(&members_key)
-        .unwrap_or(Vec::new(env));
+        .extend_ttl(&invite_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_inv"), invitee, group_id),
+        creator,
+    );
+
+    Ok(())
+}
+
+/// Files a request to join a private group, pending creator approval.
+///
+/// # Errors
+/// * `UserNotFound` - If the user has no account
+/// * `PlanNotFound` - If the group does not exist
+/// * `InvalidGroupConfig` - If the group is public (join directly), the user
+///   is already a member, or a request is already pending
+/// * `GroupFull` - If the group has reached `max_members`
+pub fn request_to_join_group(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    if !users::user_exists(env, &user) {
+        return Err(SavingsError::UserNotFound);
+    }
+
+    let group = load_group(env, group_id)?;
+    if group.is_public || group.is_completed {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    if is_full(&group) {
+        return Err(SavingsError::GroupFull);
+    }
+    if get_group_members(env, group_id).contains(&user) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let mut requests = get_join_requests(env, group_id);
+    if requests.contains(&user) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    requests.push_back(user.clone());
+    save_join_requests(env, group_id, &requests);
+
+    env.events()
+        .publish((soroban_sdk::symbol_short!("grp_req"), user), group_id);
+
+    Ok(())
+}
+
+/// Approves a pending join request and adds the requester as a member.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the user has no pending request
+/// * `GroupFull` - If the group has reached `max_members`
+pub fn approve_join_request(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    user: Address,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    take_join_request(env, group_id, &user)?;
+    add_member(env, &mut group, &user)
+}
+
+/// Rejects a pending join request.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the user has no pending request
+pub fn reject_join_request(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    user: Address,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    take_join_request(env, group_id, &user)?;
+
+    env.events()
+        .publish((soroban_sdk::symbol_short!("grp_rej"), user), group_id);
+
+    Ok(())
+}
+
+/// Caps how many members a group may hold. `0` removes the cap.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the cap is below the current member count
+pub fn set_group_max_members(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    max_members: u32,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    if max_members != 0 && max_members < group.member_count {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    group.max_members = max_members;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_max"), group_id),
+        max_members,
+    );
+
+    Ok(())
+}
+
+/// Removes a member who has not contributed anything yet.
+///
+/// Members with contributions must leave through `break_group_save` so that
+/// their funds are refunded.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `NotGroupMember` - If `member` is not in the group
+/// * `InvalidGroupConfig` - If `member` is the creator, has contributed, or
+///   the group is rotating
+pub fn remove_group_member(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    member: Address,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    if member == creator || crate::rosca::is_rotating(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    if get_member_contribution(env, group_id, &member) != 0 {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    remove_member(env, &mut group, &member)?;
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_kick"), member, group_id),
+        creator,
+    );
+
+    Ok(())
+}
+
+/// Hands the creator role to another member.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `NotGroupMember` - If `new_creator` is not in the group
+pub fn transfer_group_creator(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    new_creator: Address,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    if !get_group_members(env, group_id).contains(&new_creator) {
+        return Err(SavingsError::NotGroupMember);
+    }
+
+    group.creator = new_creator.clone();
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_own"), group_id),
+        (creator, new_creator),
+    );
+
+    Ok(())
+}
+
+/// VIEW FUNCTION - Gets users waiting for approval to join a group
+pub fn get_join_requests(env: &Env, group_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&GroupKey::JoinRequests(group_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// VIEW FUNCTION - Checks whether a user holds an unused invite to a group
+pub fn is_invited(env: &Env, group_id: u64, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&GroupKey::Invite(group_id, user.clone()))
+}
+
+fn load_group(env: &Env, group_id: u64) -> Result<GroupSave, SavingsError> {
+    get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)
+}
+
+fn is_full(group: &GroupSave) -> bool {
+    group.max_members != 0 && group.member_count >= group.max_members
+}
+
+fn save_join_requests(env: &Env, group_id: u64, requests: &Vec<Address>) {
+    let key = GroupKey::JoinRequests(group_id);
+    if requests.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, requests);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    }
+}
+
+/// Removes `user` from the pending requests, failing if none is pending.
+fn take_join_request(env: &Env, group_id: u64, user: &Address) -> Result<(), SavingsError> {
+    let mut requests = get_join_requests(env, group_id);
+    let index = requests
+        .first_index_of(user)
+        .ok_or(SavingsError::InvalidGroupConfig)?;
+    requests.remove(index);
+    save_join_requests(env, group_id, &requests);
+    Ok(())
+}
+
+/// Adds `user` to the member list and sets up their contribution tracking.
+fn add_member(env: &Env, group: &mut GroupSave, user: &Address) -> Result<(), SavingsError> {
+    let group_id = group.id;
+
+    // Rotating groups collect and pay out through the rosca module
+    if crate::rosca::is_rotating(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    // Settled groups have already paid out
+    if is_settled(env, group_id) {
+        return Err(SavingsError::PlanCompleted);
+    }
 
     // Check if user is already a member
-    for i in 0..members.len() {
-        if let Some(member) = members.get(i) {
-            if member == user {
-                return Err(SavingsError::InvalidGroupConfig);
-            }
-        }
+    let members_key = DataKey::GroupMembers(group_id);
+    let mut members = get_group_members(env, group_id);
+    if members.contains(user) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    if is_full(group) {
+        return Err(SavingsError::GroupFull);
     }
 
     // Add user to members list
@@ -297,10 +845,12 @@ pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), Sa
 
     // Increment member count
     group.member_count += 1;
-    env.storage().persistent().set(&group_key, &group);
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &*group);
 
     // Add group to user's list of groups
-    add_group_to_user_list(env, &user, group_id)?;
+    add_group_to_user_list(env, user, group_id)?;
 
     // Initialize user's contribution to 0
     let contribution_key = DataKey::GroupMemberContribution(group_id, user.clone());
@@ -330,18 +880,85 @@ pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), Sa
 
     // Extend TTL for group and user data
     ttl::extend_group_ttl(env, group_id);
-    ttl::extend_user_ttl(env, &user);
+    ttl::extend_user_ttl(env, user);
     ttl::extend_plan_ttl(env, &plan_key);
 
     // Emit event for joining group
-    env.events()
-        .publish((soroban_sdk::symbol_short!("grp_join"), user), group_id);
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_join"), user.clone()),
+        group_id,
+    );
 
     Ok(())
 }
 
+/// Removes `user` from the group and clears their per-member storage.
+///
+/// Returns what the contribution removed from the pool is worth (see
+/// `release_contribution`); the caller is responsible for refunding it.
+fn remove_member(env: &Env, group: &mut GroupSave, user: &Address) -> Result<i128, SavingsError> {
+    let group_id = group.id;
+
+    // Check if user is a member
+    let members_key = DataKey::GroupMembers(group_id);
+    let mut members: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&members_key)
+        .ok_or(SavingsError::NotGroupMember)?;
+    let index = members
+        .first_index_of(user)
+        .ok_or(SavingsError::NotGroupMember)?;
+
+    // Remove user from members list
+    members.remove(index);
+    env.storage().persistent().set(&members_key, &members);
+
+    // Decrement member count
+    group.member_count = group.member_count.saturating_sub(1);
+
+    // Get user's contribution
+    let contribution_key = DataKey::GroupMemberContribution(group_id, user.clone());
+    let user_contribution: i128 = env
+        .storage()
+        .persistent()
+        .get(&contribution_key)
+        .unwrap_or(0i128);
+
+    // Update group's current_amount
+    let refund = release_contribution(env, group, user_contribution)?;
+
+    // Save updated group
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &*group);
+
+    // Remove user's contribution entries
+    env.storage().persistent().remove(&contribution_key);
+    env.storage()
+        .persistent()
+        .remove(&GroupKey::ContributionTime(group_id, user.clone()));
+
+    // Remove group from user's list of groups
+    remove_group_from_user_list(env, user, group_id)?;
+
+    // Delete user's SavingsPlan for this group
+    let plan_key = DataKey::SavingsPlan(user.clone(), group_id);
+    env.storage().persistent().remove(&plan_key);
+
+    Ok(refund)
+}
+
 /// Allows a group member to contribute funds to the group savings plan.
 ///
+/// The amount is checked against the group's `contribution_type`:
+/// - Fixed: must equal `contribution_amount`
+/// - Flexible: must be at least `contribution_amount`
+/// - Percentage: the contract charges `contribution_amount` bps of the
+///   remaining target; `amount` is the most the member agrees to pay
+///
+/// Groups with a `period_duration` accept one contribution per member per period.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `user` - The address of the user contributing
@@ -351,7 +968,10 @@ pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), Sa
 /// # Returns
 /// `Ok(())` on success
 /// `Err(SavingsError)` if:
-/// - Amount is invalid (<= 0)
+/// - Amount is invalid (<= 0), or differs from a fixed contribution
+/// - Amount is below a flexible group's minimum (`AmountBelowMinimum`)
+/// - A percentage contribution exceeds `amount` (`AmountExceedsLimit`)
+/// - User already contributed this period (`TooEarly`)
 /// - User is not a member
 /// - Group doesn't exist
 pub fn contribute_to_group_save(
@@ -359,8 +979,21 @@ pub fn contribute_to_group_save(
     user: Address,
     group_id: u64,
     amount: i128,
+) -> Result<(), SavingsError> {
+    contribute_funded(env, user, group_id, amount, Funding::Signed)
+}
+
+/// Contributes to a group, authorized and pulled as `funding` says.
+pub(crate) fn contribute_funded(
+    env: &Env,
+    user: Address,
+    group_id: u64,
+    amount: i128,
+    funding: Funding,
 ) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
+    funding.authorize(&user);
+
     // Validate amount > 0
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
@@ -368,11 +1001,17 @@ pub fn contribute_to_group_save(
 
     // Fetch the group
     let group_key = DataKey::GroupSave(group_id);
-    let mut group: GroupSave = env
-        .storage()
-        .persistent()
-        .get(&group_key)
-        .ok_or(SavingsError::PlanNotFound)?;
+    let mut group = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+
+    // Rotating groups collect and pay out through the rosca module
+    if crate::rosca::is_rotating(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    // Settled groups have already paid out
+    if is_settled(env, group_id) {
+        return Err(SavingsError::PlanCompleted);
+    }
 
     // Check if user is a member
     let members_key = DataKey::GroupMembers(group_id);
@@ -396,6 +1035,13 @@ pub fn contribute_to_group_save(
         return Err(SavingsError::NotGroupMember);
     }
 
+    // Apply the group's contribution rules
+    let amount = resolve_contribution(&group, amount)?;
+    record_period_contribution(env, &group, &user)?;
+
+    assets::require_supported(env, &group.asset)?;
+    custody::receive_funded(env, &group.asset, &user, amount, funding)?;
+
     // Update user's contribution
     let contribution_key = DataKey::GroupMemberContribution(group_id, user.clone());
     let current_contribution: i128 = env
@@ -408,6 +1054,24 @@ pub fn contribute_to_group_save(
         .persistent()
         .set(&contribution_key, &new_contribution);
 
+    // Record when the contribution starts earning yield
+    let earning_from = env
+        .ledger()
+        .timestamp()
+        .clamp(group.start_time, group.end_time.max(group.start_time));
+    let time_key = GroupKey::ContributionTime(group_id, user.clone());
+    let contribution_time: i128 = env.storage().persistent().get(&time_key).unwrap_or(0);
+    env.storage().persistent().set(
+        &time_key,
+        &amount
+            .checked_mul(earning_from as i128)
+            .and_then(|t| t.checked_add(contribution_time))
+            .ok_or(SavingsError::Overflow)?,
+    );
+    env.storage()
+        .persistent()
+        .extend_ttl(&time_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
     // Update group's current_amount
     group.current_amount += amount;
 
@@ -541,7 +1205,8 @@ fn remove_group_from_user_list(
 ///
 /// This function handles:
 /// - Removing the user from the group member list
-/// - Refunding the user's contributions
+/// - Refunding the user's contributions, adjusted for the pool's strategy
+///   gains and losses
 /// - Updating group state (member count, current amount)
 /// - Cleaning up all related storage entries
 ///
@@ -556,9 +1221,11 @@ fn remove_group_from_user_list(
 /// - User doesn't exist
 /// - Group doesn't exist
 /// - User is not a member of the group
+/// - The group still holds strategy positions (`InvalidPlanConfig`)
 /// - Group is already completed
 pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
+    user.require_auth();
 
     // Ensure user exists
     if !users::user_exists(env, &user) {
@@ -566,89 +1233,445 @@ pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), S
     }
 
     // Fetch the group
-    let group_key = DataKey::GroupSave(group_id);
-    let mut group: GroupSave = env
-        .storage()
-        .persistent()
-        .get(&group_key)
-        .ok_or(SavingsError::PlanNotFound)?;
+    let mut group = load_group(env, group_id)?;
+
+    // Rotating groups collect and pay out through the rosca module
+    if crate::rosca::is_rotating(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    // Part of the pool may be in strategies
+    routing::require_no_positions(env, StrategyPositionKey::Group(group_id))?;
 
     // Check that the group is not already completed
     if group.is_completed {
         return Err(SavingsError::PlanCompleted);
     }
 
-    // Check if user is a member
-    let members_key = DataKey::GroupMembers(group_id);
-    let members: Vec<Address> = env
-        .storage()
+    let user_contribution = remove_member(env, &mut group, &user)?;
+    governance::record_withdrawal_power(env, &user, user_contribution)?;
+
+    // Extend TTL for group (still active for other members)
+    ttl::extend_group_ttl(env, group_id);
+
+    // Refund the member's contributions
+    custody::send(env, &group.asset, &user, user_contribution)?;
+
+    // Emit event for leaving group
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_leave"), user, group_id),
+        user_contribution,
+    );
+
+    Ok(())
+}
+
+/// Sets the length of a group's contribution period. `0` disables periods.
+///
+/// Periods run back to back from `start_time`. Can only be changed before
+/// the first contribution.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group already holds contributions or is rotating
+pub fn set_group_period(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    period_duration: u64,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    if group.current_amount != 0 || crate::rosca::is_rotating(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    group.period_duration = period_duration;
+    env.storage()
         .persistent()
-        .get(&members_key)
-        .ok_or(SavingsError::NotGroupMember)?;
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
 
-    let mut is_member = false;
-    let mut member_index: Option<u32> = None;
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_prd"), group_id),
+        period_duration,
+    );
 
-    for i in 0..members.len() {
-        if let Some(member) = members.get(i) {
-            if member == user {
-                is_member = true;
-                member_index = Some(i);
-                break;
-            }
-        }
+    Ok(())
+}
+
+/// VIEW FUNCTION - Gets the amount a member must pay on their next contribution
+///
+/// For flexible groups this is the minimum contribution.
+pub fn get_contribution_due(env: &Env, group_id: u64) -> Result<i128, SavingsError> {
+    let group = load_group(env, group_id)?;
+    if group.contribution_type == CONTRIBUTION_PERCENTAGE {
+        percentage_due(&group)
+    } else {
+        Ok(group.contribution_amount)
     }
+}
 
-    if !is_member {
+/// VIEW FUNCTION - Reports a member's compliance with the contribution schedule
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `NotGroupMember` - If `member` is not in the group
+pub fn get_member_compliance(
+    env: &Env,
+    group_id: u64,
+    member: Address,
+) -> Result<GroupCompliance, SavingsError> {
+    let group = load_group(env, group_id)?;
+    if !get_group_members(env, group_id).contains(&member) {
         return Err(SavingsError::NotGroupMember);
     }
 
-    // Remove user from members list
-    let mut new_members = Vec::new(env);
-    for i in 0..members.len() {
-        if Some(i) != member_index {
-            if let Some(member) = members.get(i) {
-                new_members.push_back(member);
+    let total_contributed = get_member_contribution(env, group_id, &member);
+    let record = get_period_record(env, group_id, &member);
+
+    let (periods_elapsed, paid_current_period) = match current_period(env, &group) {
+        Some(period) => (
+            period + 1,
+            record.periods_paid > 0 && record.last_period == period,
+        ),
+        None => (0, false),
+    };
+    let closed_paid = record.periods_paid as u64 - u64::from(paid_current_period);
+    let closed_periods = periods_elapsed.saturating_sub(1);
+    let periods_missed = closed_periods.saturating_sub(closed_paid);
+
+    Ok(GroupCompliance {
+        member,
+        total_contributed,
+        periods_elapsed,
+        periods_paid: record.periods_paid,
+        periods_missed,
+        paid_current_period,
+        is_compliant: periods_missed == 0,
+    })
+}
+
+/// Returns the amount to charge for a contribution of `amount`.
+fn resolve_contribution(group: &GroupSave, amount: i128) -> Result<i128, SavingsError> {
+    match group.contribution_type {
+        CONTRIBUTION_FIXED => {
+            if amount != group.contribution_amount {
+                return Err(SavingsError::InvalidAmount);
+            }
+            Ok(amount)
+        }
+        CONTRIBUTION_FLEXIBLE => {
+            if amount < group.contribution_amount {
+                return Err(SavingsError::AmountBelowMinimum);
             }
+            Ok(amount)
         }
+        _ => {
+            let due = percentage_due(group)?;
+            if due > amount {
+                return Err(SavingsError::AmountExceedsLimit);
+            }
+            Ok(due)
+        }
+    }
+}
+
+/// `contribution_amount` bps of the remaining target, at least 1 unit.
+fn percentage_due(group: &GroupSave) -> Result<i128, SavingsError> {
+    let remaining = group.target_amount - group.current_amount;
+    if remaining <= 0 {
+        return Err(SavingsError::PlanCompleted);
     }
-    env.storage().persistent().set(&members_key, &new_members);
+    let due = remaining
+        .checked_mul(group.contribution_amount)
+        .ok_or(SavingsError::Overflow)?
+        / 10_000;
+    Ok(due.clamp(1, remaining))
+}
 
-    // Decrement member count
-    group.member_count = group.member_count.saturating_sub(1);
+/// Index of the period open at the current ledger time, if periods are enabled
+/// and the group has started. The last period stays open past `end_time`.
+fn current_period(env: &Env, group: &GroupSave) -> Option<u64> {
+    let now = env.ledger().timestamp();
+    if group.period_duration == 0 || now < group.start_time {
+        return None;
+    }
+    let last_second = group.end_time.saturating_sub(1).max(group.start_time);
+    Some((now.min(last_second) - group.start_time) / group.period_duration)
+}
 
-    // Get user's contribution
-    let contribution_key = DataKey::GroupMemberContribution(group_id, user.clone());
-    let user_contribution: i128 = env
-        .storage()
+fn get_period_record(env: &Env, group_id: u64, member: &Address) -> PeriodRecord {
+    env.storage()
         .persistent()
-        .get(&contribution_key)
-        .unwrap_or(0i128);
+        .get(&GroupKey::MemberPeriods(group_id, member.clone()))
+        .unwrap_or(PeriodRecord {
+            last_period: 0,
+            periods_paid: 0,
+        })
+}
 
-    // Update group's current_amount
-    group.current_amount = group.current_amount.saturating_sub(user_contribution);
+/// Marks the current period as paid for `user`, rejecting a second payment.
+fn record_period_contribution(
+    env: &Env,
+    group: &GroupSave,
+    user: &Address,
+) -> Result<(), SavingsError> {
+    if group.period_duration == 0 {
+        return Ok(());
+    }
+    let period = current_period(env, group).ok_or(SavingsError::TooEarly)?;
 
-    // Save updated group
-    env.storage().persistent().set(&group_key, &group);
+    let mut record = get_period_record(env, group.id, user);
+    if record.periods_paid > 0 && record.last_period == period {
+        return Err(SavingsError::TooEarly);
+    }
+    record.last_period = period;
+    record.periods_paid += 1;
 
-    // Remove user's contribution entry
-    env.storage().persistent().remove(&contribution_key);
+    let key = GroupKey::MemberPeriods(group.id, user.clone());
+    env.storage().persistent().set(&key, &record);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
 
-    // Remove group from user's list of groups
-    remove_group_from_user_list(env, &user, group_id)?;
+    Ok(())
+}
 
-    // Delete user's SavingsPlan for this group
-    let plan_key = DataKey::SavingsPlan(user.clone(), group_id);
-    env.storage().persistent().remove(&plan_key);
+/// Settles a group and pays every member out.
+///
+/// A group can be settled once its target has been reached or its
+/// `end_time` has passed; anyone may trigger settlement. Each contribution
+/// earns yield at the group rate for the group's asset from when it was made
+/// (no earlier than `start_time`) until `end_time`, or now if the target was
+/// reached early. Each member receives their `GroupMemberContribution`,
+/// adjusted for the pool's strategy gains and losses, plus the yield on it.
+///
+/// Yield is paid from the treasury reserve of the group's asset; when that
+/// reserve cannot cover the full yield every member's yield is cut pro rata
+/// to what it holds.
+///
+/// Settlement runs in batches: each call tallies the yield of up to
+/// `SETTLE_BATCH` members and, once every member is tallied, pays out up to
+/// `SETTLE_BATCH` members. The first call closes the group to joins,
+/// contributions and breaks; later calls resume where the last one stopped
+/// until `get_group_settlement` reports it completed.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The ID of the group to settle
+///
+/// # Returns
+/// `Ok(i128)` - The amount paid to members by this call
+/// `Err(SavingsError)` if:
+/// - Group doesn't exist
+/// - Group is rotating (`InvalidGroupConfig`)
+/// - The group still holds strategy positions (`InvalidPlanConfig`)
+/// - Group was already settled (`AlreadyWithdrawn`)
+/// - Target not reached and `end_time` not passed (`GroupCycleIncomplete`)
+pub fn settle_group_save(env: &Env, group_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
 
-    // Extend TTL for group (still active for other members)
+    let mut group = load_group(env, group_id)?;
+    let settled_key = GroupKey::Settled(group_id);
+
+    let mut settlement = if is_settled(env, group_id) {
+        // Groups settled before settlement was batched have no progress
+        match get_group_settlement(env, group_id) {
+            Some(settlement) if !settlement.completed => settlement,
+            _ => return Err(SavingsError::AlreadyWithdrawn),
+        }
+    } else {
+        // Rotating groups pay out round by round
+        if crate::rosca::is_rotating(env, group_id) {
+            return Err(SavingsError::InvalidGroupConfig);
+        }
+
+        let now = env.ledger().timestamp();
+        if !group.is_completed && now < group.end_time {
+            return Err(SavingsError::GroupCycleIncomplete);
+        }
+
+        // The whole pool must be back from strategies, yield included
+        routing::require_no_positions(env, StrategyPositionKey::Group(group_id))?;
+
+        // Mark the group settled before tallying anyone, so its members and
+        // contributions stay fixed until every member is paid
+        group.is_completed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::GroupSave(group_id), &group);
+        env.storage().persistent().set(&settled_key, &now);
+
+        GroupSettlement {
+            next_member: 0,
+            tallied: false,
+            earned_total: 0,
+            funded: 0,
+            total_paid: 0,
+            total_yield: 0,
+            completed: false,
+        }
+    };
+    env.storage()
+        .persistent()
+        .extend_ttl(&settled_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
     ttl::extend_group_ttl(env, group_id);
 
-    // Emit event for leaving group
-    env.events().publish(
-        (soroban_sdk::symbol_short!("grp_leave"), user, group_id),
-        user_contribution,
-    );
+    let settled_at: u64 = env.storage().persistent().get(&settled_key).unwrap_or(0);
+    let accrue_until = settled_at.min(group.end_time) as i128;
+    let rate = rates::get_group_rate_for(env, &group.asset);
+    let members = get_group_members(env, group_id);
+
+    // Time-weighted yield per member
+    if !settlement.tallied {
+        let end = members.len().min(settlement.next_member + SETTLE_BATCH);
+        for member in members.slice(settlement.next_member..end).iter() {
+            let (_, earned) = member_settlement(env, &group, &member, accrue_until, rate)?;
+            settlement.earned_total = settlement
+                .earned_total
+                .checked_add(earned)
+                .ok_or(SavingsError::Overflow)?;
+        }
+        settlement.next_member = end;
+
+        if end == members.len() {
+            // Pay yield only as far as the reserve funds it; take it now so
+            // later batches are paid what this one promised
+            settlement.funded =
+                treasury::draw_reserve_in(env, &group.asset, settlement.earned_total);
+            settlement.tallied = true;
+            settlement.next_member = 0;
+        }
+    }
 
-    Ok(())
+    let mut payouts: Vec<(Address, i128)> = Vec::new(env);
+    let mut paid_now: i128 = 0;
+    if settlement.tallied {
+        let now = env.ledger().timestamp();
+        let end = members.len().min(settlement.next_member + SETTLE_BATCH);
+        for member in members.slice(settlement.next_member..end).iter() {
+            let (contribution, earned) =
+                member_settlement(env, &group, &member, accrue_until, rate)?;
+            let share = if settlement.funded == settlement.earned_total {
+                earned
+            } else {
+                earned
+                    .checked_mul(settlement.funded)
+                    .ok_or(SavingsError::Overflow)?
+                    / settlement.earned_total
+            };
+            settlement.total_yield += share;
+            let payout = contribution
+                .checked_add(share)
+                .ok_or(SavingsError::Overflow)?;
+            paid_now = paid_now.checked_add(payout).ok_or(SavingsError::Overflow)?;
+
+            // Close out the member's SavingsPlan
+            let plan_key = DataKey::SavingsPlan(member.clone(), group_id);
+            if let Some(mut plan) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, crate::storage_types::SavingsPlan>(&plan_key)
+            {
+                plan.balance = 0;
+                plan.is_completed = true;
+                plan.is_withdrawn = true;
+                plan.last_withdraw = now;
+                env.storage().persistent().set(&plan_key, &plan);
+                ttl::extend_plan_ttl(env, &plan_key);
+            }
+
+            env.events().publish(
+                (
+                    soroban_sdk::symbol_short!("grp_stl"),
+                    member.clone(),
+                    group_id,
+                ),
+                (contribution, share),
+            );
+
+            governance::record_withdrawal_power(env, &member, contribution)?;
+            payouts.push_back((member, payout));
+        }
+        settlement.next_member = end;
+        settlement.total_paid = settlement
+            .total_paid
+            .checked_add(paid_now)
+            .ok_or(SavingsError::Overflow)?;
+
+        if end == members.len() {
+            settlement.completed = true;
+            // Rounding dust of pro-rata yield goes back to the reserve
+            treasury::restore_reserve_in(
+                env,
+                &group.asset,
+                settlement.funded - settlement.total_yield,
+            );
+            treasury::record_yield(env, &group.asset, settlement.total_yield);
+
+            env.events().publish(
+                (soroban_sdk::symbol_short!("grp_done"), group_id),
+                (group.current_amount, settlement.total_yield),
+            );
+        }
+    }
+
+    let settlement_key = GroupKey::Settlement(group_id);
+    env.storage().persistent().set(&settlement_key, &settlement);
+    env.storage()
+        .persistent()
+        .extend_ttl(&settlement_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    for (member, payout) in payouts.iter() {
+        custody::send(env, &group.asset, &member, payout)?;
+    }
+
+    Ok(paid_now)
+}
+
+/// A member's settlement: their contribution adjusted for the pool's strategy
+/// gains and losses, and the yield it earned until `accrue_until`.
+fn member_settlement(
+    env: &Env,
+    group: &GroupSave,
+    member: &Address,
+    accrue_until: i128,
+    rate: i128,
+) -> Result<(i128, i128), SavingsError> {
+    let contribution = get_member_contribution(env, group.id, member);
+    let contribution_time: i128 = env
+        .storage()
+        .persistent()
+        .get(&GroupKey::ContributionTime(group.id, member.clone()))
+        .unwrap_or(0);
+    // Amount-seconds earned: sum of amount * (accrue_until - earning_from)
+    let weight = contribution
+        .checked_mul(accrue_until)
+        .ok_or(SavingsError::Overflow)?
+        .saturating_sub(contribution_time)
+        .max(0);
+    let earned = rates::calculate_flexi_interest(weight, rate, 1);
+    let value = contribution_value(env, group, contribution)?;
+    Ok((value, earned))
+}
+
+/// VIEW FUNCTION - Returns the progress of paying a group out, once its
+/// settlement has started
+pub fn get_group_settlement(env: &Env, group_id: u64) -> Option<GroupSettlement> {
+    env.storage()
+        .persistent()
+        .get(&GroupKey::Settlement(group_id))
+}
+
+/// VIEW FUNCTION - Checks whether a group has been settled, or its settlement
+/// has started and is being paid out in batches
+pub fn is_settled(env: &Env, group_id: u64) -> bool {
+    env.storage().persistent().has(&GroupKey::Settled(group_id))
 }
@@ -0,0 +1,271 @@
+//! Guardian council for governance.
+//!
+//! A small set of trusted addresses that can act faster than a full vote:
+//! once `threshold` of them approve, a queued proposal is vetoed during its
+//! timelock, or the contract is paused. Pause approvals lapse if the
+//! threshold is not reached within `PAUSE_APPROVAL_WINDOW`, and guardians
+//! cannot veto changes to the council itself. The admin installs the council
+//! before governance is activated; after that only governance proposals can
+//! replace it or sunset it.
+
+use crate::config;
+use crate::errors::SavingsError;
+use crate::governance::{self, ProposalAction, ProposalState};
+use crate::storage_types::DataKey;
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+/// Storage keys for the guardian council
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GuardianKey {
+    /// The current GuardianCouncil
+    Council,
+    /// Maps proposal ID to guardians who approved vetoing it
+    VetoApprovals(u64),
+    /// Guardians who approved pausing the contract
+    PauseApprovals,
+    /// When the first of the current pause approvals was given
+    PauseOpened,
+}
+
+/// How long pause approvals stay valid after the first one is given
+pub const PAUSE_APPROVAL_WINDOW: u64 = 24 * 60 * 60;
+
+/// Members of the guardian council and how many must agree to act
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianCouncil {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Installs the guardian council (admin only, before governance is active)
+///
+/// # Errors
+/// * `Unauthorized` - If the caller is not the admin, or governance is active
+/// * `InvalidAmount` - If the threshold is 0 or above the member count, or
+///   members repeat
+pub fn set_council(
+    env: &Env,
+    admin: Address,
+    members: Vec<Address>,
+    threshold: u32,
+) -> Result<(), SavingsError> {
+    admin.require_auth();
+
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(SavingsError::Unauthorized)?;
+    if admin != stored_admin || governance::is_governance_active(env) {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    write_council(env, members, threshold)
+}
+
+/// Validates and stores a new council; callers are responsible for authorization.
+pub(crate) fn write_council(
+    env: &Env,
+    members: Vec<Address>,
+    threshold: u32,
+) -> Result<(), SavingsError> {
+    validate_council(&members, threshold)?;
+
+    let council = GuardianCouncil { members, threshold };
+    env.storage()
+        .persistent()
+        .set(&GuardianKey::Council, &council);
+    extend_guardian_ttl(env, &GuardianKey::Council);
+    clear_pause_approvals(env);
+
+    env.events().publish(
+        (symbol_short!("grd_set"),),
+        (council.members.len(), threshold),
+    );
+
+    Ok(())
+}
+
+/// Removes the council for good; callers are responsible for authorization.
+pub(crate) fn remove_council(env: &Env) {
+    env.storage().persistent().remove(&GuardianKey::Council);
+    clear_pause_approvals(env);
+
+    env.events().publish((symbol_short!("grd_rm"),), ());
+}
+
+/// Checks that `threshold` is reachable and no member is listed twice
+pub(crate) fn validate_council(members: &Vec<Address>, threshold: u32) -> Result<(), SavingsError> {
+    if threshold == 0 || threshold > members.len() {
+        return Err(SavingsError::InvalidAmount);
+    }
+    for (i, member) in members.iter().enumerate() {
+        if members.first_index_of(&member) != Some(i as u32) {
+            return Err(SavingsError::InvalidAmount);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the current guardian council, if any
+pub fn get_council(env: &Env) -> Option<GuardianCouncil> {
+    env.storage().persistent().get(&GuardianKey::Council)
+}
+
+/// Records a guardian's approval to veto a queued proposal
+///
+/// Once `threshold` guardians approve, the proposal is cancelled. Vetoes are
+/// only possible while the proposal sits in its timelock, and never for
+/// proposals that replace or remove the council.
+///
+/// # Returns
+/// The number of guardians who have approved the veto so far
+///
+/// # Errors
+/// * `Unauthorized` - If there is no council, the caller is not a member, or
+///   the proposal changes the council
+/// * `PlanNotFound` - If the proposal does not exist
+/// * `TooLate` - If the proposal is not queued or its timelock has passed
+/// * `DuplicatePlanId` - If the guardian already approved this veto
+pub fn veto(env: &Env, guardian: Address, proposal_id: u64) -> Result<u32, SavingsError> {
+    guardian.require_auth();
+    let council = require_member(env, &guardian)?;
+
+    let proposal = governance::load_summary(env, proposal_id).ok_or(SavingsError::PlanNotFound)?;
+    if let Some(action_proposal) = governance::get_action_proposal(env, proposal_id) {
+        if changes_council(&action_proposal.action) {
+            return Err(SavingsError::Unauthorized);
+        }
+    }
+    if governance::get_proposal_state(env, proposal_id)? != ProposalState::Queued {
+        return Err(SavingsError::TooLate);
+    }
+    let config = governance::get_voting_config(env)?;
+    let timelock_end = proposal
+        .queued_time
+        .checked_add(config.timelock_duration)
+        .ok_or(SavingsError::Overflow)?;
+    if env.ledger().timestamp() >= timelock_end {
+        return Err(SavingsError::TooLate);
+    }
+
+    let key = GuardianKey::VetoApprovals(proposal_id);
+    let approvals = approve(env, &key, &council, guardian)?;
+
+    if approvals >= council.threshold {
+        governance::mark_cancelled(env, proposal);
+        env.events()
+            .publish((symbol_short!("grd_veto"), proposal_id), approvals);
+    }
+
+    Ok(approvals)
+}
+
+/// Records a guardian's approval to pause the contract
+///
+/// Once `threshold` guardians approve within `PAUSE_APPROVAL_WINDOW` of the
+/// first approval, the contract is paused through the config pause path and
+/// the approvals reset. Approvals older than the window are discarded.
+///
+/// # Returns
+/// The number of guardians who have approved the pause so far
+///
+/// # Errors
+/// * `Unauthorized` - If there is no council or the caller is not a member
+/// * `DuplicatePlanId` - If the guardian already approved the pause
+pub fn pause(env: &Env, guardian: Address) -> Result<u32, SavingsError> {
+    guardian.require_auth();
+    let council = require_member(env, &guardian)?;
+
+    let now = env.ledger().timestamp();
+    let opened: Option<u64> = env.storage().persistent().get(&GuardianKey::PauseOpened);
+    let window_open = opened.is_some_and(|t| now < t.saturating_add(PAUSE_APPROVAL_WINDOW));
+    if !window_open {
+        clear_pause_approvals(env);
+        env.storage()
+            .persistent()
+            .set(&GuardianKey::PauseOpened, &now);
+        extend_guardian_ttl(env, &GuardianKey::PauseOpened);
+    }
+
+    let approvals = approve(
+        env,
+        &GuardianKey::PauseApprovals,
+        &council,
+        guardian.clone(),
+    )?;
+
+    if approvals >= council.threshold {
+        clear_pause_approvals(env);
+        config::set_paused(env, guardian);
+    }
+
+    Ok(approvals)
+}
+
+/// Returns the guardians who have approved vetoing a proposal
+pub fn get_veto_approvals(env: &Env, proposal_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&GuardianKey::VetoApprovals(proposal_id))
+        .unwrap_or(Vec::new(env))
+}
+
+// ========== Helper Functions ==========
+
+/// Whether `action` replaces or removes the council, directly or in a batch
+fn changes_council(action: &ProposalAction) -> bool {
+    match action {
+        ProposalAction::SetGuardians(..) | ProposalAction::RemoveGuardians => true,
+        ProposalAction::Batch(actions) => actions.iter().any(|a| changes_council(&a)),
+        _ => false,
+    }
+}
+
+fn clear_pause_approvals(env: &Env) {
+    env.storage()
+        .persistent()
+        .remove(&GuardianKey::PauseApprovals);
+    env.storage().persistent().remove(&GuardianKey::PauseOpened);
+}
+
+fn require_member(env: &Env, guardian: &Address) -> Result<GuardianCouncil, SavingsError> {
+    let council = get_council(env).ok_or(SavingsError::Unauthorized)?;
+    if !council.members.contains(guardian) {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(council)
+}
+
+/// Adds `guardian` to the approvals under `key` and returns how many current
+/// members have approved; approvals from replaced members no longer count.
+fn approve(
+    env: &Env,
+    key: &GuardianKey,
+    council: &GuardianCouncil,
+    guardian: Address,
+) -> Result<u32, SavingsError> {
+    let mut approvals: Vec<Address> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    if approvals.contains(&guardian) {
+        return Err(SavingsError::DuplicatePlanId);
+    }
+    approvals.push_back(guardian);
+    env.storage().persistent().set(key, &approvals);
+    extend_guardian_ttl(env, key);
+
+    Ok(approvals
+        .iter()
+        .filter(|a| council.members.contains(a))
+        .count() as u32)
+}
+
+fn extend_guardian_ttl(env: &Env, key: &GuardianKey) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    }
+}
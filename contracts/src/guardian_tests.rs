@@ -0,0 +1,272 @@
+#[cfg(test)]
+mod guardian_tests {
+    use crate::governance::{ProposalAction, ProposalState};
+    use crate::rewards::storage_types::RewardsConfig;
+    use crate::{NesteraContract, NesteraContractClient, PlanType, SavingsError};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec, Address, BytesN, Env, String, Vec,
+    };
+
+    const VOTING_PERIOD: u64 = 604800;
+    const TIMELOCK: u64 = 86400;
+
+    fn setup_contract() -> (Env, NesteraContractClient<'static>, Address, Vec<Address>) {
+        let env = Env::default();
+        let contract_id = env.register(NesteraContract, ());
+        let client = NesteraContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &admin_pk);
+
+        let config = RewardsConfig {
+            points_per_token: 10,
+            streak_bonus_bps: 0,
+            long_lock_bonus_bps: 0,
+            goal_completion_bonus: 0,
+            enabled: true,
+            min_deposit_for_rewards: 0,
+            action_cooldown_seconds: 0,
+            max_daily_points: 1_000_000,
+            max_streak_multiplier: 10_000,
+        };
+        client.initialize_rewards_config(&config);
+        client.init_voting_config(&admin, &5000, &VOTING_PERIOD, &TIMELOCK, &100, &10_000);
+
+        let guardians = vec![
+            &env,
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        client.set_guardian_council(&admin, &guardians, &2);
+
+        (env, client, admin, guardians)
+    }
+
+    /// Creates `action` as a proposal, votes it through and queues it
+    fn queue_action(
+        env: &Env,
+        client: &NesteraContractClient<'static>,
+        action: &ProposalAction,
+    ) -> u64 {
+        let creator = Address::generate(env);
+        client.initialize_user(&creator);
+        let _ = client.create_savings_plan(&creator, &PlanType::Flexi, &1000);
+        env.ledger().with_mut(|li| li.timestamp += 1);
+
+        let description = String::from_str(env, "Governed change");
+        let proposal_id = client.create_action_proposal(&creator, &description, action);
+        client.vote(&proposal_id, &1, &creator);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += VOTING_PERIOD + 1;
+        });
+        client.queue_proposal(&proposal_id);
+
+        proposal_id
+    }
+
+    #[test]
+    fn test_threshold_of_guardians_vetoes_queued_proposal() {
+        let (env, client, _admin, guardians) = setup_contract();
+        let proposal_id = queue_action(&env, &client, &ProposalAction::SetFlexiRate(500));
+
+        assert_eq!(
+            client.guardian_veto(&guardians.get(0).unwrap(), &proposal_id),
+            1
+        );
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Queued
+        );
+        assert_eq!(
+            client
+                .try_guardian_veto(&guardians.get(0).unwrap(), &proposal_id)
+                .unwrap_err(),
+            Ok(SavingsError::DuplicatePlanId)
+        );
+
+        assert_eq!(
+            client.guardian_veto(&guardians.get(2).unwrap(), &proposal_id),
+            2
+        );
+        assert_eq!(
+            client.get_proposal_state(&proposal_id),
+            ProposalState::Cancelled
+        );
+        assert_eq!(client.get_veto_approvals(&proposal_id).len(), 2);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += TIMELOCK + 1;
+        });
+        assert!(client.try_execute_proposal(&proposal_id).is_err());
+    }
+
+    #[test]
+    fn test_veto_outside_timelock_fails() {
+        let (env, client, _admin, guardians) = setup_contract();
+        let proposal_id = queue_action(&env, &client, &ProposalAction::SetFlexiRate(500));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += TIMELOCK;
+        });
+        assert_eq!(
+            client
+                .try_guardian_veto(&guardians.get(0).unwrap(), &proposal_id)
+                .unwrap_err(),
+            Ok(SavingsError::TooLate)
+        );
+    }
+
+    #[test]
+    fn test_non_guardian_cannot_veto() {
+        let (env, client, _admin, _guardians) = setup_contract();
+        let proposal_id = queue_action(&env, &client, &ProposalAction::SetFlexiRate(500));
+
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client
+                .try_guardian_veto(&outsider, &proposal_id)
+                .unwrap_err(),
+            Ok(SavingsError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_guardians_trigger_emergency_pause() {
+        let (_env, client, _admin, guardians) = setup_contract();
+
+        assert_eq!(client.guardian_pause(&guardians.get(1).unwrap()), 1);
+        assert!(!client.is_paused());
+
+        assert_eq!(client.guardian_pause(&guardians.get(2).unwrap()), 2);
+        assert!(client.is_paused());
+    }
+
+    #[test]
+    fn test_guardians_cannot_veto_council_changes() {
+        let (env, client, admin, guardians) = setup_contract();
+        client.activate_governance(&admin);
+
+        let batch = ProposalAction::Batch(vec![
+            &env,
+            ProposalAction::SetFlexiRate(500),
+            ProposalAction::RemoveGuardians,
+        ]);
+        for action in [ProposalAction::RemoveGuardians, batch] {
+            let proposal_id = queue_action(&env, &client, &action);
+            assert_eq!(
+                client
+                    .try_guardian_veto(&guardians.get(0).unwrap(), &proposal_id)
+                    .unwrap_err(),
+                Ok(SavingsError::Unauthorized)
+            );
+        }
+    }
+
+    #[test]
+    fn test_stale_pause_approvals_expire() {
+        let (env, client, _admin, guardians) = setup_contract();
+
+        assert_eq!(client.guardian_pause(&guardians.get(0).unwrap()), 1);
+        env.ledger().with_mut(|li| {
+            li.timestamp += crate::guardian::PAUSE_APPROVAL_WINDOW;
+        });
+
+        // The first approval lapsed, so this starts a new window
+        assert_eq!(client.guardian_pause(&guardians.get(1).unwrap()), 1);
+        assert!(!client.is_paused());
+        assert_eq!(client.guardian_pause(&guardians.get(0).unwrap()), 2);
+        assert!(client.is_paused());
+    }
+
+    #[test]
+    fn test_invalid_council_is_rejected() {
+        let env = Env::default();
+        let contract_id = env.register(NesteraContract, ());
+        let client = NesteraContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let guardian = Address::generate(&env);
+        let members = vec![&env, guardian.clone(), Address::generate(&env)];
+        assert_eq!(
+            client
+                .try_set_guardian_council(&admin, &members, &3)
+                .unwrap_err(),
+            Ok(SavingsError::InvalidAmount)
+        );
+
+        let repeated = vec![&env, guardian.clone(), guardian];
+        assert_eq!(
+            client
+                .try_set_guardian_council(&admin, &repeated, &1)
+                .unwrap_err(),
+            Ok(SavingsError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_admin_cannot_replace_council_once_governance_is_active() {
+        let (env, client, admin, _guardians) = setup_contract();
+        client.activate_governance(&admin);
+
+        let members = vec![&env, Address::generate(&env)];
+        assert_eq!(
+            client
+                .try_set_guardian_council(&admin, &members, &1)
+                .unwrap_err(),
+            Ok(SavingsError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_governance_can_sunset_guardians() {
+        let (env, client, admin, guardians) = setup_contract();
+        client.activate_governance(&admin);
+
+        let proposal_id = queue_action(&env, &client, &ProposalAction::RemoveGuardians);
+        env.ledger().with_mut(|li| {
+            li.timestamp += TIMELOCK + 1;
+        });
+        client.execute_proposal(&proposal_id);
+
+        assert!(client.get_guardian_council().is_none());
+        assert_eq!(
+            client
+                .try_guardian_pause(&guardians.get(0).unwrap())
+                .unwrap_err(),
+            Ok(SavingsError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_governance_can_replace_guardians() {
+        let (env, client, admin, guardians) = setup_contract();
+        client.activate_governance(&admin);
+
+        let replacement = Address::generate(&env);
+        let action = ProposalAction::SetGuardians(vec![&env, replacement.clone()], 1);
+        let proposal_id = queue_action(&env, &client, &action);
+        env.ledger().with_mut(|li| {
+            li.timestamp += TIMELOCK + 1;
+        });
+        client.execute_proposal(&proposal_id);
+
+        let council = client.get_guardian_council().unwrap();
+        assert_eq!(council.members, vec![&env, replacement.clone()]);
+        assert_eq!(council.threshold, 1);
+        assert_eq!(
+            client
+                .try_guardian_pause(&guardians.get(0).unwrap())
+                .unwrap_err(),
+            Ok(SavingsError::Unauthorized)
+        );
+        assert_eq!(client.guardian_pause(&replacement), 1);
+        assert!(client.is_paused());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::storage_types::{
+    AutoSaveTarget, DataKey, LegacyAutoSave, LegacyGoalSave, LegacyGroupSave, LegacyLockSave,
+};
+use crate::{NesteraContract, NesteraContractClient};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Symbol,
+};
+
+/// Flexi balance key in the shape earlier versions stored it under
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum OldKey {
+    FlexiBalance(Address),
+}
+
+fn setup() -> (Env, NesteraContractClient<'static>, Address) {
+    let env = Env::default();
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
+
+    env.mock_all_auths();
+    client.initialize(&admin, &admin_pk);
+
+    let user = Address::generate(&env);
+    client.initialize_user(&user);
+    (env, client, user)
+}
+
+#[test]
+fn test_legacy_lock_save_is_read_and_withdrawn() {
+    let (env, client, user) = setup();
+    env.as_contract(&client.address, || {
+        let legacy = LegacyLockSave {
+            id: 7,
+            owner: user.clone(),
+            amount: 1_000,
+            interest_rate: 0,
+            start_time: 0,
+            maturity_time: 100,
+            is_withdrawn: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::LockSave(7), &legacy);
+    });
+
+    let lock = client.get_lock_save_detail(&7);
+    assert_eq!(lock.owner, user);
+    assert_eq!(lock.amount, 1_000);
+    assert_eq!(lock.asset, client.address);
+    assert!(!lock.auto_renew);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.withdraw_lock_save(&user, &7), 1_000);
+    assert!(client.get_lock_save_detail(&7).is_withdrawn);
+}
+
+#[test]
+fn test_legacy_goal_save_keeps_reached_milestones() {
+    let (env, client, user) = setup();
+    env.as_contract(&client.address, || {
+        let legacy = LegacyGoalSave {
+            id: 3,
+            owner: user.clone(),
+            goal_name: Symbol::new(&env, "house"),
+            target_amount: 10_000,
+            current_amount: 6_000,
+            interest_rate: 500,
+            start_time: 50,
+            is_completed: false,
+            is_withdrawn: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalSave(3), &legacy);
+    });
+
+    let goal = client.get_goal_save_detail(&3);
+    assert_eq!(goal.current_amount, 6_000);
+    assert_eq!(goal.milestones_reached, 50);
+    assert_eq!(goal.last_accrual, 50);
+    assert_eq!(goal.deadline, 0);
+    assert_eq!(goal.asset, client.address);
+}
+
+#[test]
+fn test_legacy_group_save_is_read_without_member_cap() {
+    let (env, client, user) = setup();
+    env.as_contract(&client.address, || {
+        let legacy = LegacyGroupSave {
+            id: 2,
+            creator: user.clone(),
+            title: String::from_str(&env, "Trip"),
+            description: String::from_str(&env, "Summer"),
+            category: String::from_str(&env, "travel"),
+            target_amount: 5_000,
+            current_amount: 0,
+            contribution_type: 0,
+            contribution_amount: 100,
+            is_public: true,
+            member_count: 1,
+            start_time: 0,
+            end_time: 1_000,
+            is_completed: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::GroupSave(2), &legacy);
+    });
+
+    let group = client.get_group_save(&2);
+    assert_eq!(group.creator, user);
+    assert_eq!(group.max_members, 0);
+    assert_eq!(group.period_duration, 0);
+    assert_eq!(group.asset, client.address);
+}
+
+#[test]
+fn test_legacy_autosave_targets_flexi() {
+    let (env, client, user) = setup();
+    env.as_contract(&client.address, || {
+        let legacy = LegacyAutoSave {
+            id: 4,
+            user: user.clone(),
+            amount: 250,
+            interval_seconds: 86_400,
+            next_execution_time: 0,
+            is_active: true,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::AutoSave(4), &legacy);
+    });
+
+    let schedule = client.get_autosave(&4).unwrap();
+    assert_eq!(schedule.target, AutoSaveTarget::Flexi);
+    assert_eq!(schedule.amount, 250);
+    assert_eq!(schedule.max_executions, 0);
+
+    client.execute_autosave(&4);
+    assert_eq!(client.get_flexi_balance(&user), 250);
+}
+
+#[test]
+fn test_legacy_flexi_balance_is_migrated_on_use() {
+    let (env, client, user) = setup();
+    let old_key = OldKey::FlexiBalance(user.clone());
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&old_key, &500_i128);
+    });
+
+    assert_eq!(client.get_flexi_balance(&user), 500);
+
+    client.withdraw_flexi(&user, &200);
+    assert_eq!(client.get_flexi_balance(&user), 300);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&old_key));
+    });
+}
@@ -5,14 +5,17 @@ use soroban_sdk::{
     Env, String, Symbol, Vec,
 };
 
+mod assets;
 mod autosave;
 mod config;
+mod custody;
 mod errors;
 mod flexi;
 mod goal;
 mod governance;
 mod governance_events;
 mod group;
+mod guardian;
 mod invariants;
 mod lock;
 
@@ -27,16 +30,23 @@ mod users;
 mod security;
 
 mod rates;
+mod rosca;
 mod views;
 
 pub use crate::config::Config;
 pub use crate::errors::SavingsError;
+pub use crate::group::{GroupCompliance, GroupSettlement};
+pub use crate::guardian::GuardianCouncil;
+pub use crate::rosca::{Rotation, RoundInfo};
 pub use crate::storage_types::{
-    AutoSave, DataKey, GoalSave, GoalSaveView, GroupSave, GroupSaveView, LockSave, LockSaveView,
-    MintPayload, PlanType, SavingsPlan, StrategyPerformance, User,
+    AssetBalance, AutoSave, AutoSaveTarget, DataKey, GoalSave, GoalSaveView, GroupSave,
+    GroupSaveView, LockSave, LockSaveView, MintPayload, PlanType, SavingsPlan, StrategyLoss,
+    StrategyPerformance, User,
 };
+pub use crate::strategy::allocator::{RiskProfile, StrategyAllocation};
+pub use crate::strategy::migration::StrategyMigration;
 pub use crate::strategy::registry::StrategyInfo;
-pub use crate::strategy::routing::{StrategyPosition, StrategyPositionKey};
+pub use crate::strategy::routing::{StrategyPosition, StrategyPositionKey, StrategyPositionValue};
 
 /// Custom error codes for the contract administration
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -307,6 +317,85 @@ impl NesteraContract {
         flexi::get_flexi_balance(&env, user).unwrap_or(0)
     }
 
+    pub fn deposit_flexi_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = flexi::flexi_deposit_asset(env.clone(), user, asset, amount);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    pub fn withdraw_flexi_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = flexi::flexi_withdraw_asset(env.clone(), user, asset, amount);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    pub fn get_flexi_asset_balance(env: Env, user: Address, asset: Address) -> i128 {
+        flexi::get_flexi_asset_balance(&env, user, asset).unwrap_or(0)
+    }
+
+    /// Pays out accrued Flexi interest in the base asset
+    pub fn claim_flexi_interest(env: Env, user: Address) -> Result<i128, SavingsError> {
+        let asset = assets::base_asset(&env);
+        Self::claim_flexi_asset_interest(env, user, asset)
+    }
+
+    /// Pays out accrued Flexi interest in `asset`
+    pub fn claim_flexi_asset_interest(
+        env: Env,
+        user: Address,
+        asset: Address,
+    ) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = flexi::claim_flexi_interest(&env, user, asset);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Returns claimable Flexi interest in `asset`, including interest not yet checkpointed
+    pub fn get_flexi_interest(
+        env: Env,
+        user: Address,
+        asset: Address,
+    ) -> Result<i128, SavingsError> {
+        flexi::get_flexi_interest(&env, &user, &asset)
+    }
+
+    /// Chooses whether Flexi interest is compounded into principal or held for claiming
+    pub fn set_flexi_auto_compound(
+        env: Env,
+        user: Address,
+        enabled: bool,
+    ) -> Result<(), SavingsError> {
+        flexi::set_flexi_auto_compound(&env, user, enabled)
+    }
+
+    pub fn is_flexi_auto_compound(env: Env, user: Address) -> bool {
+        flexi::is_auto_compound(&env, &user)
+    }
+
+    /// Returns the user's open balances per asset, split by plan type
+    pub fn get_user_asset_totals(
+        env: Env,
+        user: Address,
+    ) -> Result<Vec<AssetBalance>, SavingsError> {
+        views::get_user_asset_totals(&env, user)
+    }
+
     // --- Lock Save Logic ---
 
     pub fn create_lock_save(env: Env, user: Address, amount: i128, duration: u64) -> u64 {
@@ -320,6 +409,23 @@ impl NesteraContract {
         res
     }
 
+    pub fn create_asset_lock_save(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+        duration: u64,
+    ) -> u64 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        user.require_auth();
+        crate::security::acquire_reentrancy_guard(&env)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        let res = lock::create_asset_lock_save(&env, user, asset, amount, duration)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
     pub fn withdraw_lock_save(env: Env, user: Address, lock_id: u64) -> i128 {
         ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
         user.require_auth();
@@ -331,6 +437,57 @@ impl NesteraContract {
         res
     }
 
+    /// Breaks a lock before maturity, paying principal minus the sliding penalty
+    pub fn break_lock_save(env: Env, user: Address, lock_id: u64) -> i128 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        user.require_auth();
+        crate::security::acquire_reentrancy_guard(&env)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        let res = lock::break_lock_save(&env, user, lock_id)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Pays out a closed lock's interest the reserve could not cover earlier
+    pub fn claim_lock_interest(
+        env: Env,
+        user: Address,
+        lock_id: u64,
+    ) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        user.require_auth();
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = lock::claim_lock_interest(&env, user, lock_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Returns a closed lock's interest still waiting on the reserve
+    pub fn get_unpaid_lock_interest(env: Env, lock_id: u64) -> i128 {
+        lock::get_unpaid_interest(&env, lock_id)
+    }
+
+    /// Opts a lock into or out of auto-renewal at maturity
+    pub fn set_lock_auto_renew(
+        env: Env,
+        user: Address,
+        lock_id: u64,
+        enabled: bool,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        lock::set_lock_auto_renew(&env, user, lock_id, enabled)
+    }
+
+    /// Rolls a matured auto-renew lock into a new lock (callable by anyone)
+    pub fn rollover_lock_save(env: Env, lock_id: u64) -> Result<u64, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = lock::rollover_lock_save(&env, lock_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
     pub fn check_matured_lock(env: Env, lock_id: u64) -> bool {
         lock::check_matured_lock(&env, lock_id)
     }
@@ -339,6 +496,11 @@ impl NesteraContract {
         lock::get_user_lock_saves(&env, &user)
     }
 
+    pub fn get_lock_save_detail(env: Env, lock_id: u64) -> LockSave {
+        lock::get_lock_save(&env, lock_id)
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsError::PlanNotFound))
+    }
+
     // ========== Goal Save Functions ==========
 
     pub fn create_goal_save(
@@ -357,6 +519,30 @@ impl NesteraContract {
         res
     }
 
+    pub fn create_asset_goal_save(
+        env: Env,
+        user: Address,
+        asset: Address,
+        goal_name: Symbol,
+        target_amount: i128,
+        initial_deposit: i128,
+    ) -> u64 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        crate::security::acquire_reentrancy_guard(&env)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        let res = goal::create_asset_goal_save(
+            &env,
+            user,
+            asset,
+            goal_name,
+            target_amount,
+            initial_deposit,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, e));
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
     pub fn deposit_to_goal_save(env: Env, user: Address, goal_id: u64, amount: i128) {
         ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
         crate::security::acquire_reentrancy_guard(&env)
@@ -386,6 +572,62 @@ impl NesteraContract {
         res
     }
 
+    /// Sets, extends or clears (0) a goal's target date
+    pub fn set_goal_deadline(env: Env, user: Address, goal_id: u64, deadline: u64) {
+        goal::set_goal_deadline(&env, user, goal_id, deadline)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+    }
+
+    /// Returns the balance of a goal that missed its deadline, without the early-break fee
+    pub fn release_missed_goal_save(env: Env, user: Address, goal_id: u64) -> i128 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        crate::security::acquire_reentrancy_guard(&env)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        let res = goal::release_missed_goal_save(&env, user, goal_id)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Pays out a closed goal's interest the reserve could not cover earlier
+    pub fn claim_goal_interest(
+        env: Env,
+        user: Address,
+        goal_id: u64,
+    ) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = goal::claim_goal_interest(&env, user, goal_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Returns a closed goal's interest still waiting on the reserve
+    pub fn get_unpaid_goal_interest(env: Env, goal_id: u64) -> i128 {
+        goal::get_unpaid_interest(&env, goal_id)
+    }
+
+    pub fn set_goal_auto_payout(env: Env, user: Address, goal_id: u64, enabled: bool) {
+        goal::set_goal_auto_payout(&env, user, goal_id, enabled)
+            .unwrap_or_else(|e| panic_with_error!(&env, e));
+    }
+
+    /// Interest accrued on a goal so far
+    pub fn get_goal_interest(env: Env, goal_id: u64) -> i128 {
+        goal::get_goal_interest(&env, goal_id).unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    pub fn is_goal_deadline_missed(env: Env, goal_id: u64) -> bool {
+        let goal_save = goal::get_goal_save(&env, goal_id)
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsError::PlanNotFound));
+        goal::is_deadline_missed(&env, &goal_save)
+    }
+
+    /// Estimated completion time at the goal's average deposit rate
+    pub fn get_goal_projected_completion(env: Env, goal_id: u64) -> Option<u64> {
+        goal::get_projected_completion(&env, goal_id).unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
     pub fn get_goal_save_detail(env: Env, goal_id: u64) -> GoalSave {
         goal::get_goal_save(&env, goal_id)
             .unwrap_or_else(|| panic_with_error!(&env, SavingsError::PlanNotFound))
@@ -429,6 +671,16 @@ impl NesteraContract {
         res
     }
 
+    /// Switches a group to another whitelisted asset before any contributions
+    pub fn set_group_asset(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        group::set_group_asset(&env, creator, group_id, asset)
+    }
+
     pub fn join_group_save(env: Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
         ensure_not_paused(&env)?;
         crate::security::acquire_reentrancy_guard(&env)?;
@@ -458,85 +710,339 @@ impl NesteraContract {
         res
     }
 
-    // --- Admin Control Functions ---
+    /// Closes a finished group and pays members their contributions plus yield
+    pub fn settle_group_save(env: Env, group_id: u64) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = group::settle_group_save(&env, group_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
 
-    pub fn set_admin(
+    pub fn is_group_settled(env: Env, group_id: u64) -> bool {
+        group::is_settled(&env, group_id)
+    }
+
+    /// Returns how far a group's batched settlement has got, once started
+    pub fn get_group_settlement(env: Env, group_id: u64) -> Option<GroupSettlement> {
+        group::get_group_settlement(&env, group_id)
+    }
+
+    // --- Group Membership Functions ---
+
+    pub fn invite_to_group(
         env: Env,
-        current_admin: Address,
-        new_admin: Address,
+        creator: Address,
+        group_id: u64,
+        invitee: Address,
     ) -> Result<(), SavingsError> {
-        current_admin.require_auth();
-        let stored_admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
-        if let Some(admin) = stored_admin {
-            if admin != current_admin {
-                return Err(SavingsError::Unauthorized);
-            }
-        }
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
-        env.events()
-            .publish((symbol_short!("set_admin"),), new_admin);
-        Ok(())
+        group::invite_to_group(&env, creator, group_id, invitee)
     }
 
-    pub fn set_flexi_rate(env: Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
-        rates::set_flexi_rate(&env, caller, rate)
+    pub fn request_to_join_group(
+        env: Env,
+        user: Address,
+        group_id: u64,
+    ) -> Result<(), SavingsError> {
+        group::request_to_join_group(&env, user, group_id)
     }
 
-    pub fn set_goal_rate(env: Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
-        rates::set_goal_rate(&env, caller, rate)
+    pub fn approve_join_request(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        user: Address,
+    ) -> Result<(), SavingsError> {
+        group::approve_join_request(&env, creator, group_id, user)
     }
 
-    pub fn set_group_rate(env: Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
-        rates::set_group_rate(&env, caller, rate)
+    pub fn reject_join_request(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        user: Address,
+    ) -> Result<(), SavingsError> {
+        group::reject_join_request(&env, creator, group_id, user)
     }
 
-    pub fn set_lock_rate(
+    /// Caps group membership; 0 removes the cap
+    pub fn set_group_max_members(
         env: Env,
-        caller: Address,
-        duration_days: u64,
-        rate: i128,
+        creator: Address,
+        group_id: u64,
+        max_members: u32,
     ) -> Result<(), SavingsError> {
-        rates::set_lock_rate(&env, caller, duration_days, rate)
+        group::set_group_max_members(&env, creator, group_id, max_members)
     }
 
-    pub fn set_early_break_fee_bps(env: Env, bps: u32) -> Result<(), SavingsError> {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-        if bps > 10_000 {
-            return Err(SavingsError::InvalidAmount);
-        }
-        env.storage()
-            .instance()
-            .set(&DataKey::EarlyBreakFeeBps, &bps);
-        env.events().publish((symbol_short!("set_brk"),), bps);
-        Ok(())
+    /// Removes a member who has not contributed yet
+    pub fn remove_group_member(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), SavingsError> {
+        group::remove_group_member(&env, creator, group_id, member)
     }
 
-    pub fn set_fee_recipient(env: Env, recipient: Address) -> Result<(), SavingsError> {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-        env.storage()
-            .instance()
-            .set(&DataKey::FeeRecipient, &recipient);
-        env.events().publish((symbol_short!("set_fee"),), recipient);
-        Ok(())
+    pub fn transfer_group_creator(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        new_creator: Address,
+    ) -> Result<(), SavingsError> {
+        group::transfer_group_creator(&env, creator, group_id, new_creator)
     }
 
-    pub fn pause(env: Env, caller: Address) -> Result<(), SavingsError> {
-        caller.require_auth();
-        governance::validate_admin_or_governance(&env, &caller)?;
+    /// Sets the contribution period length; 0 disables periods
+    pub fn set_group_period(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        period_duration: u64,
+    ) -> Result<(), SavingsError> {
+        group::set_group_period(&env, creator, group_id, period_duration)
+    }
 
-        env.storage().persistent().set(&DataKey::Paused, &true);
-        ttl::extend_config_ttl(&env, &DataKey::Paused);
-        env.events().publish((symbol_short!("pause"), caller), ());
-        Ok(())
+    /// Amount due on the next contribution (the minimum for flexible groups)
+    pub fn get_group_contribution_due(env: Env, group_id: u64) -> Result<i128, SavingsError> {
+        group::get_contribution_due(&env, group_id)
     }
 
-    pub fn unpause(env: Env, caller: Address) -> Result<(), SavingsError> {
-        caller.require_auth();
-        governance::validate_admin_or_governance(&env, &caller)?;
+    pub fn get_group_member_compliance(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<GroupCompliance, SavingsError> {
+        group::get_member_compliance(&env, group_id, member)
+    }
 
-        env.storage().persistent().set(&DataKey::Paused, &false);
+    pub fn get_group_members(env: Env, group_id: u64) -> Vec<Address> {
+        group::get_group_members(&env, group_id)
+    }
+
+    pub fn get_group_join_requests(env: Env, group_id: u64) -> Vec<Address> {
+        group::get_join_requests(&env, group_id)
+    }
+
+    pub fn is_invited_to_group(env: Env, group_id: u64, user: Address) -> bool {
+        group::is_invited(&env, group_id, &user)
+    }
+
+    pub fn get_group_save(env: Env, group_id: u64) -> Result<GroupSave, SavingsError> {
+        group::get_group_save(&env, group_id).ok_or(SavingsError::PlanNotFound)
+    }
+
+    // --- Rotating Group (ROSCA) Functions ---
+
+    /// Freezes the member list into a payout order and starts round 0
+    pub fn start_group_rotation(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        cycle_duration: u64,
+        randomize: bool,
+    ) -> Result<Rotation, SavingsError> {
+        rosca::start_rotation(&env, creator, group_id, cycle_duration, randomize)
+    }
+
+    /// Pays the member's fixed contribution for the current round
+    pub fn contribute_to_round(
+        env: Env,
+        user: Address,
+        group_id: u64,
+    ) -> Result<u32, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = rosca::contribute_to_round(&env, user, group_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Pays out the current round once it is fully funded or past its deadline
+    pub fn close_group_round(env: Env, group_id: u64) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = rosca::close_round(&env, group_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Settles all rounds the member missed
+    pub fn pay_group_arrears(env: Env, user: Address, group_id: u64) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = rosca::pay_arrears(&env, user, group_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    pub fn get_group_rotation(env: Env, group_id: u64) -> Option<Rotation> {
+        rosca::get_rotation(&env, group_id)
+    }
+
+    pub fn get_group_payout_order(env: Env, group_id: u64) -> Vec<Address> {
+        rosca::get_payout_order(&env, group_id)
+    }
+
+    pub fn get_group_current_round(env: Env, group_id: u64) -> Result<RoundInfo, SavingsError> {
+        rosca::get_current_round(&env, group_id)
+    }
+
+    pub fn has_paid_group_round(env: Env, group_id: u64, round: u32, member: Address) -> bool {
+        rosca::has_paid_round(&env, group_id, round, &member)
+    }
+
+    pub fn get_group_arrears(env: Env, group_id: u64, member: Address) -> Vec<u32> {
+        rosca::get_arrears(&env, group_id, &member)
+    }
+
+    // --- Admin Control Functions ---
+
+    pub fn set_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), SavingsError> {
+        current_admin.require_auth();
+        let stored_admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if let Some(admin) = stored_admin {
+            if admin != current_admin {
+                return Err(SavingsError::Unauthorized);
+            }
+        }
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.events()
+            .publish((symbol_short!("set_admin"),), new_admin);
+        Ok(())
+    }
+
+    pub fn set_flexi_rate(env: Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+        rates::set_flexi_rate(&env, caller, rate)
+    }
+
+    pub fn set_goal_rate(env: Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+        rates::set_goal_rate(&env, caller, rate)
+    }
+
+    pub fn set_group_rate(env: Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+        rates::set_group_rate(&env, caller, rate)
+    }
+
+    pub fn set_lock_rate(
+        env: Env,
+        caller: Address,
+        duration_days: u64,
+        rate: i128,
+    ) -> Result<(), SavingsError> {
+        rates::set_lock_rate(&env, caller, duration_days, rate)
+    }
+
+    pub fn set_flexi_rate_for(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        rate: i128,
+    ) -> Result<(), SavingsError> {
+        rates::set_flexi_rate_for(&env, caller, asset, rate)
+    }
+
+    pub fn set_goal_rate_for(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        rate: i128,
+    ) -> Result<(), SavingsError> {
+        rates::set_goal_rate_for(&env, caller, asset, rate)
+    }
+
+    pub fn set_group_rate_for(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        rate: i128,
+    ) -> Result<(), SavingsError> {
+        rates::set_group_rate_for(&env, caller, asset, rate)
+    }
+
+    pub fn set_lock_rate_for(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        duration_days: u64,
+        rate: i128,
+    ) -> Result<(), SavingsError> {
+        rates::set_lock_rate_for(&env, caller, asset, duration_days, rate)
+    }
+
+    pub fn set_early_break_fee_bps(env: Env, bps: u32) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if bps > 10_000 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EarlyBreakFeeBps, &bps);
+        env.events().publish((symbol_short!("set_brk"),), bps);
+        Ok(())
+    }
+
+    /// Sets the maximum lock break penalty, charged in full at lock start and
+    /// decreasing linearly to zero at maturity
+    pub fn set_lock_break_penalty_bps(env: Env, bps: u32) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if bps > 10_000 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::LockBreakPenaltyBps, &bps);
+        env.events().publish((symbol_short!("set_lbrk"),), bps);
+        Ok(())
+    }
+
+    /// Sets the share of accrued goal interest lost when a goal is broken or
+    /// released after a missed deadline
+    pub fn set_goal_interest_forfeit_bps(env: Env, bps: u32) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if bps > 10_000 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::GoalInterestForfeitBps, &bps);
+        env.events().publish((symbol_short!("set_gint"),), bps);
+        Ok(())
+    }
+
+    pub fn set_fee_recipient(env: Env, recipient: Address) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeRecipient, &recipient);
+        env.events().publish((symbol_short!("set_fee"),), recipient);
+        Ok(())
+    }
+
+    pub fn pause(env: Env, caller: Address) -> Result<(), SavingsError> {
+        caller.require_auth();
+        governance::validate_admin_or_governance(&env, &caller)?;
+
+        env.storage().persistent().set(&DataKey::Paused, &true);
+        ttl::extend_config_ttl(&env, &DataKey::Paused);
+        env.events().publish((symbol_short!("pause"), caller), ());
+        Ok(())
+    }
+
+    pub fn unpause(env: Env, caller: Address) -> Result<(), SavingsError> {
+        caller.require_auth();
+        governance::validate_admin_or_governance(&env, &caller)?;
+
+        env.storage().persistent().set(&DataKey::Paused, &false);
         ttl::extend_config_ttl(&env, &DataKey::Paused);
         env.events().publish((symbol_short!("unpause"), caller), ());
         Ok(())
@@ -554,7 +1060,8 @@ impl NesteraContract {
     /// * `plan_id` - The ID of the plan to withdraw from
     ///
     /// # Returns
-    /// * The amount withdrawn
+    /// * The amount withdrawn; for Flexi, the sum of the user's balances in
+    ///   every asset, each paid out in its own asset
     pub fn emergency_withdraw(
         env: Env,
         admin: Address,
@@ -576,13 +1083,29 @@ impl NesteraContract {
         }
 
         // 3. Perform withdrawal based on plan type
+        let mut payouts: Vec<(Address, i128)> = Vec::new(&env);
         let withdrawn_amount = match plan_type {
             PlanType::Flexi => {
-                // For Flexi, withdraw the entire balance
-                let flexi_key = DataKey::FlexiBalance(user.clone());
-                let balance: i128 = env.storage().persistent().get(&flexi_key).unwrap_or(0);
+                // For Flexi, withdraw the entire balance in every asset
+                // (interest settled first)
+                let base = assets::base_asset(&env);
+                let mut flexi_assets = Vec::new(&env);
+                flexi_assets.push_back(base.clone());
+                for asset in assets::get_all_assets(&env).iter() {
+                    if asset != base {
+                        flexi_assets.push_back(asset);
+                    }
+                }
+
+                let mut total: i128 = 0;
+                for asset in flexi_assets.iter() {
+                    flexi::accrue_interest(&env, &user, &asset)?;
+                    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
+                    let balance: i128 = env.storage().persistent().get(&flexi_key).unwrap_or(0);
+                    if balance <= 0 {
+                        continue;
+                    }
 
-                if balance > 0 {
                     // Update flexi balance to 0
                     env.storage().persistent().set(&flexi_key, &0i128);
 
@@ -594,21 +1117,32 @@ impl NesteraContract {
                         user_data.total_balance = user_data.total_balance.saturating_sub(balance);
                         env.storage().persistent().set(&user_key, &user_data);
                     }
+                    payouts.push_back((asset, balance));
+                    total = total.checked_add(balance).ok_or(SavingsError::Overflow)?;
                 }
-                balance
+                total
             }
             PlanType::Lock(_) => {
                 // For Lock, get the lock save and withdraw if exists
                 let lock_key = DataKey::LockSave(plan_id);
-                let lock_opt: Option<LockSave> = env.storage().persistent().get(&lock_key);
+                let lock_opt = lock::get_lock_save(&env, plan_id);
 
                 if let Some(mut lock) = lock_opt {
+                    if lock.owner != user {
+                        return Err(SavingsError::Unauthorized);
+                    }
                     if lock.is_withdrawn {
                         return Err(SavingsError::AlreadyWithdrawn);
                     }
+                    strategy::routing::require_no_positions(
+                        &env,
+                        StrategyPositionKey::Lock(plan_id),
+                    )?;
                     let amount = lock.amount;
+                    payouts.push_back((lock.asset.clone(), amount));
                     lock.is_withdrawn = true;
                     env.storage().persistent().set(&lock_key, &lock);
+                    governance::release_lock_conviction(&env, &lock);
 
                     // Update user total balance
                     let user_key = DataKey::User(user.clone());
@@ -626,13 +1160,17 @@ impl NesteraContract {
             PlanType::Goal(_, _, _) => {
                 // For Goal, get the goal save and withdraw
                 let goal_key = DataKey::GoalSave(plan_id);
-                let goal_opt: Option<GoalSave> = env.storage().persistent().get(&goal_key);
+                let goal_opt = goal::get_goal_save(&env, plan_id);
 
                 if let Some(mut goal) = goal_opt {
+                    if goal.owner != user {
+                        return Err(SavingsError::Unauthorized);
+                    }
                     if goal.is_withdrawn {
                         return Err(SavingsError::AlreadyWithdrawn);
                     }
                     let amount = goal.current_amount;
+                    payouts.push_back((goal.asset.clone(), amount));
                     goal.is_withdrawn = true;
                     env.storage().persistent().set(&goal_key, &goal);
 
@@ -652,12 +1190,16 @@ impl NesteraContract {
             PlanType::Group(_, _, _, _) => {
                 // For Group, get the group save and process emergency break
                 let group_key = DataKey::GroupSave(plan_id);
-                let group_opt: Option<GroupSave> = env.storage().persistent().get(&group_key);
+                let group_opt = group::get_group_save(&env, plan_id);
 
                 if let Some(mut group) = group_opt {
                     if group.is_completed {
                         return Err(SavingsError::PlanCompleted);
                     }
+                    strategy::routing::require_no_positions(
+                        &env,
+                        StrategyPositionKey::Group(plan_id),
+                    )?;
                     // Return current amount for the user
                     let contribution_key = DataKey::GroupMemberContribution(plan_id, user.clone());
                     let contribution: i128 = env
@@ -666,12 +1208,13 @@ impl NesteraContract {
                         .get(&contribution_key)
                         .unwrap_or(0);
 
+                    let mut refund = 0;
                     if contribution > 0 {
                         // Clear user contribution
                         env.storage().persistent().set(&contribution_key, &0i128);
 
                         // Update group current amount
-                        group.current_amount = group.current_amount.saturating_sub(contribution);
+                        refund = group::release_contribution(&env, &mut group, contribution)?;
                         env.storage().persistent().set(&group_key, &group);
 
                         // Update user total balance
@@ -684,7 +1227,8 @@ impl NesteraContract {
                             env.storage().persistent().set(&user_key, &user_data);
                         }
                     }
-                    contribution
+                    payouts.push_back((group.asset.clone(), refund));
+                    refund
                 } else {
                     return Err(SavingsError::PlanNotFound);
                 }
@@ -695,6 +1239,11 @@ impl NesteraContract {
         env.storage().persistent().set(&disabled_key, &true);
         ttl::extend_config_ttl(&env, &disabled_key);
 
+        governance::record_withdrawal_power(&env, &user, withdrawn_amount)?;
+        for (asset, amount) in payouts.iter() {
+            custody::send(&env, &asset, &user, amount)?;
+        }
+
         // 5. Emit event
         env.events().publish(
             (Symbol::new(&env, "emergency_withdraw"), user, plan_id),
@@ -748,6 +1297,31 @@ impl NesteraContract {
         rates::get_lock_rate(&env, duration_days)
     }
 
+    pub fn get_flexi_rate_for(env: Env, asset: Address) -> i128 {
+        rates::get_flexi_rate_for(&env, &asset)
+    }
+
+    pub fn get_goal_rate_for(env: Env, asset: Address) -> i128 {
+        rates::get_goal_rate_for(&env, &asset)
+    }
+
+    pub fn get_group_rate_for(env: Env, asset: Address) -> i128 {
+        rates::get_group_rate_for(&env, &asset)
+    }
+
+    pub fn get_lock_rate_for(
+        env: Env,
+        asset: Address,
+        duration_days: u64,
+    ) -> Result<i128, SavingsError> {
+        rates::get_lock_rate_for(&env, &asset, duration_days)
+    }
+
+    /// Returns the configured lock durations (days) for `asset`, ascending
+    pub fn get_lock_rate_tiers(env: Env, asset: Address) -> Vec<u64> {
+        rates::get_lock_rate_tiers(&env, &asset)
+    }
+
     pub fn get_early_break_fee_bps(env: Env) -> u32 {
         env.storage()
             .instance()
@@ -755,6 +1329,20 @@ impl NesteraContract {
             .unwrap_or(0)
     }
 
+    pub fn get_lock_break_penalty_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LockBreakPenaltyBps)
+            .unwrap_or(0)
+    }
+
+    pub fn get_goal_interest_forfeit_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::GoalInterestForfeitBps)
+            .unwrap_or(10_000)
+    }
+
     pub fn get_fee_recipient(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::FeeRecipient)
     }
@@ -892,6 +1480,29 @@ impl NesteraContract {
         res
     }
 
+    /// Creates a new AutoSave schedule that deposits into a Lock, Goal, Group or Flexi target
+    pub fn create_targeted_autosave(
+        env: Env,
+        user: Address,
+        target: AutoSaveTarget,
+        amount: i128,
+        interval_seconds: u64,
+        start_time: u64,
+    ) -> Result<u64, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = autosave::create_targeted_autosave(
+            &env,
+            user,
+            target,
+            amount,
+            interval_seconds,
+            start_time,
+        );
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
     /// Executes an AutoSave schedule if it's due
     pub fn execute_autosave(env: Env, schedule_id: u64) -> Result<(), SavingsError> {
         ensure_not_paused(&env)?;
@@ -907,52 +1518,136 @@ impl NesteraContract {
         autosave::execute_due_autosaves(&env, schedule_ids)
     }
 
-    /// Cancels an AutoSave schedule
-    pub fn cancel_autosave(env: Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+    /// Batch-executes due AutoSave schedules and pays `keeper` the fees they fund.
+    pub fn execute_autosaves_as_keeper(
+        env: Env,
+        keeper: Address,
+        schedule_ids: Vec<u64>,
+    ) -> Result<Vec<bool>, SavingsError> {
         ensure_not_paused(&env)?;
         crate::security::acquire_reentrancy_guard(&env)?;
-        let res = autosave::cancel_autosave(&env, user, schedule_id);
+        let res = autosave::execute_autosaves_as_keeper(&env, keeper, schedule_ids);
         crate::security::release_reentrancy_guard(&env);
         res
     }
 
-    /// Gets an AutoSave schedule by ID
-    pub fn get_autosave(env: Env, schedule_id: u64) -> Option<AutoSave> {
-        autosave::get_autosave(&env, schedule_id)
+    /// Lists up to `limit` due AutoSave schedule IDs, skipping the first `cursor`
+    pub fn get_due_autosaves(env: Env, cursor: u32, limit: u32) -> Vec<u64> {
+        autosave::get_due_autosaves(&env, cursor, limit)
     }
 
-    /// Gets all AutoSave schedule IDs for a user
-    pub fn get_user_autosaves(env: Env, user: Address) -> Vec<u64> {
-        autosave::get_user_autosaves(&env, &user)
+    /// Sets the per-execution keeper fee for an AutoSave schedule
+    pub fn set_autosave_keeper_fee(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        fee: i128,
+    ) -> Result<(), SavingsError> {
+        autosave::set_keeper_fee(&env, user, schedule_id, fee)
     }
 
-    // ========== Config Functions ==========
-
-    /// Initializes the protocol configuration. Can only be called once.
-    pub fn initialize_config(
+    /// Tops up the prepaid keeper balance of an AutoSave schedule; returns the new balance
+    pub fn fund_autosave_keeper(
         env: Env,
-        admin: Address,
-        treasury: Address,
-        deposit_fee_bps: u32,
-        withdrawal_fee_bps: u32,
-        performance_fee_bps: u32,
-    ) -> Result<(), SavingsError> {
-        config::initialize_config(
-            &env,
-            admin,
-            treasury,
-            deposit_fee_bps,
-            withdrawal_fee_bps,
-            performance_fee_bps,
-        )
+        user: Address,
+        schedule_id: u64,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = autosave::fund_keeper(&env, user, schedule_id, amount);
+        crate::security::release_reentrancy_guard(&env);
+        res
     }
 
-    /// Returns the current global configuration
-    pub fn get_config(env: Env) -> Result<Config, SavingsError> {
-        config::get_config(&env)
+    /// Pauses an AutoSave schedule until it is resumed
+    pub fn pause_autosave(env: Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+        autosave::pause_autosave(&env, user, schedule_id)
     }
 
-    /// Updates the treasury address (admin only)
+    /// Resumes a paused AutoSave schedule
+    pub fn resume_autosave(env: Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+        autosave::resume_autosave(&env, user, schedule_id)
+    }
+
+    /// Changes the amount and interval of an AutoSave schedule
+    pub fn update_autosave(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        amount: i128,
+        interval_seconds: u64,
+    ) -> Result<(), SavingsError> {
+        autosave::update_autosave(&env, user, schedule_id, amount, interval_seconds)
+    }
+
+    /// Sets an optional end date and run limit for an AutoSave schedule (0 = none)
+    pub fn set_autosave_limits(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        end_time: u64,
+        max_executions: u32,
+    ) -> Result<(), SavingsError> {
+        autosave::set_autosave_limits(&env, user, schedule_id, end_time, max_executions)
+    }
+
+    /// Chooses whether missed AutoSave runs are executed one by one or skipped
+    pub fn set_autosave_catch_up(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        catch_up: bool,
+    ) -> Result<(), SavingsError> {
+        autosave::set_autosave_catch_up(&env, user, schedule_id, catch_up)
+    }
+
+    /// Cancels an AutoSave schedule
+    pub fn cancel_autosave(env: Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = autosave::cancel_autosave(&env, user, schedule_id);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Gets an AutoSave schedule by ID
+    pub fn get_autosave(env: Env, schedule_id: u64) -> Option<AutoSave> {
+        autosave::get_autosave(&env, schedule_id)
+    }
+
+    /// Gets all AutoSave schedule IDs for a user
+    pub fn get_user_autosaves(env: Env, user: Address) -> Vec<u64> {
+        autosave::get_user_autosaves(&env, &user)
+    }
+
+    // ========== Config Functions ==========
+
+    /// Initializes the protocol configuration. Can only be called once.
+    pub fn initialize_config(
+        env: Env,
+        admin: Address,
+        treasury: Address,
+        deposit_fee_bps: u32,
+        withdrawal_fee_bps: u32,
+        performance_fee_bps: u32,
+    ) -> Result<(), SavingsError> {
+        config::initialize_config(
+            &env,
+            admin,
+            treasury,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            performance_fee_bps,
+        )
+    }
+
+    /// Returns the current global configuration
+    pub fn get_config(env: Env) -> Result<Config, SavingsError> {
+        config::get_config(&env)
+    }
+
+    /// Updates the treasury address (admin only)
     pub fn set_treasury(
         env: Env,
         admin: Address,
@@ -972,6 +1667,47 @@ impl NesteraContract {
         config::set_fees(&env, admin, deposit_fee, withdrawal_fee, performance_fee)
     }
 
+    /// Sets the SEP-41 token held in custody for all savings plans (admin only, one-time)
+    pub fn set_underlying_asset(
+        env: Env,
+        admin: Address,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        config::set_underlying_asset(&env, admin, asset)
+    }
+
+    /// Returns the configured underlying asset, if any
+    pub fn get_underlying_asset(env: Env) -> Option<Address> {
+        config::get_underlying_asset(&env)
+    }
+
+    /// Whitelists an asset for savings deposits (admin or governance)
+    pub fn add_supported_asset(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        assets::add_supported_asset(&env, caller, asset)
+    }
+
+    /// Stops new deposits in an asset; existing plans can still be withdrawn
+    pub fn remove_supported_asset(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        assets::remove_supported_asset(&env, caller, asset)
+    }
+
+    /// Returns the base asset followed by every asset accepting deposits
+    pub fn get_supported_assets(env: Env) -> Vec<Address> {
+        assets::get_supported_assets(&env)
+    }
+
+    pub fn is_asset_supported(env: Env, asset: Address) -> bool {
+        assets::is_supported(&env, &asset)
+    }
+
     /// Pauses the contract via config module (admin only)
     pub fn pause_contract(env: Env, admin: Address) -> Result<(), SavingsError> {
         config::pause_contract(&env, admin)
@@ -992,11 +1728,16 @@ impl NesteraContract {
 
     // ========== Treasury Functions ==========
 
-    /// Returns the current treasury state
+    /// Returns the current treasury state of the base asset
     pub fn get_treasury(env: Env) -> treasury::types::Treasury {
         treasury::get_treasury(&env)
     }
 
+    /// Returns the current treasury state of `asset`
+    pub fn get_asset_treasury(env: Env, asset: Address) -> treasury::types::Treasury {
+        treasury::get_asset_treasury(&env, &asset)
+    }
+
     /// Returns the unallocated treasury balance (fees pending allocation).
     pub fn get_treasury_balance(env: Env) -> i128 {
         treasury::get_treasury_balance(&env)
@@ -1017,7 +1758,24 @@ impl NesteraContract {
         treasury::get_reserve_balance(&env)
     }
 
-    /// Allocates the unallocated treasury balance into reserves, rewards, and operations.
+    /// Adds base-asset funds from `funder` to the treasury reserve.
+    pub fn fund_reserve(env: Env, funder: Address, amount: i128) -> Result<i128, SavingsError> {
+        let asset = assets::base_asset(&env);
+        Self::fund_asset_reserve(env, funder, asset, amount)
+    }
+
+    /// Adds `asset` funds from `funder` to that asset's treasury reserve.
+    pub fn fund_asset_reserve(
+        env: Env,
+        funder: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        treasury::fund_reserve(&env, funder, asset, amount)
+    }
+
+    /// Allocates the unallocated treasury balance of every asset into reserves, rewards, and operations.
     /// Percentages are in basis points and must sum to 10_000.
     pub fn allocate_treasury(
         env: Env,
@@ -1053,6 +1811,8 @@ impl NesteraContract {
             timelock_duration,
             proposal_threshold,
             max_voting_power,
+            grace_period: governance::DEFAULT_GRACE_PERIOD,
+            weighting: governance::VoteWeighting::Linear,
         };
         governance::init_voting_config(&env, admin, config)
     }
@@ -1096,11 +1856,31 @@ impl NesteraContract {
         governance::list_proposals(&env)
     }
 
-    /// Gets the voting power for a user based on their lifetime deposited funds
+    /// Gets the voting power a user currently controls, including delegations
     pub fn get_voting_power(env: Env, user: Address) -> u128 {
         governance::get_voting_power(&env, &user)
     }
 
+    /// Gets the voting power a user controlled at `timestamp`
+    pub fn get_past_voting_power(env: Env, user: Address, timestamp: u64) -> u128 {
+        governance::get_past_voting_power(&env, &user, timestamp)
+    }
+
+    /// Hands the caller's voting power to another account
+    pub fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), SavingsError> {
+        governance::delegate(&env, delegator, delegatee)
+    }
+
+    /// Takes the caller's voting power back from its delegate
+    pub fn undelegate(env: Env, delegator: Address) -> Result<(), SavingsError> {
+        governance::undelegate(&env, delegator)
+    }
+
+    /// Gets the account a user has delegated their voting power to, if any
+    pub fn get_delegate(env: Env, user: Address) -> Option<Address> {
+        governance::get_delegate(&env, &user)
+    }
+
     /// Casts a weighted vote on a proposal
     pub fn vote(
         env: Env,
@@ -1111,6 +1891,51 @@ impl NesteraContract {
         governance::vote(&env, proposal_id, vote_type, voter)
     }
 
+    /// Casts a weighted vote on a proposal with a stored reason
+    pub fn vote_with_reason(
+        env: Env,
+        proposal_id: u64,
+        vote_type: u32,
+        voter: Address,
+        reason: String,
+    ) -> Result<(), SavingsError> {
+        governance::vote_with_reason(&env, proposal_id, vote_type, voter, reason)
+    }
+
+    /// Replaces an earlier vote while the proposal is still open
+    pub fn change_vote(
+        env: Env,
+        proposal_id: u64,
+        vote_type: u32,
+        voter: Address,
+        reason: String,
+    ) -> Result<(), SavingsError> {
+        governance::change_vote(&env, proposal_id, vote_type, voter, reason)
+    }
+
+    /// Gets the vote a user cast on a proposal
+    pub fn get_vote_record(
+        env: Env,
+        proposal_id: u64,
+        voter: Address,
+    ) -> Option<governance::VoteRecord> {
+        governance::get_vote_record(&env, proposal_id, &voter)
+    }
+
+    /// Chooses linear, quadratic or conviction vote weighting (admin only)
+    pub fn set_vote_weighting(
+        env: Env,
+        admin: Address,
+        weighting: governance::VoteWeighting,
+    ) -> Result<(), SavingsError> {
+        governance::set_vote_weighting(&env, admin, weighting)
+    }
+
+    /// Gets the vote weighting a proposal was created under
+    pub fn get_proposal_weighting(env: Env, proposal_id: u64) -> governance::VoteWeighting {
+        governance::get_proposal_weighting(&env, proposal_id)
+    }
+
     /// Checks if a user has voted on a proposal
     pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
         governance::has_voted(&env, proposal_id, &voter)
@@ -1126,6 +1951,71 @@ impl NesteraContract {
         governance::execute_proposal(&env, proposal_id)
     }
 
+    /// Cancels a proposal before it is queued (creator or admin only)
+    pub fn cancel_proposal(
+        env: Env,
+        proposal_id: u64,
+        caller: Address,
+    ) -> Result<(), SavingsError> {
+        governance::cancel_proposal(&env, proposal_id, caller)
+    }
+
+    /// Gets the lifecycle state of a proposal
+    pub fn get_proposal_state(
+        env: Env,
+        proposal_id: u64,
+    ) -> Result<governance::ProposalState, SavingsError> {
+        governance::get_proposal_state(&env, proposal_id)
+    }
+
+    /// Sets how long queued proposals remain executable after their timelock (admin only)
+    pub fn set_proposal_grace_period(
+        env: Env,
+        admin: Address,
+        grace_period: u64,
+    ) -> Result<(), SavingsError> {
+        governance::set_grace_period(&env, admin, grace_period)
+    }
+
+    /// Gets the total voting power held across all accounts
+    pub fn get_total_voting_supply(env: Env) -> u128 {
+        governance::get_total_voting_supply(&env)
+    }
+
+    /// Installs the guardian council (admin only, before governance is active)
+    pub fn set_guardian_council(
+        env: Env,
+        admin: Address,
+        members: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), SavingsError> {
+        guardian::set_council(&env, admin, members, threshold)
+    }
+
+    /// Gets the guardian council, if one is installed
+    pub fn get_guardian_council(env: Env) -> Option<GuardianCouncil> {
+        guardian::get_council(&env)
+    }
+
+    /// Approves vetoing a queued proposal; returns the approvals so far
+    pub fn guardian_veto(
+        env: Env,
+        guardian: Address,
+        proposal_id: u64,
+    ) -> Result<u32, SavingsError> {
+        guardian::veto(&env, guardian, proposal_id)
+    }
+
+    /// Approves an emergency pause; returns the approvals so far
+    pub fn guardian_pause(env: Env, guardian: Address) -> Result<u32, SavingsError> {
+        guardian::pause(&env, guardian)
+    }
+
+    /// Gets the guardians who approved vetoing a proposal
+    pub fn get_veto_approvals(env: Env, proposal_id: u64) -> Vec<Address> {
+        guardian::get_veto_approvals(&env, proposal_id)
+    }
+
     /// Activates governance (admin only, one-time)
     pub fn activate_governance(env: Env, admin: Address) -> Result<(), SavingsError> {
         governance::activate_governance(&env, admin)
@@ -1148,6 +2038,29 @@ impl NesteraContract {
         strategy::registry::register_strategy(&env, caller, strategy_address, risk_level)
     }
 
+    /// Registers a new yield strategy taking a whitelisted asset other than
+    /// the base asset (admin/governance only).
+    pub fn register_asset_strategy(
+        env: Env,
+        caller: Address,
+        strategy_address: Address,
+        risk_level: u32,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        strategy::registry::register_asset_strategy(
+            &env,
+            caller,
+            strategy_address,
+            risk_level,
+            asset,
+        )
+    }
+
+    /// Returns the asset a strategy takes.
+    pub fn get_strategy_asset(env: Env, strategy_address: Address) -> Address {
+        strategy::registry::get_strategy_asset(&env, &strategy_address)
+    }
+
     /// Disables a registered yield strategy (admin/governance only).
     pub fn disable_strategy(
         env: Env,
@@ -1157,6 +2070,23 @@ impl NesteraContract {
         strategy::registry::disable_strategy(&env, caller, strategy_address)
     }
 
+    /// Sets a strategy's deposit cap and TVL share limit (admin/governance only).
+    pub fn set_strategy_limits(
+        env: Env,
+        caller: Address,
+        strategy_address: Address,
+        deposit_cap: i128,
+        max_tvl_share_bps: u32,
+    ) -> Result<(), SavingsError> {
+        strategy::registry::set_strategy_limits(
+            &env,
+            caller,
+            strategy_address,
+            deposit_cap,
+            max_tvl_share_bps,
+        )
+    }
+
     /// Returns info about a registered strategy.
     pub fn get_strategy(env: Env, strategy_address: Address) -> Result<StrategyInfo, SavingsError> {
         strategy::registry::get_strategy(&env, strategy_address)
@@ -1167,7 +2097,8 @@ impl NesteraContract {
         strategy::registry::get_all_strategies(&env)
     }
 
-    /// Routes a LockSave deposit to a yield strategy.
+    /// Routes part of a LockSave deposit to a yield strategy (lock owner only,
+    /// up to the lock's unallocated balance).
     pub fn route_lock_to_strategy(
         env: Env,
         caller: Address,
@@ -1179,13 +2110,19 @@ impl NesteraContract {
         ensure_not_paused(&env)?;
         crate::security::acquire_reentrancy_guard(&env)?;
         let position_key = StrategyPositionKey::Lock(lock_id);
-        let res =
-            strategy::routing::route_to_strategy(&env, strategy_address, position_key, amount);
+        let res = strategy::routing::route_to_strategy(
+            &env,
+            &caller,
+            strategy_address,
+            position_key,
+            amount,
+        );
         crate::security::release_reentrancy_guard(&env);
         res
     }
 
-    /// Routes a GroupSave pooled deposit to a yield strategy.
+    /// Routes part of a GroupSave pool to a yield strategy (group creator only,
+    /// up to the pool's unallocated balance).
     pub fn route_group_to_strategy(
         env: Env,
         caller: Address,
@@ -1197,24 +2134,156 @@ impl NesteraContract {
         ensure_not_paused(&env)?;
         crate::security::acquire_reentrancy_guard(&env)?;
         let position_key = StrategyPositionKey::Group(group_id);
-        let res =
-            strategy::routing::route_to_strategy(&env, strategy_address, position_key, amount);
+        let res = strategy::routing::route_to_strategy(
+            &env,
+            &caller,
+            strategy_address,
+            position_key,
+            amount,
+        );
         crate::security::release_reentrancy_guard(&env);
         res
     }
 
-    /// Returns the strategy position for a lock plan.
-    pub fn get_lock_strategy_position(env: Env, lock_id: u64) -> Option<StrategyPosition> {
-        strategy::routing::get_position(&env, StrategyPositionKey::Lock(lock_id))
+    /// Sets the risk profile used to allocate a lock's funds (lock owner only).
+    pub fn set_lock_risk_profile(
+        env: Env,
+        caller: Address,
+        lock_id: u64,
+        profile: RiskProfile,
+    ) -> Result<(), SavingsError> {
+        caller.require_auth();
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
+        strategy::allocator::set_risk_profile(&env, position_key, profile);
+        Ok(())
     }
 
-    /// Returns the strategy position for a group plan.
-    pub fn get_group_strategy_position(env: Env, group_id: u64) -> Option<StrategyPosition> {
-        strategy::routing::get_position(&env, StrategyPositionKey::Group(group_id))
+    /// Sets the risk profile used to allocate a group's funds (group creator only).
+    pub fn set_group_risk_profile(
+        env: Env,
+        caller: Address,
+        group_id: u64,
+        profile: RiskProfile,
+    ) -> Result<(), SavingsError> {
+        caller.require_auth();
+        let position_key = StrategyPositionKey::Group(group_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
+        strategy::allocator::set_risk_profile(&env, position_key, profile);
+        Ok(())
     }
 
-    /// Withdraws funds from a lock's strategy position.
-    pub fn withdraw_lock_strategy(
+    /// Returns a lock's risk profile (conservative unless set).
+    pub fn get_lock_risk_profile(env: Env, lock_id: u64) -> RiskProfile {
+        strategy::allocator::get_risk_profile(&env, StrategyPositionKey::Lock(lock_id))
+    }
+
+    /// Returns a group's risk profile (conservative unless set).
+    pub fn get_group_risk_profile(env: Env, group_id: u64) -> RiskProfile {
+        strategy::allocator::get_risk_profile(&env, StrategyPositionKey::Group(group_id))
+    }
+
+    /// Previews how a deposit would be split across strategies for a risk profile.
+    pub fn preview_strategy_allocation(
+        env: Env,
+        profile: RiskProfile,
+        amount: i128,
+    ) -> Result<Vec<StrategyAllocation>, SavingsError> {
+        strategy::allocator::plan_allocation(&env, profile, amount)
+    }
+
+    /// Splits a LockSave deposit across strategies that fit the lock's risk profile.
+    pub fn allocate_lock_to_strategies(
+        env: Env,
+        caller: Address,
+        lock_id: u64,
+        amount: i128,
+    ) -> Result<Vec<StrategyAllocation>, SavingsError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::allocator::allocate(&env, &caller, position_key, amount);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Splits a GroupSave pooled deposit across strategies that fit the group's risk profile.
+    pub fn allocate_group_to_strategies(
+        env: Env,
+        caller: Address,
+        group_id: u64,
+        amount: i128,
+    ) -> Result<Vec<StrategyAllocation>, SavingsError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Group(group_id);
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::allocator::allocate(&env, &caller, position_key, amount);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Returns a lock plan's position in one strategy.
+    pub fn get_lock_strategy_position(
+        env: Env,
+        lock_id: u64,
+        strategy_address: Address,
+    ) -> Option<StrategyPosition> {
+        strategy::routing::get_position(&env, StrategyPositionKey::Lock(lock_id), strategy_address)
+    }
+
+    /// Returns a group plan's position in one strategy.
+    pub fn get_group_strategy_position(
+        env: Env,
+        group_id: u64,
+        strategy_address: Address,
+    ) -> Option<StrategyPosition> {
+        strategy::routing::get_position(
+            &env,
+            StrategyPositionKey::Group(group_id),
+            strategy_address,
+        )
+    }
+
+    /// Lists every strategy position held by a lock plan.
+    pub fn get_lock_strategy_positions(env: Env, lock_id: u64) -> Vec<StrategyPosition> {
+        strategy::routing::get_positions(&env, StrategyPositionKey::Lock(lock_id))
+    }
+
+    /// Lists every strategy position held by a group plan.
+    pub fn get_group_strategy_positions(env: Env, group_id: u64) -> Vec<StrategyPosition> {
+        strategy::routing::get_positions(&env, StrategyPositionKey::Group(group_id))
+    }
+
+    /// Returns the current value and claimable yield of a lock's strategy position.
+    pub fn get_lock_strategy_value(
+        env: Env,
+        lock_id: u64,
+        strategy_address: Address,
+    ) -> Result<Option<StrategyPositionValue>, SavingsError> {
+        strategy::routing::get_position_value(
+            &env,
+            StrategyPositionKey::Lock(lock_id),
+            strategy_address,
+        )
+    }
+
+    /// Returns the current value and claimable yield of a group's strategy position.
+    pub fn get_group_strategy_value(
+        env: Env,
+        group_id: u64,
+        strategy_address: Address,
+    ) -> Result<Option<StrategyPositionValue>, SavingsError> {
+        strategy::routing::get_position_value(
+            &env,
+            StrategyPositionKey::Group(group_id),
+            strategy_address,
+        )
+    }
+
+    /// Claims harvested yield across a lock's strategy positions (lock owner only).
+    pub fn claim_lock_strategy_yield(
         env: Env,
         caller: Address,
         lock_id: u64,
@@ -1222,32 +2291,135 @@ impl NesteraContract {
     ) -> Result<i128, SavingsError> {
         caller.require_auth();
         ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
         crate::security::acquire_reentrancy_guard(&env)?;
-        let res =
-            strategy::routing::withdraw_from_strategy(&env, StrategyPositionKey::Lock(lock_id), to);
+        let res = strategy::routing::claim_position_yield(&env, lock_id, to);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Adds harvested yield across a group's strategy positions to the group
+    /// pool (group creator only).
+    pub fn claim_group_strategy_yield(
+        env: Env,
+        caller: Address,
+        group_id: u64,
+    ) -> Result<i128, SavingsError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Group(group_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::routing::pool_group_yield(&env, group_id);
         crate::security::release_reentrancy_guard(&env);
         res
     }
 
-    /// Withdraws funds from a group's strategy position.
+    /// Withdraws a lock's whole position in one strategy back into the lock.
+    pub fn withdraw_lock_strategy(
+        env: Env,
+        caller: Address,
+        lock_id: u64,
+        strategy_address: Address,
+    ) -> Result<i128, SavingsError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::routing::withdraw_from_strategy(&env, position_key, strategy_address);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Withdraws a group's whole position in one strategy back into the pool.
     pub fn withdraw_group_strategy(
         env: Env,
         caller: Address,
         group_id: u64,
-        to: Address,
+        strategy_address: Address,
+    ) -> Result<i128, SavingsError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Group(group_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::routing::withdraw_from_strategy(&env, position_key, strategy_address);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Withdraws `amount` from a lock's position in one strategy back into
+    /// the lock.
+    pub fn withdraw_lock_strategy_amount(
+        env: Env,
+        caller: Address,
+        lock_id: u64,
+        strategy_address: Address,
+        amount: i128,
     ) -> Result<i128, SavingsError> {
         caller.require_auth();
         ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
         crate::security::acquire_reentrancy_guard(&env)?;
-        let res = strategy::routing::withdraw_from_strategy(
+        let res = strategy::routing::withdraw_amount_from_strategy(
             &env,
-            StrategyPositionKey::Group(group_id),
-            to,
+            position_key,
+            strategy_address,
+            amount,
+        );
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Withdraws `amount` from a group's position in one strategy back into
+    /// the pool.
+    pub fn withdraw_group_strategy_amount(
+        env: Env,
+        caller: Address,
+        group_id: u64,
+        strategy_address: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        let position_key = StrategyPositionKey::Group(group_id);
+        strategy::routing::require_position_owner(&env, &position_key, &caller)?;
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::routing::withdraw_amount_from_strategy(
+            &env,
+            position_key,
+            strategy_address,
+            amount,
         );
         crate::security::release_reentrancy_guard(&env);
         res
     }
 
+    /// Moves up to `batch_size` positions from one strategy to another
+    /// (admin/governance only); call again to resume until `completed`.
+    pub fn migrate_strategy(
+        env: Env,
+        caller: Address,
+        from: Address,
+        to: Address,
+        max_loss_bps: u32,
+        batch_size: u32,
+    ) -> Result<StrategyMigration, SavingsError> {
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res =
+            strategy::migration::migrate_strategy(&env, caller, from, to, max_loss_bps, batch_size);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Returns the latest migration out of a strategy, if any.
+    pub fn get_strategy_migration(env: Env, from: Address) -> Option<StrategyMigration> {
+        strategy::migration::get_migration(&env, &from)
+    }
+
     /// Harvests yield from a yield strategy.
     ///
     /// Calculates profit as `strategy_balance - principal`, calls `strategy_harvest`,
@@ -1267,14 +2439,40 @@ impl NesteraContract {
         res
     }
 
-    /// Returns the performance metrics for a give strategy.
-    pub fn get_strategy_performance(_env: &Env) -> StrategyPerformance {
-        StrategyPerformance {
-            total_deposited: 0,
-            total_withdrawn: 0,
-            total_harvested: 0,
-            apy_estimate_bps: 0,
-        }
+    /// Realizes any shortfall of a strategy's balance against its recorded
+    /// principal (admin / governance only), covering it from the treasury
+    /// reserve when enabled and writing the rest off against the strategy's
+    /// positions.
+    ///
+    /// Returns the loss realized.
+    pub fn realize_strategy_loss(
+        env: Env,
+        caller: Address,
+        strategy_address: Address,
+    ) -> Result<i128, SavingsError> {
+        crate::security::acquire_reentrancy_guard(&env)?;
+        let res = strategy::loss::realize_strategy_loss(&env, caller, strategy_address);
+        crate::security::release_reentrancy_guard(&env);
+        res
+    }
+
+    /// Sets whether realized strategy losses are covered from the treasury reserve.
+    pub fn set_loss_reserve_cover(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), SavingsError> {
+        strategy::loss::set_reserve_cover(&env, caller, enabled)
+    }
+
+    /// Returns whether realized strategy losses are covered from the treasury reserve.
+    pub fn get_loss_reserve_cover(env: Env) -> bool {
+        strategy::loss::get_reserve_cover(&env)
+    }
+
+    /// Returns the performance metrics, including realized losses, for a strategy.
+    pub fn get_strategy_performance(env: Env, strategy_address: Address) -> StrategyPerformance {
+        strategy::routing::get_strategy_performance(&env, strategy_address)
     }
 }
 
@@ -1287,6 +2485,10 @@ mod execution_tests;
 #[cfg(test)]
 mod governance_tests;
 #[cfg(test)]
+mod guardian_tests;
+#[cfg(test)]
+mod legacy_tests;
+#[cfg(test)]
 mod rates_test;
 #[cfg(test)]
 mod test;
@@ -1,20 +1,58 @@
+use crate::assets;
+use crate::custody::{self, Funding};
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
+use crate::governance;
+use crate::rates;
 use crate::rewards::storage;
-use crate::storage_types::{DataKey, LockSave, User};
+use crate::storage_types::{decode_versioned, DataKey, LegacyLockSave, LockSave, User};
+use crate::strategy::routing::{self, StrategyPositionKey};
+use crate::treasury;
 use crate::ttl;
 use crate::users;
-use soroban_sdk::{symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Val, Vec};
+
+/// Storage keys for Lock Save data beyond the plan itself
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LockKey {
+    /// Interest of a closed lock the treasury reserve could not cover yet
+    UnpaidInterest(u64),
+}
 
-/// Creates a new Lock Save plan for a user
+/// Creates a new Lock Save plan for a user in the base asset
 pub fn create_lock_save(
     env: &Env,
     user: Address,
     amount: i128,
     duration: u64,
+) -> Result<u64, SavingsError> {
+    create_asset_lock_save(env, user, assets::base_asset(env), amount, duration)
+}
+
+/// Creates a new Lock Save plan for a user in a whitelisted asset
+pub fn create_asset_lock_save(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    duration: u64,
+) -> Result<u64, SavingsError> {
+    open_lock_save(env, user, asset, amount, duration, Funding::Signed)
+}
+
+/// Opens a Lock Save with funds pulled as `funding` says.
+pub(crate) fn open_lock_save(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    duration: u64,
+    funding: Funding,
 ) -> Result<u64, SavingsError> {
     ensure_not_paused(env)?;
-    // Note: user.require_auth() is already called in lib.rs wrapper function
+    // Note: user.require_auth() is already called in lib.rs wrapper function;
+    // scheduled runs were authorized when the schedule was created
 
     // Validate inputs
     if amount <= 0 {
@@ -25,11 +63,16 @@ pub fn create_lock_save(
         return Err(SavingsError::InvalidTimestamp);
     }
 
+    assets::require_supported(env, &asset)?;
+    let interest_rate = rates::resolve_lock_rate(env, &asset, duration)?;
+
     // Ensure user exists using your users module
     if !users::user_exists(env, &user) {
         return Err(SavingsError::UserNotFound);
     }
 
+    custody::receive_funded(env, &asset, &user, amount, funding)?;
+
     // ID Logic
     let lock_id = get_next_lock_id(env);
     increment_next_lock_id(env);
@@ -39,14 +82,17 @@ pub fn create_lock_save(
         .checked_add(duration)
         .ok_or(SavingsError::Overflow)?;
 
-    let lock_save = LockSave {
+    let mut lock_save = LockSave {
         id: lock_id,
         owner: user.clone(),
+        asset,
         amount,
-        interest_rate: 500, // Matching your test expectation of 500 (5%)
+        interest_rate,
         start_time,
         maturity_time,
         is_withdrawn: false,
+        bonus_points: 0,
+        auto_renew: false,
     };
 
     // Store the LockSave
@@ -65,7 +111,15 @@ pub fn create_lock_save(
     env.storage().persistent().set(&user_key, &user_data);
 
     storage::award_deposit_points(env, user.clone(), amount)?;
-    storage::award_long_lock_bonus(env, user.clone(), amount, duration)?;
+    let bonus_points = storage::award_long_lock_bonus(env, user.clone(), amount, duration)?;
+    if bonus_points > 0 {
+        // Remember the bonus so an early break can claw it back
+        lock_save.bonus_points = bonus_points;
+        env.storage()
+            .persistent()
+            .set(&DataKey::LockSave(lock_id), &lock_save);
+    }
+    governance::record_lock_conviction(env, &lock_save)?;
 
     // Extend TTL for new lock save and user data
     ttl::extend_lock_ttl(env, lock_id);
@@ -93,12 +147,23 @@ pub fn withdraw_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128
         return Err(SavingsError::TooEarly);
     }
 
-    let final_amount = calculate_lock_save_yield(&lock_save, env.ledger().timestamp());
+    // Part of the principal may be in strategies
+    routing::require_no_positions(env, StrategyPositionKey::Lock(lock_id))?;
+
+    let interest = calculate_lock_save_yield(&lock_save, env.ledger().timestamp())
+        .checked_sub(lock_save.amount)
+        .ok_or(SavingsError::Underflow)?;
+    let final_amount = lock_save
+        .amount
+        .checked_add(fund_interest(env, &lock_save, interest)?)
+        .ok_or(SavingsError::Overflow)?;
 
     lock_save.is_withdrawn = true;
     env.storage()
         .persistent()
         .set(&DataKey::LockSave(lock_id), &lock_save);
+    governance::release_lock_conviction(env, &lock_save);
+    governance::record_withdrawal_power(env, &user, lock_save.amount)?;
 
     // Update user's total balance (subtracting the locked portion)
     let user_key = DataKey::User(user.clone());
@@ -111,12 +176,346 @@ pub fn withdraw_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128
     ttl::extend_lock_ttl(env, lock_id);
     ttl::extend_user_ttl(env, &user);
 
+    custody::send(env, &lock_save.asset, &user, final_amount)?;
+
     env.events()
         .publish((symbol_short!("withdraw"), user, lock_id), final_amount);
 
     Ok(final_amount)
 }
 
+/// Breaks a Lock Save before maturity.
+///
+/// The principal is returned minus a penalty that starts at
+/// `LockBreakPenaltyBps` when the lock opens and falls linearly to zero at
+/// maturity. No yield is paid. The penalty is credited to the fee recipient
+/// and recorded in the treasury, and any long-lock bonus points are revoked.
+///
+/// # Errors
+/// * `PlanNotFound` - If the lock does not exist
+/// * `Unauthorized` - If the caller does not own the lock
+/// * `PlanCompleted` - If the lock was already withdrawn
+/// * `TooLate` - If the lock has matured (use `withdraw_lock_save`)
+/// * `InvalidPlanConfig` - If the lock still holds strategy positions
+pub fn break_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    // Note: user.require_auth() is already called in lib.rs wrapper function
+
+    let mut lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if lock_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if lock_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    let now = env.ledger().timestamp();
+    if now >= lock_save.maturity_time {
+        return Err(SavingsError::TooLate);
+    }
+
+    // Part of the principal may be in strategies
+    routing::require_no_positions(env, StrategyPositionKey::Lock(lock_id))?;
+
+    let penalty = calculate_break_penalty(env, &lock_save, now)?;
+    let net_amount = lock_save
+        .amount
+        .checked_sub(penalty)
+        .ok_or(SavingsError::Underflow)?;
+
+    lock_save.is_withdrawn = true;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+    governance::release_lock_conviction(env, &lock_save);
+    governance::record_withdrawal_power(env, &user, lock_save.amount)?;
+
+    let user_key = DataKey::User(user.clone());
+    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
+        user_data.total_balance = user_data.total_balance.saturating_sub(lock_save.amount);
+        env.storage().persistent().set(&user_key, &user_data);
+    }
+
+    if penalty > 0 {
+        if let Some(fee_recipient) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::FeeRecipient)
+        {
+            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
+            let current_fee_balance = env
+                .storage()
+                .persistent()
+                .get::<DataKey, i128>(&fee_key)
+                .unwrap_or(0i128);
+            let new_fee_balance = current_fee_balance
+                .checked_add(penalty)
+                .ok_or(SavingsError::Overflow)?;
+            env.storage().persistent().set(&fee_key, &new_fee_balance);
+            ttl::extend_config_ttl(env, &fee_key);
+
+            env.events()
+                .publish((symbol_short!("lbrk_fee"), fee_recipient, lock_id), penalty);
+        }
+        crate::treasury::record_fee(
+            env,
+            &lock_save.asset,
+            penalty,
+            Symbol::new(env, "lock_break"),
+        );
+    }
+
+    storage::revoke_long_lock_bonus(env, user.clone(), lock_save.bonus_points);
+
+    // Extend TTL (broken locks get shorter extension)
+    ttl::extend_lock_ttl(env, lock_id);
+    ttl::extend_user_ttl(env, &user);
+
+    env.events().publish(
+        (symbol_short!("lock_brk"), user.clone(), lock_id),
+        net_amount,
+    );
+
+    custody::send(env, &lock_save.asset, &user, net_amount)?;
+
+    Ok(net_amount)
+}
+
+/// Returns the penalty for breaking `lock_save` at `now`.
+///
+/// penalty = amount * max_bps * remaining / (10_000 * total_duration)
+pub fn calculate_break_penalty(
+    env: &Env,
+    lock_save: &LockSave,
+    now: u64,
+) -> Result<i128, SavingsError> {
+    let max_bps: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LockBreakPenaltyBps)
+        .unwrap_or(0);
+
+    let total = lock_save.maturity_time.saturating_sub(lock_save.start_time);
+    let remaining = lock_save.maturity_time.saturating_sub(now);
+    if max_bps == 0 || total == 0 || remaining == 0 {
+        return Ok(0);
+    }
+
+    let numerator = lock_save
+        .amount
+        .checked_mul(max_bps as i128)
+        .ok_or(SavingsError::Overflow)?
+        .checked_mul(remaining as i128)
+        .ok_or(SavingsError::Overflow)?;
+    Ok(numerator / (10_000i128 * total as i128))
+}
+
+/// Opts a Lock Save into (or out of) auto-renewal at maturity.
+///
+/// # Errors
+/// * `PlanNotFound` - If the lock does not exist
+/// * `Unauthorized` - If the caller does not own the lock
+/// * `PlanCompleted` - If the lock was already withdrawn
+pub fn set_lock_auto_renew(
+    env: &Env,
+    user: Address,
+    lock_id: u64,
+    enabled: bool,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
+    // Note: user.require_auth() is already called in lib.rs wrapper function
+
+    let mut lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if lock_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if lock_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    lock_save.auto_renew = enabled;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+    ttl::extend_lock_ttl(env, lock_id);
+
+    env.events()
+        .publish((symbol_short!("lock_rnw"), user, lock_id), enabled);
+
+    Ok(())
+}
+
+/// Rolls a matured auto-renew Lock Save into a new lock.
+///
+/// Callable by anyone (keepers). Principal plus yield up to maturity becomes
+/// the principal of a new lock with the same duration, at the lock rate in
+/// force now. Yield is drawn from the treasury reserve; what it cannot cover
+/// stays claimable on the old lock. The new lock keeps auto-renew enabled.
+/// No tokens move.
+///
+/// # Errors
+/// * `PlanNotFound` - If the lock does not exist
+/// * `PlanCompleted` - If the lock was already withdrawn or rolled over
+/// * `InvalidPlanConfig` - If the lock has not opted into auto-renew, the
+///   duration no longer matches a rate tier, or funds are still in strategies
+/// * `TooEarly` - If the lock has not matured
+pub fn rollover_lock_save(env: &Env, lock_id: u64) -> Result<u64, SavingsError> {
+    ensure_not_paused(env)?;
+
+    let mut old_lock = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if old_lock.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    if !old_lock.auto_renew {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+
+    let now = env.ledger().timestamp();
+    if now < old_lock.maturity_time {
+        return Err(SavingsError::TooEarly);
+    }
+
+    assets::require_supported(env, &old_lock.asset)?;
+
+    // Positions are keyed by lock ID, so they would be orphaned on the old lock
+    routing::require_no_positions(env, StrategyPositionKey::Lock(lock_id))?;
+
+    let duration = old_lock.maturity_time - old_lock.start_time;
+    let interest_rate = rates::resolve_lock_rate(env, &old_lock.asset, duration)?;
+    // Yield stops at maturity so a late keeper call doesn't change the payout.
+    // Only interest the reserve funds is compounded; the rest stays claimable
+    // on the old lock.
+    let interest = calculate_lock_save_yield(&old_lock, old_lock.maturity_time)
+        .checked_sub(old_lock.amount)
+        .ok_or(SavingsError::Underflow)?;
+    let rolled_amount = old_lock
+        .amount
+        .checked_add(fund_interest(env, &old_lock, interest)?)
+        .ok_or(SavingsError::Overflow)?;
+
+    old_lock.is_withdrawn = true;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &old_lock);
+    governance::release_lock_conviction(env, &old_lock);
+
+    let new_id = get_next_lock_id(env);
+    increment_next_lock_id(env);
+
+    let owner = old_lock.owner.clone();
+    let new_lock = LockSave {
+        id: new_id,
+        owner: owner.clone(),
+        asset: old_lock.asset.clone(),
+        amount: rolled_amount,
+        interest_rate,
+        start_time: now,
+        maturity_time: now.checked_add(duration).ok_or(SavingsError::Overflow)?,
+        is_withdrawn: false,
+        bonus_points: 0,
+        auto_renew: true,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(new_id), &new_lock);
+    add_lock_to_user(env, &owner, new_id);
+    governance::record_lock_conviction(env, &new_lock)?;
+
+    // The yield becomes principal of the new lock
+    let user_key = DataKey::User(owner.clone());
+    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
+        user_data.total_balance = user_data
+            .total_balance
+            .checked_add(rolled_amount - old_lock.amount)
+            .ok_or(SavingsError::Overflow)?;
+        user_data.savings_count += 1;
+        env.storage().persistent().set(&user_key, &user_data);
+    }
+
+    ttl::extend_lock_ttl(env, lock_id);
+    ttl::extend_lock_ttl(env, new_id);
+    ttl::extend_user_ttl(env, &owner);
+    ttl::extend_user_plan_list_ttl(env, &DataKey::UserLockSaves(owner.clone()));
+
+    env.events().publish(
+        (symbol_short!("lock_roll"), owner, lock_id),
+        (new_id, rolled_amount),
+    );
+
+    Ok(new_id)
+}
+
+/// Draws a lock's interest from the treasury reserve. Whatever the reserve
+/// cannot cover is held as unpaid interest of the lock, claimable later via
+/// `claim_lock_interest`.
+///
+/// # Returns
+/// The interest funded now.
+fn fund_interest(env: &Env, lock_save: &LockSave, interest: i128) -> Result<i128, SavingsError> {
+    if interest <= 0 {
+        return Ok(0);
+    }
+    let funded = treasury::draw_reserve_in(env, &lock_save.asset, interest);
+    treasury::record_yield(env, &lock_save.asset, funded);
+    if funded < interest {
+        let key = LockKey::UnpaidInterest(lock_save.id);
+        let unpaid = get_unpaid_interest(env, lock_save.id)
+            .checked_add(interest - funded)
+            .ok_or(SavingsError::Overflow)?;
+        env.storage().persistent().set(&key, &unpaid);
+    }
+    Ok(funded)
+}
+
+/// Returns the interest of a closed lock still waiting on the reserve.
+pub fn get_unpaid_interest(env: &Env, lock_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&LockKey::UnpaidInterest(lock_id))
+        .unwrap_or(0)
+}
+
+/// Pays out a closed lock's unpaid interest as far as the treasury reserve
+/// covers it. The rest stays claimable.
+///
+/// # Errors
+/// * `PlanNotFound` - If the lock does not exist
+/// * `Unauthorized` - If the caller does not own the lock
+pub fn claim_lock_interest(env: &Env, user: Address, lock_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+
+    let lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+    if lock_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    let unpaid = get_unpaid_interest(env, lock_id);
+    let amount = treasury::draw_reserve_in(env, &lock_save.asset, unpaid);
+    if amount == 0 {
+        return Ok(0);
+    }
+    let key = LockKey::UnpaidInterest(lock_id);
+    if amount == unpaid {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &(unpaid - amount));
+    }
+    treasury::record_yield(env, &lock_save.asset, amount);
+
+    env.events()
+        .publish((symbol_short!("lock_int"), user.clone(), lock_id), amount);
+
+    custody::send(env, &lock_save.asset, &user, amount)?;
+
+    Ok(amount)
+}
+
 pub fn check_matured_lock(env: &Env, lock_id: u64) -> bool {
     if let Some(lock_save) = get_lock_save(env, lock_id) {
         // Extend TTL on check
@@ -127,13 +526,27 @@ pub fn check_matured_lock(env: &Env, lock_id: u64) -> bool {
     }
 }
 
+/// Reads a Lock Save, upgrading one stored by earlier versions (see
+/// `LegacyLockSave`) to a base-asset lock without bonus or auto-renewal.
 pub fn get_lock_save(env: &Env, lock_id: u64) -> Option<LockSave> {
-    let lock_save = env.storage().persistent().get(&DataKey::LockSave(lock_id));
-    if lock_save.is_some() {
-        // Extend TTL on read
-        ttl::extend_lock_ttl(env, lock_id);
-    }
-    lock_save
+    let raw: Map<Symbol, Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::LockSave(lock_id))?;
+    // Extend TTL on read
+    ttl::extend_lock_ttl(env, lock_id);
+    decode_versioned(env, raw, "asset", |legacy: LegacyLockSave| LockSave {
+        id: legacy.id,
+        owner: legacy.owner,
+        asset: assets::base_asset(env),
+        amount: legacy.amount,
+        interest_rate: legacy.interest_rate,
+        start_time: legacy.start_time,
+        maturity_time: legacy.maturity_time,
+        is_withdrawn: legacy.is_withdrawn,
+        bonus_points: 0,
+        auto_renew: false,
+    })
 }
 
 pub fn get_user_lock_saves(env: &Env, user: &Address) -> Vec<u64> {
@@ -212,6 +625,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize(&admin, &admin_pk);
+        client.set_lock_rate(&admin, &0, &500);
 
         let config = RewardsConfig {
             points_per_token: 10,
@@ -364,4 +778,151 @@ mod tests {
         // base points = 1000 * 10 = 10000, bonus = 2000
         assert_eq!(rewards.total_points, 12_000);
     }
+
+    #[test]
+    fn test_break_lock_penalty_slides_to_zero() {
+        let (env, client, _) = setup_env_with_rewards_enabled(false);
+        let user = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        client.set_fee_recipient(&treasury);
+        client.set_lock_break_penalty_bps(&1_000); // 10% at lock start
+
+        let duration = 1_000u64;
+        let lock_id = client.create_lock_save(&user, &10_000, &duration);
+
+        // A quarter of the way in: 75% of the max penalty remains
+        env.ledger().with_mut(|li| li.timestamp += 250);
+        let paid = client.break_lock_save(&user, &lock_id);
+
+        assert_eq!(paid, 9_250);
+        assert_eq!(client.get_protocol_fee_balance(&treasury), 750);
+        assert_eq!(client.get_treasury().total_fees_collected, 750);
+        assert!(client.get_lock_save_detail(&lock_id).is_withdrawn);
+        assert_eq!(client.get_user(&user).total_balance, 0);
+    }
+
+    #[test]
+    fn test_break_lock_after_maturity_is_rejected() {
+        let (env, client, _) = setup_env_with_rewards_enabled(false);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        client.set_lock_break_penalty_bps(&1_000);
+
+        let lock_id = client.create_lock_save(&user, &10_000, &1_000);
+        env.ledger().with_mut(|li| li.timestamp += 1_000);
+
+        assert!(client.try_break_lock_save(&user, &lock_id).is_err());
+        assert!(client
+            .try_break_lock_save(&Address::generate(&env), &lock_id)
+            .is_err());
+    }
+
+    #[test]
+    fn test_break_lock_revokes_long_lock_bonus() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let duration = LONG_LOCK_BONUS_THRESHOLD_SECS + 1;
+        let lock_id = client.create_lock_save(&user, &1_000, &duration);
+        assert_eq!(client.get_user_rewards(&user).total_points, 12_000);
+        assert_eq!(client.get_lock_save_detail(&lock_id).bonus_points, 2_000);
+
+        client.break_lock_save(&user, &lock_id);
+
+        // Deposit points stay, the long-lock bonus is clawed back
+        assert_eq!(client.get_user_rewards(&user).total_points, 10_000);
+    }
+
+    #[test]
+    fn test_rollover_folds_yield_into_new_lock() {
+        let (env, client, _) = setup_env_with_rewards_enabled(false);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let duration = 365 * 24 * 3600u64;
+        let lock_id = client.create_lock_save(&user, &10_000, &duration);
+        client.set_lock_auto_renew(&user, &lock_id, &true);
+        client.fund_reserve(&user, &100);
+
+        // Keeper arrives late; yield still stops at maturity
+        env.ledger()
+            .with_mut(|li| li.timestamp += duration + 86_400);
+        let new_id = client.rollover_lock_save(&lock_id);
+
+        let old_lock = client.get_lock_save_detail(&lock_id);
+        let new_lock = client.get_lock_save_detail(&new_id);
+        assert!(old_lock.is_withdrawn);
+        assert!(new_lock.auto_renew);
+        assert!(!new_lock.is_withdrawn);
+        // Only the yield the reserve funds is compounded
+        assert_eq!(new_lock.amount, 10_100);
+        assert_eq!(client.get_reserve_balance(), 0);
+        assert!(client.get_unpaid_lock_interest(&lock_id) > 0);
+        assert_eq!(client.get_unpaid_lock_interest(&new_id), 0);
+        assert_eq!(
+            new_lock.maturity_time - new_lock.start_time,
+            old_lock.maturity_time - old_lock.start_time
+        );
+        assert_eq!(client.get_user(&user).total_balance, new_lock.amount);
+        assert_eq!(client.get_user_lock_saves(&user).len(), 2);
+    }
+
+    #[test]
+    fn test_rollover_uses_current_lock_rate() {
+        let (env, client, admin) = setup_env_with_rewards_enabled(false);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        client.set_lock_rate(&admin, &30, &600);
+
+        let duration = 30 * 86_400u64;
+        let lock_id = client.create_lock_save(&user, &1_000, &duration);
+        client.set_lock_auto_renew(&user, &lock_id, &true);
+        client.set_lock_rate(&admin, &30, &800);
+
+        env.ledger().with_mut(|li| li.timestamp += duration);
+        let new_id = client.rollover_lock_save(&lock_id);
+
+        assert_eq!(client.get_lock_save_detail(&lock_id).interest_rate, 600);
+        assert_eq!(client.get_lock_save_detail(&new_id).interest_rate, 800);
+    }
+
+    #[test]
+    fn test_rollover_requires_opt_in_and_maturity() {
+        let (env, client, _) = setup_env_with_rewards_enabled(false);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+
+        let lock_id = client.create_lock_save(&user, &1_000, &1_000);
+        assert_eq!(
+            client.try_rollover_lock_save(&lock_id),
+            Err(Ok(crate::SavingsError::InvalidPlanConfig))
+        );
+
+        client.set_lock_auto_renew(&user, &lock_id, &true);
+        assert_eq!(
+            client.try_rollover_lock_save(&lock_id),
+            Err(Ok(crate::SavingsError::TooEarly))
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 1_000);
+        client.rollover_lock_save(&lock_id);
+        assert_eq!(
+            client.try_rollover_lock_save(&lock_id),
+            Err(Ok(crate::SavingsError::PlanCompleted))
+        );
+    }
 }
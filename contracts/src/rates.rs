@@ -1,91 +1,224 @@
+use crate::assets;
+use crate::flexi;
 use crate::governance;
 use crate::storage_types::DataKey;
 use crate::SavingsError;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
 
 // --- Admin Setters (with governance transition) ---
+//
+// The un-suffixed setters and getters operate on the base asset; the `_for`
+// variants take the asset explicitly.
 
 pub fn set_flexi_rate(env: &Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+    set_flexi_rate_for(env, caller, assets::base_asset(env), rate)
+}
+
+pub fn set_goal_rate(env: &Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+    set_goal_rate_for(env, caller, assets::base_asset(env), rate)
+}
+
+pub fn set_group_rate(env: &Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+    set_group_rate_for(env, caller, assets::base_asset(env), rate)
+}
+
+pub fn set_lock_rate(
+    env: &Env,
+    caller: Address,
+    duration_days: u64,
+    rate: i128,
+) -> Result<(), SavingsError> {
+    set_lock_rate_for(env, caller, assets::base_asset(env), duration_days, rate)
+}
+
+pub fn set_flexi_rate_for(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    rate: i128,
+) -> Result<(), SavingsError> {
     caller.require_auth();
     governance::validate_admin_or_governance(env, &caller)?;
+    write_flexi_rate(env, &asset, rate)
+}
 
-    if rate < 0 {
-        return Err(SavingsError::InvalidInterestRate);
-    }
-    env.storage().instance().set(&DataKey::FlexiRate, &rate);
-    Ok(())
+pub fn set_goal_rate_for(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    rate: i128,
+) -> Result<(), SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+    write_rate(env, &DataKey::GoalRate(asset), rate)
 }
 
-pub fn set_goal_rate(env: &Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
+pub fn set_group_rate_for(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    rate: i128,
+) -> Result<(), SavingsError> {
     caller.require_auth();
     governance::validate_admin_or_governance(env, &caller)?;
+    write_rate(env, &DataKey::GroupRate(asset), rate)
+}
 
+pub fn set_lock_rate_for(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    duration_days: u64,
+    rate: i128,
+) -> Result<(), SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+    write_lock_rate(env, &asset, duration_days, rate)
+}
+
+/// Stores a rate under `key` after validating it. Authorization is the caller's job.
+pub(crate) fn write_rate(env: &Env, key: &DataKey, rate: i128) -> Result<(), SavingsError> {
     if rate < 0 {
         return Err(SavingsError::InvalidInterestRate);
     }
-    env.storage().instance().set(&DataKey::GoalRate, &rate);
+    env.storage().instance().set(key, &rate);
     Ok(())
 }
 
-pub fn set_group_rate(env: &Env, caller: Address, rate: i128) -> Result<(), SavingsError> {
-    caller.require_auth();
-    governance::validate_admin_or_governance(env, &caller)?;
-
+/// Stores a new Flexi rate for `asset`.
+///
+/// The interest index is checkpointed at the old rate first, so the change
+/// only affects interest from now on.
+pub(crate) fn write_flexi_rate(env: &Env, asset: &Address, rate: i128) -> Result<(), SavingsError> {
     if rate < 0 {
         return Err(SavingsError::InvalidInterestRate);
     }
-    env.storage().instance().set(&DataKey::GroupRate, &rate);
-    Ok(())
+    flexi::update_index(env, asset);
+    write_rate(env, &DataKey::FlexiRate(asset.clone()), rate)
 }
 
-pub fn set_lock_rate(
+/// Stores a lock rate tier for `asset` and records the duration in the tier list.
+pub(crate) fn write_lock_rate(
     env: &Env,
-    caller: Address,
+    asset: &Address,
     duration_days: u64,
     rate: i128,
 ) -> Result<(), SavingsError> {
-    caller.require_auth();
-    governance::validate_admin_or_governance(env, &caller)?;
-
-    if rate < 0 {
+    if rate > u32::MAX as i128 {
         return Err(SavingsError::InvalidInterestRate);
     }
-    env.storage()
-        .instance()
-        .set(&DataKey::LockRate(duration_days), &rate);
+    write_rate(env, &DataKey::LockRate(asset.clone(), duration_days), rate)?;
+
+    let tiers = get_lock_rate_tiers(env, asset);
+    if !tiers.contains(duration_days) {
+        // Keep the list sorted ascending so lookups can stop at the first larger tier
+        let mut sorted = Vec::new(env);
+        let mut inserted = false;
+        for days in tiers.iter() {
+            if !inserted && duration_days < days {
+                sorted.push_back(duration_days);
+                inserted = true;
+            }
+            sorted.push_back(days);
+        }
+        if !inserted {
+            sorted.push_back(duration_days);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::LockRateTiers(asset.clone()), &sorted);
+    }
     Ok(())
 }
 
 // --- Getters ---
 
 pub fn get_flexi_rate(env: &Env) -> i128 {
+    get_flexi_rate_for(env, &assets::base_asset(env))
+}
+
+pub fn get_goal_rate(env: &Env) -> i128 {
+    get_goal_rate_for(env, &assets::base_asset(env))
+}
+
+pub fn get_group_rate(env: &Env) -> i128 {
+    get_group_rate_for(env, &assets::base_asset(env))
+}
+
+pub fn get_lock_rate(env: &Env, duration_days: u64) -> Result<i128, SavingsError> {
+    get_lock_rate_for(env, &assets::base_asset(env), duration_days)
+}
+
+pub fn get_flexi_rate_for(env: &Env, asset: &Address) -> i128 {
     env.storage()
         .instance()
-        .get(&DataKey::FlexiRate)
+        .get(&DataKey::FlexiRate(asset.clone()))
         .unwrap_or(0)
 }
 
-pub fn get_goal_rate(env: &Env) -> i128 {
+pub fn get_goal_rate_for(env: &Env, asset: &Address) -> i128 {
     env.storage()
         .instance()
-        .get(&DataKey::GoalRate)
+        .get(&DataKey::GoalRate(asset.clone()))
         .unwrap_or(0)
 }
 
-pub fn get_group_rate(env: &Env) -> i128 {
+pub fn get_group_rate_for(env: &Env, asset: &Address) -> i128 {
     env.storage()
         .instance()
-        .get(&DataKey::GroupRate)
+        .get(&DataKey::GroupRate(asset.clone()))
         .unwrap_or(0)
 }
 
-pub fn get_lock_rate(env: &Env, duration_days: u64) -> Result<i128, SavingsError> {
+pub fn get_lock_rate_for(
+    env: &Env,
+    asset: &Address,
+    duration_days: u64,
+) -> Result<i128, SavingsError> {
     env.storage()
         .instance()
-        .get(&DataKey::LockRate(duration_days))
+        .get(&DataKey::LockRate(asset.clone(), duration_days))
         .ok_or(SavingsError::PlanNotFound)
 }
 
+/// Returns the durations (days) with a configured lock rate for `asset`, ascending.
+pub fn get_lock_rate_tiers(env: &Env, asset: &Address) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&DataKey::LockRateTiers(asset.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Resolves the rate a new lock of `duration_seconds` earns in `asset`.
+///
+/// Uses a floor-tier policy: the lock gets the rate of the longest configured
+/// tier that does not exceed its duration.
+///
+/// # Errors
+/// * `InvalidPlanConfig` - If the asset has no tiers configured, or the
+///   duration is shorter than the shortest tier
+pub fn resolve_lock_rate(
+    env: &Env,
+    asset: &Address,
+    duration_seconds: u64,
+) -> Result<u32, SavingsError> {
+    let tiers = get_lock_rate_tiers(env, asset);
+    let duration_days = duration_seconds / SECONDS_PER_DAY;
+    let mut matched: Option<u64> = None;
+    for days in tiers.iter() {
+        if days > duration_days {
+            break;
+        }
+        matched = Some(days);
+    }
+
+    let days = matched.ok_or(SavingsError::InvalidPlanConfig)?;
+    let rate = get_lock_rate_for(env, asset, days)?;
+    u32::try_from(rate).map_err(|_| SavingsError::InvalidInterestRate)
+}
+
 // --- Interest Calculation Helpers ---
 
 pub fn calculate_flexi_interest(balance: i128, rate: i128, duration_seconds: u64) -> i128 {
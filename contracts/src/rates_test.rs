@@ -115,3 +115,83 @@ fn test_invalid_rates() {
     let res = client.try_set_flexi_rate(&admin, &-100);
     assert_eq!(res.unwrap_err(), Ok(SavingsError::InvalidInterestRate));
 }
+
+#[test]
+fn test_lock_tiers_are_kept_sorted() {
+    let (env, client, admin) = setup();
+
+    client.set_lock_rate(&admin, &90, &900);
+    client.set_lock_rate(&admin, &30, &600);
+    client.set_lock_rate(&admin, &180, &1_200);
+    client.set_lock_rate(&admin, &30, &650); // update, not a new tier
+
+    let asset = client.get_supported_assets().get(0).unwrap();
+    let tiers = client.get_lock_rate_tiers(&asset);
+    assert_eq!(tiers, soroban_sdk::vec![&env, 30u64, 90, 180]);
+}
+
+#[test]
+fn test_lock_uses_floor_tier_rate() {
+    let (env, client, admin) = setup();
+    let user = Address::generate(&env);
+    client.initialize_user(&user);
+
+    client.set_lock_rate(&admin, &30, &600);
+    client.set_lock_rate(&admin, &90, &900);
+
+    let day = 86_400u64;
+    let exact = client.create_lock_save(&user, &1_000, &(30 * day));
+    let between = client.create_lock_save(&user, &1_000, &(89 * day));
+    let above = client.create_lock_save(&user, &1_000, &(365 * day));
+
+    assert_eq!(client.get_lock_save_detail(&exact).interest_rate, 600);
+    assert_eq!(client.get_lock_save_detail(&between).interest_rate, 600);
+    assert_eq!(client.get_lock_save_detail(&above).interest_rate, 900);
+}
+
+#[test]
+fn test_lock_shorter_than_any_tier_is_rejected() {
+    let (env, client, admin) = setup();
+    let user = Address::generate(&env);
+    client.initialize_user(&user);
+
+    client.set_lock_rate(&admin, &30, &600);
+
+    let res = client.try_create_lock_save(&user, &1_000, &(29 * 86_400));
+    assert_eq!(
+        res.unwrap_err(),
+        Ok(soroban_sdk::Error::from_contract_error(
+            SavingsError::InvalidPlanConfig as u32
+        ))
+    );
+    assert!(client.get_user_lock_saves(&user).is_empty());
+}
+
+#[test]
+fn test_lock_rate_is_fixed_at_creation() {
+    let (env, client, admin) = setup();
+    let user = Address::generate(&env);
+    client.initialize_user(&user);
+
+    client.set_lock_rate(&admin, &30, &600);
+    let lock_id = client.create_lock_save(&user, &1_000, &(30 * 86_400));
+
+    client.set_lock_rate(&admin, &30, &100);
+    assert_eq!(client.get_lock_save_detail(&lock_id).interest_rate, 600);
+}
+
+#[test]
+fn test_lock_without_tiers_is_rejected() {
+    let (env, client, _admin) = setup();
+    let user = Address::generate(&env);
+    client.initialize_user(&user);
+
+    let res = client.try_create_lock_save(&user, &1_000, &100);
+    assert_eq!(
+        res.unwrap_err(),
+        Ok(soroban_sdk::Error::from_contract_error(
+            SavingsError::InvalidPlanConfig as u32
+        ))
+    );
+    assert!(client.get_user_lock_saves(&user).is_empty());
+}
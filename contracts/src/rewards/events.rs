@@ -16,6 +16,14 @@ pub struct BonusAwarded {
     pub bonus_type: Symbol, // e.g., "streak", "lock", "goal"
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BonusRevoked {
+    pub user: Address,
+    pub amount: u128,
+    pub bonus_type: Symbol,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PointsRedeemed {
@@ -55,6 +63,19 @@ pub fn emit_bonus_awarded(env: &Env, user: Address, amount: u128, bonus_type: Sy
     );
 }
 
+/// Emits a BonusRevoked event.
+pub fn emit_bonus_revoked(env: &Env, user: Address, amount: u128, bonus_type: Symbol) {
+    let event = BonusRevoked {
+        user: user.clone(),
+        amount,
+        bonus_type,
+    };
+    env.events().publish(
+        (symbol_short!("rewards"), symbol_short!("revoked"), user),
+        event,
+    );
+}
+
 /// Emits a PointsRedeemed event.
 pub fn emit_points_redeemed(env: &Env, user: Address, amount: u128) {
     let event = PointsRedeemed {
@@ -1,7 +1,9 @@
 use super::storage_types::{RewardsDataKey, UserRewards};
 use crate::errors::SavingsError;
 use crate::rewards::config::get_rewards_config;
-use crate::rewards::events::{emit_bonus_awarded, emit_points_awarded, emit_streak_updated};
+use crate::rewards::events::{
+    emit_bonus_awarded, emit_bonus_revoked, emit_points_awarded, emit_streak_updated,
+};
 use soroban_sdk::{Address, Env, Symbol};
 
 /// Duration threshold for long-lock bonus eligibility (in seconds).
@@ -207,6 +209,7 @@ pub fn award_deposit_points(env: &Env, user: Address, amount: i128) -> Result<()
         .lifetime_deposited
         .checked_add(amount)
         .ok_or(SavingsError::Overflow)?;
+    crate::governance::record_deposit_power(env, &user, amount)?;
 
     // 6. Save and Emit Event
     save_user_rewards(env, user.clone(), &user_rewards);
@@ -261,6 +264,37 @@ pub fn award_long_lock_bonus(
     Ok(bonus_points)
 }
 
+/// Claws back a long-lock bonus when the lock is broken before maturity.
+///
+/// Points the user has already spent cannot be recovered, so the deduction
+/// is capped at their current balance. Returns the points actually removed.
+pub fn revoke_long_lock_bonus(env: &Env, user: Address, points: u128) -> u128 {
+    revoke_bonus(env, user, points, Symbol::new(env, "lock"))
+}
+
+/// Claws back goal milestone points when the goal is given up before
+/// completion. Capped at the user's balance like `revoke_long_lock_bonus`.
+pub fn revoke_goal_milestone_bonus(env: &Env, user: Address, points: u128) -> u128 {
+    revoke_bonus(env, user, points, Symbol::new(env, "milestone"))
+}
+
+fn revoke_bonus(env: &Env, user: Address, points: u128, bonus_type: Symbol) -> u128 {
+    if points == 0 {
+        return 0;
+    }
+
+    let mut rewards = get_user_rewards(env, user.clone());
+    let revoked = points.min(rewards.total_points);
+    if revoked == 0 {
+        return 0;
+    }
+    rewards.total_points -= revoked;
+    save_user_rewards(env, user.clone(), &rewards);
+
+    emit_bonus_revoked(env, user, revoked, bonus_type);
+    revoked
+}
+
 /// Awards a fixed goal completion bonus when a goal reaches its target.
 pub fn award_goal_completion_bonus(env: &Env, user: Address) -> Result<u128, SavingsError> {
     let config = match get_rewards_config(env) {
@@ -278,6 +312,24 @@ pub fn award_goal_completion_bonus(env: &Env, user: Address) -> Result<u128, Sav
     Ok(bonus_points)
 }
 
+/// Awards a quarter of the goal completion bonus when a goal crosses a
+/// progress milestone.
+pub fn award_goal_milestone_bonus(env: &Env, user: Address) -> Result<u128, SavingsError> {
+    let config = match get_rewards_config(env) {
+        Ok(config) if config.enabled => config,
+        _ => return Ok(0),
+    };
+
+    let bonus_points = config.goal_completion_bonus as u128 / 4;
+    if bonus_points == 0 {
+        return Ok(0);
+    }
+
+    add_points(env, user.clone(), bonus_points)?;
+    emit_bonus_awarded(env, user, bonus_points, Symbol::new(env, "milestone"));
+    Ok(bonus_points)
+}
+
 #[cfg(test)]
 mod tests {
     use super::STREAK_WINDOW_SECS;
@@ -0,0 +1,471 @@
+//! Rotating savings (ROSCA / ajo) mode for Group Save.
+//!
+//! A rotating group collects a fixed `contribution_amount` from every member
+//! each cycle and pays the whole pot to one member per round, following a
+//! payout order fixed when the rotation starts. Members who miss a round
+//! accrue arrears; arrears are settled either by the member paying them or
+//! by withholding them from that member's own payout. Settled arrears go to
+//! the recipient of the round that was short.
+
+use crate::custody::{self, Funding};
+use crate::ensure_not_paused;
+use crate::errors::SavingsError;
+use crate::group::{self, CONTRIBUTION_FIXED};
+use crate::storage_types::{DataKey, GroupSave};
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+/// Storage keys for rotating groups
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RoscaKey {
+    /// Maps group ID to its Rotation state
+    Rotation(u64),
+    /// Maps group ID to the order in which members receive the pot
+    PayoutOrder(u64),
+    /// Maps (group_id, round) to the members who have paid that round
+    RoundPaid(u64, u32),
+    /// Maps (group_id, member) to the rounds the member missed and still owes
+    Arrears(u64, Address),
+    /// Maps group ID to when its current round opened
+    RoundOpened(u64),
+}
+
+/// Rotation schedule and progress for a group
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rotation {
+    pub group_id: u64,
+    /// Length of each round in seconds
+    pub cycle_duration: u64,
+    /// Timestamp when round 0 opened
+    pub start_time: u64,
+    /// Index of the round currently collecting contributions
+    pub current_round: u32,
+    /// One round per member
+    pub total_rounds: u32,
+    /// Amount each member pays per round
+    pub contribution_amount: i128,
+    pub is_randomized: bool,
+    pub is_finished: bool,
+}
+
+/// Snapshot of the round currently collecting contributions
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundInfo {
+    pub round: u32,
+    pub recipient: Address,
+    /// Contributions are accepted until this timestamp
+    pub deadline: u64,
+    pub pot: i128,
+    pub paid_count: u32,
+    pub member_count: u32,
+}
+
+/// Switches a group into rotating mode and fixes the payout order.
+///
+/// The member list is frozen at this point: one round is scheduled per
+/// member, each open for `cycle_duration` seconds from when the previous
+/// round closed. Only fixed-contribution groups can rotate. With `randomize` the payout
+/// order is shuffled using the ledger PRNG; otherwise members are paid in
+/// joining order.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group already rotates, is not a
+///   fixed-contribution group, already holds pooled contributions, has fewer
+///   than two members, or `cycle_duration` is 0
+pub fn start_rotation(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    cycle_duration: u64,
+    randomize: bool,
+) -> Result<Rotation, SavingsError> {
+    ensure_not_paused(env)?;
+    creator.require_auth();
+
+    let group = get_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if is_rotating(env, group_id)
+        || group.contribution_type != CONTRIBUTION_FIXED
+        || group.is_completed
+        || group.current_amount != 0
+        || cycle_duration == 0
+    {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let mut order: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::GroupMembers(group_id))
+        .unwrap_or(Vec::new(env));
+    if order.len() < 2 {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    if randomize {
+        env.prng().shuffle(&mut order);
+    }
+
+    let rotation = Rotation {
+        group_id,
+        cycle_duration,
+        start_time: env.ledger().timestamp(),
+        current_round: 0,
+        total_rounds: order.len(),
+        contribution_amount: group.contribution_amount,
+        is_randomized: randomize,
+        is_finished: false,
+    };
+
+    env.storage()
+        .persistent()
+        .set(&RoscaKey::PayoutOrder(group_id), &order);
+    save_rotation(env, &rotation);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events()
+        .publish((symbol_short!("rsc_start"), group_id), order);
+
+    Ok(rotation)
+}
+
+/// Pays the member's fixed contribution for the current round.
+///
+/// # Errors
+/// * `InvalidGroupConfig` - If the group is not rotating
+/// * `PlanCompleted` - If every round has been paid out
+/// * `NotGroupMember` - If the user is not in the payout order
+/// * `TooLate` - If the round deadline has passed (the round must be closed first)
+/// * `RoundAlreadyPaid` - If the member already paid this round
+pub fn contribute_to_round(env: &Env, user: Address, group_id: u64) -> Result<u32, SavingsError> {
+    contribute_to_round_funded(env, user, group_id, Funding::Signed)
+}
+
+/// Pays the member's round contribution, authorized and pulled as `funding` says.
+pub(crate) fn contribute_to_round_funded(
+    env: &Env,
+    user: Address,
+    group_id: u64,
+    funding: Funding,
+) -> Result<u32, SavingsError> {
+    ensure_not_paused(env)?;
+    funding.authorize(&user);
+
+    let rotation = get_active_rotation(env, group_id)?;
+    let order = get_payout_order(env, group_id);
+    if !order.contains(&user) {
+        return Err(SavingsError::NotGroupMember);
+    }
+
+    if env.ledger().timestamp() >= round_deadline(env, &rotation) {
+        return Err(SavingsError::TooLate);
+    }
+
+    let round = rotation.current_round;
+    let paid_key = RoscaKey::RoundPaid(group_id, round);
+    let mut paid: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&paid_key)
+        .unwrap_or(Vec::new(env));
+    if paid.contains(&user) {
+        return Err(SavingsError::RoundAlreadyPaid);
+    }
+
+    let mut group = get_group(env, group_id)?;
+    custody::receive_funded(
+        env,
+        &group.asset,
+        &user,
+        rotation.contribution_amount,
+        funding,
+    )?;
+
+    paid.push_back(user.clone());
+    env.storage().persistent().set(&paid_key, &paid);
+    extend_rosca_ttl(env, &paid_key);
+
+    group.current_amount = group
+        .current_amount
+        .checked_add(rotation.contribution_amount)
+        .ok_or(SavingsError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (symbol_short!("rsc_pay"), user, group_id),
+        (round, rotation.contribution_amount),
+    );
+
+    Ok(round)
+}
+
+/// Closes the current round and pays the pot to its recipient.
+///
+/// Callable by anyone once every member has paid, or once the round deadline
+/// has passed. Members who did not pay are charged arrears for the round.
+/// The next round opens now and runs for a full `cycle_duration`.
+/// Before the recipient is paid, any arrears they owe are withheld from the
+/// pot and forwarded to the recipients of the rounds they missed.
+///
+/// Returns the amount paid to the round's recipient.
+///
+/// # Errors
+/// * `InvalidGroupConfig` - If the group is not rotating
+/// * `PlanCompleted` - If every round has been paid out
+/// * `GroupCycleIncomplete` - If members are still to pay and the deadline has not passed
+pub fn close_round(env: &Env, group_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+
+    let mut rotation = get_active_rotation(env, group_id)?;
+    let round = rotation.current_round;
+    let order = get_payout_order(env, group_id);
+    let paid: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&RoscaKey::RoundPaid(group_id, round))
+        .unwrap_or(Vec::new(env));
+
+    let deadline = round_deadline(env, &rotation);
+    if paid.len() < order.len() && env.ledger().timestamp() < deadline {
+        return Err(SavingsError::GroupCycleIncomplete);
+    }
+
+    // Record missed payments
+    for member in order.iter() {
+        if !paid.contains(&member) {
+            let key = RoscaKey::Arrears(group_id, member.clone());
+            let mut missed = get_arrears(env, group_id, &member);
+            missed.push_back(round);
+            env.storage().persistent().set(&key, &missed);
+            extend_rosca_ttl(env, &key);
+
+            env.events()
+                .publish((symbol_short!("rsc_miss"), member, group_id), round);
+        }
+    }
+
+    let mut group = get_group(env, group_id)?;
+    let recipient = order.get(round).ok_or(SavingsError::DataCorruption)?;
+    let mut pot = group.current_amount;
+
+    // Withhold the recipient's own arrears and forward them to the short rounds
+    let arrears_key = RoscaKey::Arrears(group_id, recipient.clone());
+    let missed = get_arrears(env, group_id, &recipient);
+    let mut still_owed = Vec::new(env);
+    let mut forwards: Vec<(Address, i128)> = Vec::new(env);
+    for missed_round in missed.iter() {
+        if missed_round == round || pot < rotation.contribution_amount {
+            still_owed.push_back(missed_round);
+            continue;
+        }
+        pot -= rotation.contribution_amount;
+        let short_recipient = order
+            .get(missed_round)
+            .ok_or(SavingsError::DataCorruption)?;
+        forwards.push_back((short_recipient, rotation.contribution_amount));
+    }
+    if still_owed.is_empty() {
+        env.storage().persistent().remove(&arrears_key);
+    } else {
+        env.storage().persistent().set(&arrears_key, &still_owed);
+        extend_rosca_ttl(env, &arrears_key);
+    }
+
+    rotation.current_round += 1;
+    let opened_key = RoscaKey::RoundOpened(group_id);
+    if rotation.current_round >= rotation.total_rounds {
+        rotation.is_finished = true;
+        group.is_completed = true;
+        env.storage().persistent().remove(&opened_key);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&opened_key, &env.ledger().timestamp());
+    }
+    save_rotation(env, &rotation);
+
+    group.current_amount = 0;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (symbol_short!("rsc_out"), recipient.clone(), group_id),
+        (round, pot),
+    );
+    if rotation.is_finished {
+        env.events()
+            .publish((symbol_short!("rsc_done"), group_id), rotation.total_rounds);
+    }
+
+    for (to, amount) in forwards.iter() {
+        custody::send(env, &group.asset, &to, amount)?;
+    }
+    custody::send(env, &group.asset, &recipient, pot)?;
+
+    Ok(pot)
+}
+
+/// Pays all of a member's outstanding arrears.
+///
+/// Each missed contribution goes straight to the recipient of the round it
+/// was missed in. Returns the total paid.
+///
+/// # Errors
+/// * `InvalidGroupConfig` - If the group is not rotating
+pub fn pay_arrears(env: &Env, user: Address, group_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let rotation = get_rotation(env, group_id).ok_or(SavingsError::InvalidGroupConfig)?;
+    let missed = get_arrears(env, group_id, &user);
+    if missed.is_empty() {
+        return Ok(0);
+    }
+
+    let group = get_group(env, group_id)?;
+    let order = get_payout_order(env, group_id);
+    let total = rotation
+        .contribution_amount
+        .checked_mul(missed.len() as i128)
+        .ok_or(SavingsError::Overflow)?;
+
+    env.storage()
+        .persistent()
+        .remove(&RoscaKey::Arrears(group_id, user.clone()));
+
+    env.events()
+        .publish((symbol_short!("rsc_arr"), user.clone(), group_id), total);
+
+    custody::receive(env, &group.asset, &user, total)?;
+    for missed_round in missed.iter() {
+        let to = order
+            .get(missed_round)
+            .ok_or(SavingsError::DataCorruption)?;
+        custody::send(env, &group.asset, &to, rotation.contribution_amount)?;
+    }
+
+    Ok(total)
+}
+
+// ========== Views ==========
+
+/// Returns true once a group has started rotating.
+pub fn is_rotating(env: &Env, group_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&RoscaKey::Rotation(group_id))
+}
+
+pub fn get_rotation(env: &Env, group_id: u64) -> Option<Rotation> {
+    env.storage()
+        .persistent()
+        .get(&RoscaKey::Rotation(group_id))
+}
+
+pub fn get_payout_order(env: &Env, group_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&RoscaKey::PayoutOrder(group_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Returns the rounds a member missed and has not yet settled.
+pub fn get_arrears(env: &Env, group_id: u64, member: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&RoscaKey::Arrears(group_id, member.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Returns the state of the round currently collecting contributions.
+///
+/// # Errors
+/// * `InvalidGroupConfig` - If the group is not rotating
+/// * `PlanCompleted` - If every round has been paid out
+pub fn get_current_round(env: &Env, group_id: u64) -> Result<RoundInfo, SavingsError> {
+    let rotation = get_active_rotation(env, group_id)?;
+    let order = get_payout_order(env, group_id);
+    let round = rotation.current_round;
+    let paid: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&RoscaKey::RoundPaid(group_id, round))
+        .unwrap_or(Vec::new(env));
+
+    Ok(RoundInfo {
+        round,
+        recipient: order.get(round).ok_or(SavingsError::DataCorruption)?,
+        deadline: round_deadline(env, &rotation),
+        pot: get_group(env, group_id)?.current_amount,
+        paid_count: paid.len(),
+        member_count: order.len(),
+    })
+}
+
+/// Returns true if `member` has paid `round`.
+pub fn has_paid_round(env: &Env, group_id: u64, round: u32, member: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<RoscaKey, Vec<Address>>(&RoscaKey::RoundPaid(group_id, round))
+        .map(|paid| paid.contains(member))
+        .unwrap_or(false)
+}
+
+// ========== Internal Helpers ==========
+
+fn get_group(env: &Env, group_id: u64) -> Result<GroupSave, SavingsError> {
+    group::get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)
+}
+
+fn get_active_rotation(env: &Env, group_id: u64) -> Result<Rotation, SavingsError> {
+    let rotation = get_rotation(env, group_id).ok_or(SavingsError::InvalidGroupConfig)?;
+    if rotation.is_finished {
+        return Err(SavingsError::PlanCompleted);
+    }
+    Ok(rotation)
+}
+
+/// Deadline of the current round: one cycle after it opened. Round 0 opens
+/// at `start_time`; later rounds open when the previous round closes.
+fn round_deadline(env: &Env, rotation: &Rotation) -> u64 {
+    let opened = env
+        .storage()
+        .persistent()
+        .get(&RoscaKey::RoundOpened(rotation.group_id))
+        .unwrap_or_else(|| {
+            rotation.start_time.saturating_add(
+                rotation
+                    .cycle_duration
+                    .saturating_mul(rotation.current_round as u64),
+            )
+        });
+    opened.saturating_add(rotation.cycle_duration)
+}
+
+fn save_rotation(env: &Env, rotation: &Rotation) {
+    let key = RoscaKey::Rotation(rotation.group_id);
+    env.storage().persistent().set(&key, rotation);
+    extend_rosca_ttl(env, &key);
+    extend_rosca_ttl(env, &RoscaKey::PayoutOrder(rotation.group_id));
+    extend_rosca_ttl(env, &RoscaKey::RoundOpened(rotation.group_id));
+}
+
+fn extend_rosca_ttl(env: &Env, key: &RoscaKey) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    }
+}
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracterror, contracttype, Address, String, Symbol};
+use soroban_sdk::{
+    contracterror, contracttype, Address, Env, Map, String, Symbol, TryFromVal, Val, Vec,
+};
 
 /// Represents the different types of savings plans available in Nestera
 #[contracttype]
@@ -55,6 +57,8 @@ impl User {
 pub struct GroupSave {
     pub id: u64,
     pub creator: Address,
+    /// Asset members contribute in
+    pub asset: Address,
     pub title: String,
     pub description: String,
     pub category: String,
@@ -64,6 +68,10 @@ pub struct GroupSave {
     pub contribution_amount: i128,
     pub is_public: bool,
     pub member_count: u32,
+    /// Maximum number of members; 0 means no cap
+    pub max_members: u32,
+    /// Length of each contribution period in seconds; 0 disables periods
+    pub period_duration: u64,
     pub start_time: u64,
     pub end_time: u64,
     pub is_completed: bool,
@@ -75,11 +83,17 @@ pub struct GroupSave {
 pub struct LockSave {
     pub id: u64,
     pub owner: Address,
+    /// Asset the principal is locked in
+    pub asset: Address,
     pub amount: i128,
     pub interest_rate: u32,
     pub start_time: u64,
     pub maturity_time: u64,
     pub is_withdrawn: bool,
+    /// Long-lock bonus points awarded at creation, revoked on early break
+    pub bonus_points: u128,
+    /// Whether the lock rolls into a new lock of the same duration at maturity
+    pub auto_renew: bool,
 }
 
 /// Custom error types for the savings contract
@@ -105,25 +119,66 @@ pub enum SavingsError {
 pub struct GoalSave {
     pub id: u64,
     pub owner: Address,
+    /// Asset the goal is saved in
+    pub asset: Address,
     pub goal_name: Symbol,
     pub target_amount: i128,
     pub current_amount: i128,
+    /// Annual rate in basis points, fixed from the goal rate at creation
     pub interest_rate: u32,
+    /// Interest accrued up to `last_accrual`
+    pub accrued_interest: i128,
+    pub last_accrual: u64,
     pub start_time: u64,
+    /// Target date for reaching the goal; 0 means no deadline
+    pub deadline: u64,
+    /// Highest milestone reached, in percent of target (0, 25, 50 or 75)
+    pub milestones_reached: u32,
+    /// Pay the goal out automatically once the target is reached
+    pub auto_payout: bool,
     pub is_completed: bool,
     pub is_withdrawn: bool,
 }
 
-/// Represents an automated recurring deposit schedule for Flexi Save
+/// Where each AutoSave execution sends its funds
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AutoSaveTarget {
+    /// Deposit into the user's Flexi balance
+    Flexi,
+    /// Open a new Lock Save of the given duration (seconds) each run
+    Lock(u64),
+    /// Deposit into the given Goal Save
+    Goal(u64),
+    /// Contribute to the given Group Save (or its current rotation round)
+    Group(u64),
+}
+
+/// Represents an automated recurring deposit schedule
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AutoSave {
     pub id: u64,
     pub user: Address,
+    pub target: AutoSaveTarget,
     pub amount: i128,
     pub interval_seconds: u64,
     pub next_execution_time: u64,
     pub is_active: bool,
+    /// Paused schedules keep their settings but are not executed
+    pub is_paused: bool,
+    /// Last timestamp a run may be scheduled at (0 = no end date)
+    pub end_time: u64,
+    /// Number of runs after which the schedule finishes (0 = unlimited)
+    pub max_executions: u32,
+    /// Number of successful runs so far
+    pub executions: u32,
+    /// Run every missed interval one by one instead of skipping to the next future slot
+    pub catch_up: bool,
+    /// Base-asset fee paid to the keeper for each execution (0 = none)
+    pub keeper_fee: i128,
+    /// Prepaid balance that keeper fees are drawn from
+    pub keeper_balance: i128,
 }
 
 /// Storage keys for the contract's persistent data
@@ -151,11 +206,20 @@ pub enum DataKey {
     AllocationConfig,
     /// Early break fee (basis points) for goal saves
     EarlyBreakFeeBps,
+    /// Maximum early break penalty (basis points) for lock saves, charged at
+    /// lock start and decreasing linearly to zero at maturity
+    LockBreakPenaltyBps,
+    /// Share (basis points) of accrued goal interest forfeited when a goal is
+    /// broken or released after a missed deadline; full forfeit when unset
+    GoalInterestForfeitBps,
     /// Fee recipient for protocol/treasury fees
     FeeRecipient,
+    /// SEP-41 token contract held in custody for savings deposits
+    UnderlyingAsset,
     /// Track total principal deposited in a strategy (deposits - withdrawals)
     StrategyTotalPrincipal(Address),
-    /// Track accumulated yield designated for Nestera users from a strategy
+    /// Track accumulated yield designated for Nestera users from a strategy;
+    /// per-position attribution lives in `strategy::routing::StrategyShareKey`
     StrategyYield(Address),
     /// Aggregate performance metrics for a strategy (total deposited, withdrawn, harvested, APY)
     StrategyPerformance(Address),
@@ -164,7 +228,8 @@ pub enum DataKey {
     User(Address),
     /// Maps a (user address, plan_id) tuple to a SavingsPlan
     SavingsPlan(Address, u64),
-    FlexiBalance(Address),
+    /// Maps (user, asset) to the user's Flexi Save balance in that asset
+    FlexiBalance(Address, Address),
     TotalBalance(Address),
     /// Maps group ID to GroupSave struct
     GroupSave(u64),
@@ -194,12 +259,14 @@ pub enum DataKey {
     UserAutoSaves(Address),
     /// Stores the next auto-incrementing AutoSave schedule ID
     NextAutoSaveId,
-    // Interest Rates
-    FlexiRate,
-    GoalRate,
-    GroupRate,
-    /// Maps duration (days) to interest rate
-    LockRate(u64),
+    // Interest Rates (keyed by asset)
+    FlexiRate(Address),
+    GoalRate(Address),
+    GroupRate(Address),
+    /// Maps (asset, duration in days) to interest rate
+    LockRate(Address, u64),
+    /// Sorted list of durations (days) with a configured lock rate, per asset
+    LockRateTiers(Address),
     /// Maps (plan_type, plan_id) to disabled status
     DisabledStrategy(PlanType, u64),
 }
@@ -233,6 +300,125 @@ pub struct StrategyPerformance {
     /// Computed as: (total_harvested * 10_000) / total_deposited
     /// Returns 0 when no deposits have been made.
     pub apy_estimate_bps: u32,
+    /// Cumulative shortfall realized against the strategy's recorded principal
+    pub total_losses: i128,
+    /// Part of `total_losses` covered from the treasury reserve
+    pub total_loss_covered: i128,
+    /// Most recent realized losses, oldest first
+    pub losses: Vec<StrategyLoss>,
+}
+
+/// A realized shortfall of a strategy's balance against its recorded principal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyLoss {
+    /// Total shortfall realized
+    pub amount: i128,
+    /// Part covered from the treasury reserve; the rest was written off
+    /// against the strategy's positions pro rata to their shares
+    pub covered: i128,
+    /// Unix timestamp when the loss was realized
+    pub timestamp: u64,
+}
+
+// ========== Legacy Shapes ==========
+//
+// Plans stored by earlier versions keep their original shape until they are
+// next written; readers upgrade them with `decode_versioned`.
+
+/// Lock Save as stored before plans were kept per asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyLockSave {
+    pub id: u64,
+    pub owner: Address,
+    pub amount: i128,
+    pub interest_rate: u32,
+    pub start_time: u64,
+    pub maturity_time: u64,
+    pub is_withdrawn: bool,
+}
+
+/// Goal Save as stored before plans were kept per asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGoalSave {
+    pub id: u64,
+    pub owner: Address,
+    pub goal_name: Symbol,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub interest_rate: u32,
+    pub start_time: u64,
+    pub is_completed: bool,
+    pub is_withdrawn: bool,
+}
+
+/// Group Save as stored before plans were kept per asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGroupSave {
+    pub id: u64,
+    pub creator: Address,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub contribution_type: u32,
+    pub contribution_amount: i128,
+    pub is_public: bool,
+    pub member_count: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub is_completed: bool,
+}
+
+/// AutoSave schedule as stored before schedules had targets
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyAutoSave {
+    pub id: u64,
+    pub user: Address,
+    pub amount: i128,
+    pub interval_seconds: u64,
+    pub next_execution_time: u64,
+    pub is_active: bool,
+}
+
+/// Strategy performance as stored before losses were tracked
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyStrategyPerformance {
+    pub total_deposited: i128,
+    pub total_withdrawn: i128,
+    pub total_harvested: i128,
+    pub apy_estimate_bps: u32,
+}
+
+/// Decodes a stored struct, upgrading the shape stored by earlier versions
+/// (`L`) when `field` is missing from it.
+pub(crate) fn decode_versioned<T, L>(
+    env: &Env,
+    raw: Map<Symbol, Val>,
+    field: &str,
+    upgrade: impl FnOnce(L) -> T,
+) -> Option<T>
+where
+    T: TryFromVal<Env, Val>,
+    L: TryFromVal<Env, Val>,
+{
+    if raw.contains_key(Symbol::new(env, field)) {
+        return T::try_from_val(env, &raw.to_val()).ok();
+    }
+    L::try_from_val(env, &raw.to_val()).ok().map(upgrade)
+}
+
+/// Reads a boolean field of a stored struct in any of its shapes.
+pub(crate) fn stored_flag(env: &Env, raw: &Map<Symbol, Val>, field: &str) -> bool {
+    raw.get(Symbol::new(env, field))
+        .and_then(|value| bool::try_from_val(env, &value).ok())
+        .unwrap_or(false)
 }
 
 // View-specific structures (used by views.rs module)
@@ -273,3 +459,14 @@ pub struct GroupSaveView {
     pub contribution_type: u32,
     pub group_id: u64,
 }
+
+/// A user's holdings in a single asset, split by plan type
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AssetBalance {
+    pub asset: Address,
+    pub flexi_balance: i128,
+    pub lock_balance: i128,
+    pub goal_balance: i128,
+    pub group_balance: i128,
+}
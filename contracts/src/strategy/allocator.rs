@@ -0,0 +1,286 @@
+use crate::errors::SavingsError;
+use crate::strategy::registry::{self, StrategyInfo};
+use crate::strategy::routing::{self, StrategyPositionKey};
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+/// Highest strategy `risk_level` a conservative plan may be allocated to.
+pub const CONSERVATIVE_MAX_RISK: u32 = 2;
+/// Highest strategy `risk_level` a balanced plan may be allocated to.
+pub const BALANCED_MAX_RISK: u32 = 5;
+
+/// How much strategy risk a plan accepts when its deposits are allocated.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskProfile {
+    /// Only strategies with `risk_level <= CONSERVATIVE_MAX_RISK`
+    Conservative,
+    /// Only strategies with `risk_level <= BALANCED_MAX_RISK`
+    Balanced,
+    /// Any enabled strategy
+    Aggressive,
+}
+
+impl RiskProfile {
+    /// Highest strategy `risk_level` this profile accepts.
+    pub fn max_risk_level(&self) -> u32 {
+        match self {
+            RiskProfile::Conservative => CONSERVATIVE_MAX_RISK,
+            RiskProfile::Balanced => BALANCED_MAX_RISK,
+            RiskProfile::Aggressive => u32::MAX,
+        }
+    }
+}
+
+/// Part of a deposit assigned to one strategy.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyAllocation {
+    pub strategy: Address,
+    pub amount: i128,
+}
+
+/// Storage keys for the strategy allocator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AllocatorKey {
+    /// Risk profile chosen for a plan
+    Profile(StrategyPositionKey),
+}
+
+/// Sets the risk profile used when allocating a plan's deposits.
+///
+/// Callers are responsible for checking that the plan belongs to the caller.
+pub fn set_risk_profile(env: &Env, position_key: StrategyPositionKey, profile: RiskProfile) {
+    let key = AllocatorKey::Profile(position_key);
+    env.storage().persistent().set(&key, &profile);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    env.events()
+        .publish((symbol_short!("strat"), symbol_short!("profile")), profile);
+}
+
+/// Returns a plan's risk profile, defaulting to `Conservative`.
+pub fn get_risk_profile(env: &Env, position_key: StrategyPositionKey) -> RiskProfile {
+    env.storage()
+        .persistent()
+        .get(&AllocatorKey::Profile(position_key))
+        .unwrap_or(RiskProfile::Conservative)
+}
+
+/// Total principal routed across all registered strategies.
+pub fn total_value_locked(env: &Env) -> i128 {
+    registry::get_all_strategies(env)
+        .iter()
+        .fold(0i128, |total, strategy| {
+            total.saturating_add(routing::get_total_principal(env, &strategy))
+        })
+}
+
+/// How much more principal a strategy can take, given the protocol's routed
+/// principal once the deposit being allocated is included.
+pub(crate) fn strategy_room(
+    env: &Env,
+    info: &StrategyInfo,
+    tvl_after: i128,
+) -> Result<i128, SavingsError> {
+    let principal = routing::get_total_principal(env, &info.address);
+
+    let cap_room = if info.deposit_cap > 0 {
+        info.deposit_cap - principal
+    } else {
+        i128::MAX
+    };
+    let share_room = if info.max_tvl_share_bps < 10_000 {
+        tvl_after
+            .checked_mul(info.max_tvl_share_bps as i128)
+            .ok_or(SavingsError::Overflow)?
+            / 10_000
+            - principal
+    } else {
+        i128::MAX
+    };
+
+    Ok(cap_room.min(share_room).max(0))
+}
+
+/// Splits `amount` across the enabled base-asset strategies that fit
+/// `profile` (see `plan_allocation_in`).
+pub fn plan_allocation(
+    env: &Env,
+    profile: RiskProfile,
+    amount: i128,
+) -> Result<Vec<StrategyAllocation>, SavingsError> {
+    plan_allocation_in(env, profile, &crate::assets::base_asset(env), amount)
+}
+
+/// Splits `amount` across the enabled strategies in `asset` that fit
+/// `profile`.
+///
+/// The amount is spread evenly; strategies that hit their deposit cap or TVL
+/// share limit are filled to the limit and the rest is spread over the others.
+/// Strategies are taken in registration order, which also decides who gets
+/// the rounding remainder.
+///
+/// # Errors
+/// * `InvalidAmount` - If amount <= 0
+/// * `StrategyNotFound` - If no enabled strategy fits the profile
+/// * `AmountExceedsLimit` - If the eligible strategies cannot take the whole amount
+pub fn plan_allocation_in(
+    env: &Env,
+    profile: RiskProfile,
+    asset: &Address,
+    amount: i128,
+) -> Result<Vec<StrategyAllocation>, SavingsError> {
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let tvl_after = total_value_locked(env)
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+
+    // (allocation so far, room left) for every eligible strategy
+    let mut allocations: Vec<StrategyAllocation> = Vec::new(env);
+    let mut rooms: Vec<i128> = Vec::new(env);
+    for strategy in registry::get_all_strategies(env).iter() {
+        let info = registry::get_strategy(env, strategy.clone())?;
+        if !info.enabled
+            || info.risk_level > profile.max_risk_level()
+            || registry::get_strategy_asset(env, &strategy) != *asset
+        {
+            continue;
+        }
+        allocations.push_back(StrategyAllocation {
+            strategy,
+            amount: 0,
+        });
+        rooms.push_back(strategy_room(env, &info, tvl_after)?);
+    }
+    if allocations.is_empty() {
+        return Err(SavingsError::StrategyNotFound);
+    }
+
+    let mut remaining = amount;
+    while remaining > 0 {
+        let open = rooms.iter().filter(|room| *room > 0).count() as i128;
+        if open == 0 {
+            return Err(SavingsError::AmountExceedsLimit);
+        }
+
+        let share = remaining / open;
+        let mut extra = remaining % open;
+        for i in 0..allocations.len() {
+            let room = rooms.get(i).unwrap();
+            if room <= 0 {
+                continue;
+            }
+            let mut want = share;
+            if extra > 0 {
+                want += 1;
+                extra -= 1;
+            }
+            let give = want.min(room);
+            if give == 0 {
+                continue;
+            }
+
+            let mut allocation = allocations.get(i).unwrap();
+            allocation.amount += give;
+            allocations.set(i, allocation);
+            rooms.set(i, room - give);
+            remaining -= give;
+        }
+    }
+
+    let mut result = Vec::new(env);
+    for allocation in allocations.iter() {
+        if allocation.amount > 0 {
+            result.push_back(allocation);
+        }
+    }
+    Ok(result)
+}
+
+/// Part of a plan's balance not yet routed to strategies: the lock amount or
+/// the group pool, less the principal its positions hold.
+///
+/// # Errors
+/// * `PlanNotFound` - If the plan does not exist
+pub fn unallocated_balance(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+) -> Result<i128, SavingsError> {
+    let balance = match position_key {
+        StrategyPositionKey::Lock(lock_id) => {
+            let lock =
+                crate::lock::get_lock_save(env, *lock_id).ok_or(SavingsError::PlanNotFound)?;
+            if lock.is_withdrawn {
+                0
+            } else {
+                lock.amount
+            }
+        }
+        StrategyPositionKey::Group(group_id) => {
+            crate::group::get_group_save(env, *group_id)
+                .ok_or(SavingsError::PlanNotFound)?
+                .current_amount
+        }
+    };
+    let routed = routing::get_positions(env, position_key.clone())
+        .iter()
+        .fold(0i128, |total, position| {
+            total.saturating_add(position.principal_deposited)
+        });
+    Ok((balance - routed).max(0))
+}
+
+/// Allocates `amount` of a plan's funds across strategies according to the
+/// plan's risk profile, routing each part like `routing::route_to_strategy`.
+///
+/// # Returns
+/// The allocation that was routed.
+///
+/// # Errors
+/// * `PlanNotFound` / `Unauthorized` - If `caller` does not own the plan
+/// * `InsufficientBalance` - If `amount` exceeds the plan's unallocated balance
+/// * Any error from `plan_allocation_in` or routing
+pub fn allocate(
+    env: &Env,
+    caller: &Address,
+    position_key: StrategyPositionKey,
+    amount: i128,
+) -> Result<Vec<StrategyAllocation>, SavingsError> {
+    routing::require_position_owner(env, &position_key, caller)?;
+    if amount > unallocated_balance(env, &position_key)? {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    let profile = get_risk_profile(env, position_key.clone());
+    let asset = routing::plan_asset(env, &position_key)?;
+    let allocations = plan_allocation_in(env, profile, &asset, amount)?;
+
+    // Limits are judged against the TVL once the whole deposit is routed,
+    // as when the allocation was planned
+    let tvl_after = total_value_locked(env)
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    for allocation in allocations.iter() {
+        routing::route_with_tvl(
+            env,
+            allocation.strategy,
+            position_key.clone(),
+            allocation.amount,
+            tvl_after,
+        )?;
+    }
+
+    env.events().publish(
+        (symbol_short!("strat"), symbol_short!("allocate")),
+        (profile, amount, allocations.len()),
+    );
+
+    Ok(allocations)
+}
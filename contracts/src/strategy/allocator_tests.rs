@@ -0,0 +1,321 @@
+use crate::errors::SavingsError;
+use crate::strategy::allocator::{self, RiskProfile, StrategyAllocation};
+use crate::strategy::interface::YieldStrategy;
+use crate::strategy::routing::StrategyPositionKey;
+use crate::{NesteraContract, NesteraContractClient};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, Symbol, Vec,
+};
+
+/// Strategy that simply holds whatever is deposited, one share per unit.
+#[contract]
+pub struct HoldingStrategy;
+
+#[contractimpl]
+impl YieldStrategy for HoldingStrategy {
+    fn strategy_deposit(env: Env, _from: Address, amount: i128) -> i128 {
+        let key = Symbol::new(&env, "held");
+        let held: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(held + amount));
+        amount
+    }
+
+    fn strategy_withdraw(env: Env, _to: Address, amount: i128) -> i128 {
+        let key = Symbol::new(&env, "held");
+        let held: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(held - amount));
+        amount
+    }
+
+    fn strategy_harvest(_env: Env, _to: Address) -> i128 {
+        0
+    }
+
+    fn strategy_balance(env: Env, _addr: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "held"))
+            .unwrap_or(0)
+    }
+}
+
+/// Sets up the contract with one registered `HoldingStrategy` per risk level.
+fn setup(risk_levels: &[u32]) -> (Env, NesteraContractClient<'static>, Address, Vec<Address>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
+
+    let mut strategies = Vec::new(&env);
+    for risk_level in risk_levels {
+        let strategy = env.register(HoldingStrategy, ());
+        client.register_strategy(&admin, &strategy, risk_level);
+        strategies.push_back(strategy);
+    }
+    (env, client, admin, strategies)
+}
+
+fn allocation(strategy: Address, amount: i128) -> StrategyAllocation {
+    StrategyAllocation { strategy, amount }
+}
+
+#[test]
+fn test_profile_limits_strategies_by_risk_level() {
+    let (env, client, _admin, s) = setup(&[1, 4, 8]);
+
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Conservative, &900),
+        vec![&env, allocation(s.get(0).unwrap(), 900)]
+    );
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Balanced, &900),
+        vec![
+            &env,
+            allocation(s.get(0).unwrap(), 450),
+            allocation(s.get(1).unwrap(), 450)
+        ]
+    );
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Aggressive, &900),
+        vec![
+            &env,
+            allocation(s.get(0).unwrap(), 300),
+            allocation(s.get(1).unwrap(), 300),
+            allocation(s.get(2).unwrap(), 300)
+        ]
+    );
+}
+
+#[test]
+fn test_rounding_remainder_goes_to_earliest_strategies() {
+    let (env, client, _admin, s) = setup(&[0, 1, 2]);
+
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Conservative, &1_000),
+        vec![
+            &env,
+            allocation(s.get(0).unwrap(), 334),
+            allocation(s.get(1).unwrap(), 333),
+            allocation(s.get(2).unwrap(), 333)
+        ]
+    );
+}
+
+#[test]
+fn test_disabled_strategies_are_skipped() {
+    let (env, client, admin, s) = setup(&[1, 1]);
+    client.disable_strategy(&admin, &s.get(0).unwrap());
+
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Conservative, &500),
+        vec![&env, allocation(s.get(1).unwrap(), 500)]
+    );
+}
+
+#[test]
+fn test_deposit_cap_overflows_into_other_strategies() {
+    let (env, client, admin, s) = setup(&[1, 2, 2]);
+    client.set_strategy_limits(&admin, &s.get(0).unwrap(), &200, &10_000);
+
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Conservative, &1_000),
+        vec![
+            &env,
+            allocation(s.get(0).unwrap(), 200),
+            allocation(s.get(1).unwrap(), 400),
+            allocation(s.get(2).unwrap(), 400)
+        ]
+    );
+}
+
+#[test]
+fn test_tvl_share_limit_counts_the_new_deposit() {
+    let (env, client, admin, s) = setup(&[1, 1]);
+    client.set_strategy_limits(&admin, &s.get(0).unwrap(), &0, &2_500);
+
+    // 25% of the 1,000 the protocol will hold once this deposit is routed
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Conservative, &1_000),
+        vec![
+            &env,
+            allocation(s.get(0).unwrap(), 250),
+            allocation(s.get(1).unwrap(), 750)
+        ]
+    );
+}
+
+#[test]
+fn test_allocation_fails_when_limits_cannot_absorb_deposit() {
+    let (_env, client, admin, s) = setup(&[1, 1]);
+    client.set_strategy_limits(&admin, &s.get(0).unwrap(), &300, &10_000);
+    client.set_strategy_limits(&admin, &s.get(1).unwrap(), &300, &10_000);
+
+    assert_eq!(
+        client.try_preview_strategy_allocation(&RiskProfile::Conservative, &601),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+}
+
+#[test]
+fn test_no_fitting_strategy_errors() {
+    let (_env, client, _admin, _s) = setup(&[7, 9]);
+
+    assert_eq!(
+        client.try_preview_strategy_allocation(&RiskProfile::Balanced, &100),
+        Err(Ok(SavingsError::StrategyNotFound))
+    );
+    assert_eq!(
+        client.try_preview_strategy_allocation(&RiskProfile::Aggressive, &0),
+        Err(Ok(SavingsError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_allocate_routes_lock_by_its_profile() {
+    let (env, client, _admin, s) = setup(&[1, 4, 8]);
+    let user = Address::generate(&env);
+    client.init_user(&user);
+    let lock_id = client.create_lock_save(&user, &1_000, &3600);
+
+    assert_eq!(
+        client.get_lock_risk_profile(&lock_id),
+        RiskProfile::Conservative
+    );
+    client.set_lock_risk_profile(&user, &lock_id, &RiskProfile::Balanced);
+    client.allocate_lock_to_strategies(&user, &lock_id, &1_000);
+
+    let positions = client.get_lock_strategy_positions(&lock_id);
+    assert_eq!(positions.len(), 2);
+    for (i, position) in positions.iter().enumerate() {
+        assert_eq!(position.strategy, s.get(i as u32).unwrap());
+        assert_eq!(position.principal_deposited, 500);
+    }
+
+    env.as_contract(&client.address, || {
+        assert_eq!(allocator::total_value_locked(&env), 1_000);
+    });
+}
+
+#[test]
+fn test_existing_principal_counts_against_limits() {
+    let (env, client, admin, s) = setup(&[1, 1]);
+    client.set_strategy_limits(&admin, &s.get(0).unwrap(), &600, &10_000);
+
+    let user = Address::generate(&env);
+    client.init_user(&user);
+    let lock_id = client.create_lock_save(&user, &1_000, &3600);
+    client.route_lock_to_strategy(&user, &lock_id, &s.get(0).unwrap(), &500);
+
+    assert_eq!(
+        client.preview_strategy_allocation(&RiskProfile::Conservative, &400),
+        vec![
+            &env,
+            allocation(s.get(0).unwrap(), 100),
+            allocation(s.get(1).unwrap(), 300)
+        ]
+    );
+
+    // Direct routing honours the cap too
+    assert_eq!(
+        client.try_route_lock_to_strategy(&user, &lock_id, &s.get(0).unwrap(), &101),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+}
+
+#[test]
+fn test_only_owner_sets_risk_profile_and_limits_are_validated() {
+    let (env, client, admin, s) = setup(&[1]);
+    let user = Address::generate(&env);
+    client.init_user(&user);
+    let lock_id = client.create_lock_save(&user, &1_000, &3600);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_lock_risk_profile(&stranger, &lock_id, &RiskProfile::Aggressive),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_strategy_limits(&admin, &s.get(0).unwrap(), &-1, &10_000),
+        Err(Ok(SavingsError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_strategy_limits(&admin, &s.get(0).unwrap(), &0, &10_001),
+        Err(Ok(SavingsError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_only_owner_allocates_within_plan_balance() {
+    let (env, client, _admin, s) = setup(&[1, 1]);
+    let user = Address::generate(&env);
+    client.init_user(&user);
+    let lock_id = client.create_lock_save(&user, &1_000, &3600);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_allocate_lock_to_strategies(&stranger, &lock_id, &500),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_allocate_lock_to_strategies(&user, &lock_id, &1_001),
+        Err(Ok(SavingsError::InsufficientBalance))
+    );
+
+    // Principal already routed counts against the lock amount
+    client.route_lock_to_strategy(&user, &lock_id, &s.get(0).unwrap(), &600);
+    assert_eq!(
+        client.try_allocate_lock_to_strategies(&user, &lock_id, &401),
+        Err(Ok(SavingsError::InsufficientBalance))
+    );
+    client.allocate_lock_to_strategies(&user, &lock_id, &400);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            allocator::unallocated_balance(&env, &StrategyPositionKey::Lock(lock_id)),
+            Ok(0)
+        );
+    });
+}
+
+#[test]
+fn test_direct_routing_honours_tvl_share_limit() {
+    let (env, client, admin, s) = setup(&[1, 1]);
+    client.set_strategy_limits(&admin, &s.get(0).unwrap(), &0, &5_000);
+
+    let user = Address::generate(&env);
+    client.init_user(&user);
+    let lock_id = client.create_lock_save(&user, &3_000, &3600);
+    client.route_lock_to_strategy(&user, &lock_id, &s.get(1).unwrap(), &1_000);
+
+    // 1,001 of 2,001 would exceed half of the protocol's TVL
+    assert_eq!(
+        client.try_route_lock_to_strategy(&user, &lock_id, &s.get(0).unwrap(), &1_001),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+    client.route_lock_to_strategy(&user, &lock_id, &s.get(0).unwrap(), &1_000);
+}
+
+#[test]
+fn test_rollover_waits_for_strategy_positions_to_close() {
+    let (env, client, _admin, s) = setup(&[1]);
+    let user = Address::generate(&env);
+    client.init_user(&user);
+    let lock_id = client.create_lock_save(&user, &1_000, &3600);
+    client.set_lock_auto_renew(&user, &lock_id, &true);
+    client.route_lock_to_strategy(&user, &lock_id, &s.get(0).unwrap(), &500);
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(
+        client.try_rollover_lock_save(&lock_id),
+        Err(Ok(SavingsError::InvalidPlanConfig))
+    );
+
+    client.withdraw_lock_strategy(&user, &lock_id, &s.get(0).unwrap());
+    assert!(client.get_lock_strategy_positions(&lock_id).is_empty());
+    client.rollover_lock_save(&lock_id);
+}
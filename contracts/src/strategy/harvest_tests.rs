@@ -438,8 +438,13 @@ fn test_treasury_struct_no_double_counting() {
         let user_yield = profit - treasury_fee; // 9_000
 
         // Simulate what harvest_strategy does after the fix
-        treasury::record_fee(&env, treasury_fee, Symbol::new(&env, "perf"));
-        treasury::record_yield(&env, user_yield);
+        treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            treasury_fee,
+            Symbol::new(&env, "perf"),
+        );
+        treasury::record_yield(&env, &crate::assets::base_asset(&env), user_yield);
 
         let t = treasury::get_treasury(&env);
 
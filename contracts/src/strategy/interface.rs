@@ -7,6 +7,8 @@
 /// # Security Assumptions
 /// - Strategy contracts are audited and registered via governance/admin.
 /// - All state updates in Nestera happen BEFORE external strategy calls (CEI pattern).
+/// - Nestera transfers the tokens to the strategy before calling `deposit`;
+///   strategies never pull funds from Nestera.
 /// - Strategy contracts must not hold user funds beyond what is deposited via `deposit`.
 /// - `get_total_balance` must reflect the actual deposited principal + any accrued yield.
 /// - `withdraw` must return exactly the requested amount or revert.
@@ -22,6 +24,9 @@ use soroban_sdk::{contractclient, Address, Env};
 pub trait YieldStrategy {
     /// Deposits funds into the yield strategy.
     ///
+    /// The `amount` of tokens has already been transferred to the strategy
+    /// when this is called.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `from` - The address depositing (the Nestera contract)
@@ -31,7 +36,7 @@ pub trait YieldStrategy {
     /// The number of strategy shares minted for this deposit.
     fn strategy_deposit(env: Env, from: Address, amount: i128) -> i128;
 
-    /// Withdraws funds from the yield strategy.
+    /// Withdraws funds from the yield strategy, transferring them to `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
@@ -42,7 +47,7 @@ pub trait YieldStrategy {
     /// The actual amount of tokens returned.
     fn strategy_withdraw(env: Env, to: Address, amount: i128) -> i128;
 
-    /// Harvests accrued yield from the strategy.
+    /// Harvests accrued yield from the strategy, transferring it to `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
@@ -0,0 +1,284 @@
+use crate::custody;
+use crate::errors::SavingsError;
+use crate::governance;
+use crate::storage_types::{DataKey, StrategyLoss};
+use crate::strategy::interface::YieldStrategyClient;
+use crate::strategy::registry::{self, StrategyKey};
+use crate::strategy::routing::{self, StrategyPosition, StrategyPositionKey, YIELD_INDEX_SCALE};
+use crate::treasury;
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+/// Realized losses kept in a strategy's `StrategyPerformance::losses`.
+pub const MAX_LOSS_HISTORY: u32 = 10;
+
+/// Storage keys for strategy loss handling.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LossKey {
+    /// Whether realized losses are covered from the treasury reserve first
+    CoverFromReserve,
+    /// Cumulative written-off principal per share (scaled by `YIELD_INDEX_SCALE`)
+    Index(Address),
+    /// Written-off principal that positions have not absorbed yet
+    Unabsorbed(Address),
+    /// Loss index at a position's last absorption
+    Checkpoint(StrategyPositionKey, Address),
+}
+
+/// Sets whether realized strategy losses are covered from the treasury
+/// reserve before being written off against positions.
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+pub fn set_reserve_cover(env: &Env, caller: Address, enabled: bool) -> Result<(), SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+
+    env.storage()
+        .instance()
+        .set(&LossKey::CoverFromReserve, &enabled);
+
+    env.events()
+        .publish((symbol_short!("strat"), symbol_short!("loss_cov")), enabled);
+    Ok(())
+}
+
+/// Returns whether realized losses are covered from the treasury reserve.
+pub fn get_reserve_cover(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&LossKey::CoverFromReserve)
+        .unwrap_or(false)
+}
+
+fn read_i128(env: &Env, key: &LossKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn write_i128(env: &Env, key: &LossKey, value: i128) {
+    env.storage().persistent().set(key, &value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
+
+/// Cumulative written-off principal per share for a strategy.
+pub fn get_loss_index(env: &Env, strategy: &Address) -> i128 {
+    read_i128(env, &LossKey::Index(strategy.clone()))
+}
+
+/// Written-off principal of a strategy not yet taken off its positions.
+pub fn get_unabsorbed(env: &Env, strategy: &Address) -> i128 {
+    read_i128(env, &LossKey::Unabsorbed(strategy.clone()))
+}
+
+/// Principal a position has yet to give up for losses realized since its
+/// last absorption, capped at its principal.
+pub fn pending_loss(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+    position: &StrategyPosition,
+) -> Result<i128, SavingsError> {
+    if position.shares == 0 {
+        return Ok(0);
+    }
+    let checkpoint = read_i128(
+        env,
+        &LossKey::Checkpoint(position_key.clone(), position.strategy.clone()),
+    );
+    let delta = get_loss_index(env, &position.strategy)
+        .checked_sub(checkpoint)
+        .ok_or(SavingsError::Underflow)?;
+    Ok((position
+        .shares
+        .checked_mul(delta)
+        .ok_or(SavingsError::Overflow)?
+        / YIELD_INDEX_SCALE)
+        .min(position.principal_deposited))
+}
+
+/// Takes a position's part of realized losses off its principal, its plan's
+/// balance and the strategy's recorded principal, and moves its checkpoint to
+/// the current loss index. Must run before a position's shares or principal change; the
+/// caller saves the position.
+pub(crate) fn absorb_loss(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+    position: &mut StrategyPosition,
+) -> Result<(), SavingsError> {
+    let strategy = position.strategy.clone();
+    let part = pending_loss(env, position_key, position)?;
+    if part > 0 {
+        position.principal_deposited -= part;
+        routing::adjust_plan_balance(env, position_key, -part)?;
+
+        let principal_key = DataKey::StrategyTotalPrincipal(strategy.clone());
+        let principal: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&principal_key, &(principal - part).max(0));
+        let unabsorbed = get_unabsorbed(env, &strategy);
+        write_i128(
+            env,
+            &LossKey::Unabsorbed(strategy.clone()),
+            (unabsorbed - part).max(0),
+        );
+    }
+
+    let index = get_loss_index(env, &strategy);
+    let checkpoint_key = LossKey::Checkpoint(position_key.clone(), strategy);
+    if read_i128(env, &checkpoint_key) != index {
+        write_i128(env, &checkpoint_key, index);
+    }
+    Ok(())
+}
+
+/// Starts a position that did not share earlier losses at the current loss
+/// index.
+pub(crate) fn reset_checkpoint(env: &Env, position_key: &StrategyPositionKey, strategy: &Address) {
+    let index = get_loss_index(env, strategy);
+    if index != 0 {
+        write_i128(
+            env,
+            &LossKey::Checkpoint(position_key.clone(), strategy.clone()),
+            index,
+        );
+    }
+}
+
+/// Drops a removed position's loss checkpoint.
+pub(crate) fn clear_checkpoint(env: &Env, position_key: &StrategyPositionKey, strategy: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&LossKey::Checkpoint(position_key.clone(), strategy.clone()));
+}
+
+/// Forgets a strategy's written-off principal once it has no shares left, so
+/// rounding residue is not mistaken for a shortfall later.
+pub(crate) fn clear_unabsorbed(env: &Env, strategy: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&LossKey::Unabsorbed(strategy.clone()));
+}
+
+/// Realizes the shortfall of a strategy's balance against its recorded
+/// principal (admin / governance only).
+///
+/// A balance below principal is not treated as a loss until realized here:
+/// until then withdrawals simply redeem at the lower balance, so a temporary
+/// dip that recovers costs nobody principal.
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+/// * See `realize_loss`
+pub fn realize_strategy_loss(
+    env: &Env,
+    caller: Address,
+    strategy: Address,
+) -> Result<i128, SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+    realize_loss(env, &strategy)
+}
+
+/// Realizes any shortfall of a strategy's balance against its
+/// `StrategyTotalPrincipal`.
+///
+/// When reserve cover is enabled, as much of the loss as the treasury reserve
+/// holds in the strategy's asset is sent back into the strategy; the strategy
+/// shares it buys belong to all holders (see `routing::release_cover_shares`).
+/// The rest is written off: it
+/// raises the strategy's per-share loss index, and each position gives up its
+/// part of it the next time it changes (see `absorb_loss`). The strategy's
+/// recorded principal drops by exactly what positions absorb; until then the
+/// remainder is held as unabsorbed and excluded from the principal.
+///
+/// # Returns
+/// The loss realized, 0 if the strategy is not short.
+///
+/// # Errors
+/// * `StrategyNotFound` - If the strategy is not registered
+/// * `InvalidStrategyResponse` - If the strategy rejects the reserve top-up
+pub fn realize_loss(env: &Env, strategy: &Address) -> Result<i128, SavingsError> {
+    if !env
+        .storage()
+        .persistent()
+        .has(&StrategyKey::Info(strategy.clone()))
+    {
+        return Err(SavingsError::StrategyNotFound);
+    }
+
+    let principal = routing::get_total_principal(env, strategy);
+    if principal <= 0 {
+        return Ok(0);
+    }
+    let client = YieldStrategyClient::new(env, strategy);
+    let balance = client.strategy_balance(&env.current_contract_address());
+    if balance >= principal {
+        return Ok(0);
+    }
+    let loss = principal - balance.max(0);
+
+    let asset = registry::get_strategy_asset(env, strategy);
+    let covered = if get_reserve_cover(env) {
+        treasury::draw_reserve_in(env, &asset, loss)
+    } else {
+        0
+    };
+    let written_off = loss - covered;
+
+    // --- EFFECTS ---
+    if written_off > 0 {
+        let total_shares = routing::get_total_shares(env, strategy);
+        let index = get_loss_index(env, strategy)
+            .checked_add(
+                written_off
+                    .checked_mul(YIELD_INDEX_SCALE)
+                    .ok_or(SavingsError::Overflow)?
+                    / total_shares.max(1),
+            )
+            .ok_or(SavingsError::Overflow)?;
+        write_i128(env, &LossKey::Index(strategy.clone()), index);
+        write_i128(
+            env,
+            &LossKey::Unabsorbed(strategy.clone()),
+            get_unabsorbed(env, strategy)
+                .checked_add(written_off)
+                .ok_or(SavingsError::Overflow)?,
+        );
+    }
+
+    let mut perf = routing::load_performance(env, strategy);
+    perf.total_losses = perf.total_losses.checked_add(loss).unwrap_or(i128::MAX);
+    perf.total_loss_covered = perf
+        .total_loss_covered
+        .checked_add(covered)
+        .unwrap_or(i128::MAX);
+    if perf.losses.len() >= MAX_LOSS_HISTORY {
+        perf.losses.pop_front();
+    }
+    perf.losses.push_back(StrategyLoss {
+        amount: loss,
+        covered,
+        timestamp: env.ledger().timestamp(),
+    });
+    routing::save_performance(env, strategy, &perf);
+
+    // --- INTERACTIONS ---
+    if covered > 0 {
+        custody::send(env, &asset, strategy, covered)?;
+        let shares = client.strategy_deposit(&env.current_contract_address(), &covered);
+        if shares <= 0 {
+            return Err(SavingsError::InvalidStrategyResponse);
+        }
+        routing::add_cover_shares(env, strategy, shares)?;
+    }
+
+    env.events().publish(
+        (Symbol::new(env, "strat_loss"),),
+        (strategy.clone(), loss, covered, written_off),
+    );
+
+    Ok(loss)
+}
@@ -0,0 +1,409 @@
+use crate::errors::SavingsError;
+use crate::storage_types::{DataKey, LegacyStrategyPerformance, StrategyLoss};
+use crate::strategy::loss::{self, MAX_LOSS_HISTORY};
+use crate::strategy::migration_tests::{LeakyStrategy, LeakyStrategyClient};
+use crate::strategy::routing;
+use crate::treasury;
+use crate::{NesteraContract, NesteraContractClient};
+use soroban_sdk::{
+    testutils::Address as _, testutils::Ledger, token::StellarAssetClient, Address, BytesN, Env,
+    String,
+};
+
+struct Setup {
+    env: Env,
+    client: NesteraContractClient<'static>,
+    admin: Address,
+    owner: Address,
+    strategy: Address,
+}
+
+/// One registered strategy and a saver with a lock per amount routed to it.
+fn setup(amounts: &[i128]) -> (Setup, soroban_sdk::Vec<u64>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
+
+    let strategy = env.register(LeakyStrategy, ());
+    client.register_strategy(&admin, &strategy, &1);
+
+    let owner = Address::generate(&env);
+    client.init_user(&owner);
+    let mut lock_ids = soroban_sdk::Vec::new(&env);
+    for amount in amounts {
+        let lock_id = client.create_lock_save(&owner, amount, &3600);
+        client.route_lock_to_strategy(&owner, &lock_id, &strategy, amount);
+        lock_ids.push_back(lock_id);
+    }
+
+    (
+        Setup {
+            env,
+            client,
+            admin,
+            owner,
+            strategy,
+        },
+        lock_ids,
+    )
+}
+
+impl Setup {
+    fn lose(&self, amount: i128) {
+        LeakyStrategyClient::new(&self.env, &self.strategy).lose(&amount);
+    }
+
+    fn principal_of(&self, lock_id: u64) -> i128 {
+        self.client
+            .get_lock_strategy_value(&lock_id, &self.strategy)
+            .unwrap()
+            .principal_deposited
+    }
+
+    fn withdraw(&self, lock_id: u64) -> i128 {
+        self.client
+            .withdraw_lock_strategy(&self.owner, &lock_id, &self.strategy)
+    }
+
+    fn total_principal(&self) -> i128 {
+        self.env.as_contract(&self.client.address, || {
+            routing::get_total_principal(&self.env, &self.strategy)
+        })
+    }
+
+    /// Recorded principal and written-off principal not absorbed yet.
+    fn principal_records(&self) -> (i128, i128) {
+        self.env.as_contract(&self.client.address, || {
+            let recorded: i128 = self
+                .env
+                .storage()
+                .persistent()
+                .get(&DataKey::StrategyTotalPrincipal(self.strategy.clone()))
+                .unwrap_or(0);
+            (recorded, loss::get_unabsorbed(&self.env, &self.strategy))
+        })
+    }
+
+    fn fund_reserve(&self, amount: i128) {
+        self.env.as_contract(&self.client.address, || {
+            let mut treasury = treasury::get_treasury(&self.env);
+            treasury.reserve_balance = amount;
+            self.env
+                .storage()
+                .persistent()
+                .set(&DataKey::Treasury, &treasury);
+        });
+    }
+}
+
+#[test]
+fn test_loss_is_written_off_pro_rata() {
+    let (s, locks) = setup(&[1_000, 3_000]);
+    s.lose(400);
+
+    assert_eq!(s.client.realize_strategy_loss(&s.admin, &s.strategy), 400);
+    assert_eq!(s.principal_of(locks.get(0).unwrap()), 900);
+    assert_eq!(s.principal_of(locks.get(1).unwrap()), 2_700);
+    assert_eq!(s.total_principal(), 3_600);
+
+    let perf = s.client.get_strategy_performance(&s.strategy);
+    assert_eq!(perf.total_losses, 400);
+    assert_eq!(perf.total_loss_covered, 0);
+    assert_eq!(
+        perf.losses,
+        soroban_sdk::vec![
+            &s.env,
+            StrategyLoss {
+                amount: 400,
+                covered: 0,
+                timestamp: s.env.ledger().timestamp(),
+            }
+        ]
+    );
+
+    // Once written off there is nothing left to realize
+    assert_eq!(s.client.realize_strategy_loss(&s.admin, &s.strategy), 0);
+    assert_eq!(
+        s.client.get_strategy_performance(&s.strategy).losses.len(),
+        1
+    );
+
+    // A position gives up its part when it next changes
+    assert_eq!(s.withdraw(locks.get(0).unwrap()), 900);
+    assert_eq!(s.principal_records(), (3_000, 300));
+    assert_eq!(s.total_principal(), 2_700);
+}
+
+#[test]
+fn test_reserve_covers_loss_before_positions() {
+    let (s, locks) = setup(&[1_000, 3_000]);
+    s.fund_reserve(150);
+    s.client.set_loss_reserve_cover(&s.admin, &true);
+    s.lose(400);
+
+    assert_eq!(s.client.realize_strategy_loss(&s.admin, &s.strategy), 400);
+    assert_eq!(s.client.get_reserve_balance(), 0);
+
+    // 250 written off pro rata, each part rounded down
+    assert_eq!(s.principal_of(locks.get(0).unwrap()), 938);
+    assert_eq!(s.principal_of(locks.get(1).unwrap()), 2_813);
+    assert_eq!(s.total_principal(), 3_750);
+    assert_eq!(
+        LeakyStrategyClient::new(&s.env, &s.strategy).strategy_balance(&s.client.address),
+        3_750
+    );
+
+    let perf = s.client.get_strategy_performance(&s.strategy);
+    assert_eq!(perf.total_losses, 400);
+    assert_eq!(perf.total_loss_covered, 150);
+
+    // The shares the cover bought are released as holders redeem
+    let cover_shares = || {
+        s.env.as_contract(&s.client.address, || {
+            routing::get_cover_shares(&s.env, &s.strategy)
+        })
+    };
+    assert_eq!(cover_shares(), 150);
+    s.withdraw(locks.get(0).unwrap());
+    assert_eq!(cover_shares(), 113);
+    s.withdraw(locks.get(1).unwrap());
+    assert_eq!(cover_shares(), 0);
+}
+
+#[test]
+fn test_reserve_does_not_cover_other_asset_strategies() {
+    let (s, _locks) = setup(&[]);
+    let asset = s
+        .env
+        .register_stellar_asset_contract_v2(s.admin.clone())
+        .address();
+    StellarAssetClient::new(&s.env, &asset).mint(&s.owner, &1_000);
+    s.client.add_supported_asset(&s.admin, &asset);
+    s.client.set_lock_rate_for(&s.admin, &asset, &0, &500);
+    let strategy = s.env.register(LeakyStrategy, ());
+    s.client
+        .register_asset_strategy(&s.admin, &strategy, &1, &asset);
+    let lock_id = s
+        .client
+        .create_asset_lock_save(&s.owner, &asset, &1_000, &3600);
+    s.client
+        .route_lock_to_strategy(&s.owner, &lock_id, &strategy, &1_000);
+
+    s.fund_reserve(500);
+    s.client.set_loss_reserve_cover(&s.admin, &true);
+    LeakyStrategyClient::new(&s.env, &strategy).lose(&100);
+
+    assert_eq!(s.client.realize_strategy_loss(&s.admin, &strategy), 100);
+    assert_eq!(s.client.get_reserve_balance(), 500);
+    let perf = s.client.get_strategy_performance(&strategy);
+    assert_eq!(perf.total_loss_covered, 0);
+}
+
+#[test]
+fn test_reserve_covering_whole_loss_leaves_positions_intact() {
+    let (s, locks) = setup(&[1_000]);
+    s.fund_reserve(500);
+    s.client.set_loss_reserve_cover(&s.admin, &true);
+    s.lose(100);
+
+    s.client.realize_strategy_loss(&s.admin, &s.strategy);
+    assert_eq!(s.client.get_reserve_balance(), 400);
+    assert_eq!(s.principal_of(locks.get(0).unwrap()), 1_000);
+    assert_eq!(s.withdraw(locks.get(0).unwrap()), 1_000);
+}
+
+#[test]
+fn test_dip_is_borne_by_withdrawals_without_write_off() {
+    let (s, locks) = setup(&[1_000, 1_000]);
+    s.lose(200);
+
+    assert_eq!(s.withdraw(locks.get(0).unwrap()), 900);
+    assert_eq!(s.principal_of(locks.get(1).unwrap()), 1_000);
+    assert_eq!(s.total_principal(), 1_000);
+    assert_eq!(
+        s.client.get_strategy_performance(&s.strategy).total_losses,
+        0
+    );
+}
+
+#[test]
+fn test_recovered_dip_costs_nothing() {
+    let (s, locks) = setup(&[1_000, 3_000]);
+    s.lose(400);
+
+    // Harvesting during the dip neither pays out nor writes anything off
+    assert_eq!(s.client.harvest_strategy(&s.admin, &s.strategy), 0);
+    assert_eq!(s.principal_of(locks.get(0).unwrap()), 1_000);
+
+    s.lose(-400);
+    assert_eq!(s.withdraw(locks.get(0).unwrap()), 1_000);
+    assert_eq!(s.withdraw(locks.get(1).unwrap()), 3_000);
+    let perf = s.client.get_strategy_performance(&s.strategy);
+    assert_eq!(perf.total_losses, 0);
+    assert!(perf.losses.is_empty());
+}
+
+#[test]
+fn test_written_off_principal_reconciles() {
+    let (s, locks) = setup(&[1_000, 1_000, 1_000]);
+    s.lose(100);
+    s.client.realize_strategy_loss(&s.admin, &s.strategy);
+
+    // Each part is rounded down; the rest stays unabsorbed
+    assert_eq!(s.principal_of(locks.get(0).unwrap()), 967);
+    assert_eq!(s.principal_records(), (3_000, 100));
+    assert_eq!(s.total_principal(), 2_900);
+
+    assert_eq!(s.withdraw(locks.get(0).unwrap()), 966);
+    assert_eq!(s.principal_records(), (2_000, 67));
+    assert_eq!(s.total_principal(), 1_933);
+    s.withdraw(locks.get(1).unwrap());
+    s.withdraw(locks.get(2).unwrap());
+    assert_eq!(s.principal_records(), (0, 0));
+}
+
+#[test]
+fn test_loss_history_keeps_latest_entries() {
+    let (s, _locks) = setup(&[1_000]);
+    for i in 0..(MAX_LOSS_HISTORY + 2) {
+        s.lose(i as i128 + 1);
+        s.client.realize_strategy_loss(&s.admin, &s.strategy);
+    }
+
+    let perf = s.client.get_strategy_performance(&s.strategy);
+    assert_eq!(perf.losses.len(), MAX_LOSS_HISTORY);
+    assert_eq!(perf.losses.get(0).unwrap().amount, 3);
+    assert_eq!(
+        perf.losses.last().unwrap().amount,
+        MAX_LOSS_HISTORY as i128 + 2
+    );
+    let n = MAX_LOSS_HISTORY as i128 + 2;
+    assert_eq!(perf.total_losses, n * (n + 1) / 2);
+}
+
+#[test]
+fn test_only_admin_sets_reserve_cover() {
+    let (s, _locks) = setup(&[]);
+    assert!(!s.client.get_loss_reserve_cover());
+
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_set_loss_reserve_cover(&stranger, &true),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    s.client.set_loss_reserve_cover(&s.admin, &true);
+    assert!(s.client.get_loss_reserve_cover());
+
+    assert_eq!(
+        s.client
+            .try_realize_strategy_loss(&s.admin, &Address::generate(&s.env)),
+        Err(Ok(SavingsError::StrategyNotFound))
+    );
+}
+
+#[test]
+fn test_only_admin_realizes_loss() {
+    let (s, locks) = setup(&[1_000]);
+    s.lose(100);
+
+    assert_eq!(
+        s.client.try_realize_strategy_loss(&s.owner, &s.strategy),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(s.principal_of(locks.get(0).unwrap()), 1_000);
+}
+
+#[test]
+fn test_redemption_returns_to_the_lock_net_of_its_loss() {
+    let (s, locks) = setup(&[1_000, 1_000]);
+    let lock_id = locks.get(0).unwrap();
+    s.lose(200);
+
+    // Funds still in the strategy can't be paid out of custody
+    s.env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert!(s.client.try_withdraw_lock_save(&s.owner, &lock_id).is_err());
+
+    assert_eq!(s.withdraw(lock_id), 900);
+    assert_eq!(s.client.get_lock_save_detail(&lock_id).amount, 900);
+    let paid = s.client.withdraw_lock_save(&s.owner, &lock_id);
+    assert!((900..1_000).contains(&paid));
+}
+
+#[test]
+fn test_group_redemption_stays_in_the_pool() {
+    let (s, _locks) = setup(&[]);
+    let member = Address::generate(&s.env);
+    s.client.init_user(&member);
+    let now = s.env.ledger().timestamp();
+    let group_id = s.client.create_group_save(
+        &s.owner,
+        &String::from_str(&s.env, "Pool"),
+        &String::from_str(&s.env, "Shared savings"),
+        &String::from_str(&s.env, "general"),
+        &100_000,
+        &1u32,
+        &100,
+        &true,
+        &now,
+        &(now + 86400),
+    );
+    s.client.join_group_save(&member, &group_id);
+    s.client
+        .contribute_to_group_save(&s.owner, &group_id, &1_000);
+    s.client
+        .contribute_to_group_save(&member, &group_id, &3_000);
+    s.client
+        .route_group_to_strategy(&s.owner, &group_id, &s.strategy, &4_000);
+    s.lose(400);
+
+    assert_eq!(
+        s.client.try_break_group_save(&member, &group_id),
+        Err(Ok(SavingsError::InvalidPlanConfig))
+    );
+
+    assert_eq!(
+        s.client
+            .withdraw_group_strategy(&s.owner, &group_id, &s.strategy),
+        3_600
+    );
+    assert_eq!(s.client.get_group_save(&group_id).current_amount, 3_600);
+
+    // Contributions bear the loss pro rata
+    s.client.break_group_save(&member, &group_id);
+    assert_eq!(s.client.get_group_save(&group_id).current_amount, 900);
+}
+
+#[test]
+fn test_performance_recorded_before_loss_tracking_is_kept() {
+    let (s, _) = setup(&[1_000]);
+    // Stored in the shape used before losses were tracked
+    s.env.as_contract(&s.client.address, || {
+        let legacy = LegacyStrategyPerformance {
+            total_deposited: 1_000,
+            total_withdrawn: 0,
+            total_harvested: 50,
+            apy_estimate_bps: 500,
+        };
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::StrategyPerformance(s.strategy.clone()), &legacy);
+    });
+
+    let perf = s.client.get_strategy_performance(&s.strategy);
+    assert_eq!(perf.total_harvested, 50);
+    assert_eq!(perf.total_losses, 0);
+    assert_eq!(perf.losses.len(), 0);
+
+    s.lose(400);
+    assert_eq!(s.client.realize_strategy_loss(&s.admin, &s.strategy), 400);
+    let perf = s.client.get_strategy_performance(&s.strategy);
+    assert_eq!(perf.total_deposited, 1_000);
+    assert_eq!(perf.apy_estimate_bps, 500);
+    assert_eq!(perf.total_losses, 400);
+}
@@ -79,6 +79,7 @@ mod tests {
         let admin = Address::generate(env);
         let admin_pk = BytesN::from_array(env, &[1u8; 32]);
         nestera_client.initialize(&admin, &admin_pk);
+        nestera_client.set_lock_rate(&admin, &0, &500);
 
         let malicious_id = env.register(MaliciousStrategy, ());
         env.as_contract(&malicious_id, || {
@@ -121,7 +122,7 @@ mod tests {
         client.route_lock_to_strategy(&user, &lock_id, &malicious_id, &500);
 
         // This will call strategy_withdraw which will try to re-enter
-        let result = client.try_withdraw_lock_strategy(&user, &lock_id, &user);
+        let result = client.try_withdraw_lock_strategy(&user, &lock_id, &malicious_id);
         assert!(result.is_ok());
     }
 
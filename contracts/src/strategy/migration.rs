@@ -0,0 +1,416 @@
+use crate::custody;
+use crate::errors::SavingsError;
+use crate::governance;
+use crate::storage_types::DataKey;
+use crate::strategy::allocator;
+use crate::strategy::interface::YieldStrategyClient;
+use crate::strategy::loss;
+use crate::strategy::registry;
+use crate::strategy::routing::{self, StrategyPosition, StrategyPositionKey, StrategyShareKey};
+use crate::ttl;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+/// Most positions moved by a single `migrate_strategy` call.
+pub const MAX_MIGRATION_BATCH: u32 = 20;
+
+/// Progress of moving every position out of one strategy into another.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyMigration {
+    pub from: Address,
+    pub to: Address,
+    /// Largest tolerated shortfall (basis points) of `from`'s balance against
+    /// its recorded principal, and of the amount it returns against the
+    /// amount requested
+    pub max_loss_bps: u32,
+    /// Positions moved so far
+    pub positions_migrated: u32,
+    /// Total received back from `from` so far
+    pub amount_withdrawn: i128,
+    /// Total redeposited into `to` so far
+    pub amount_redeposited: i128,
+    pub started_at: u64,
+    /// Set once `from` has no positions left
+    pub completed: bool,
+}
+
+/// Storage keys for strategy migrations.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MigrationKey {
+    /// Latest migration out of a strategy
+    Migration(Address),
+}
+
+/// A position being moved in the current batch: its plan, its value in
+/// `from`, the harvested yield it carries over and its principal in `from`.
+type MovingPosition = (StrategyPositionKey, i128, i128, i128);
+
+/// Returns the latest migration out of `from`, if any.
+pub fn get_migration(env: &Env, from: &Address) -> Option<StrategyMigration> {
+    env.storage()
+        .persistent()
+        .get(&MigrationKey::Migration(from.clone()))
+}
+
+/// Moves up to `batch_size` positions from one strategy to another.
+///
+/// `from` must be disabled first so no new positions join it mid-migration.
+/// The first call starts a migration; later calls with the same `from` and
+/// `to` resume it until `from` holds no positions. Each batch harvests `from`,
+/// withdraws the positions' combined value and redeposits what comes back
+/// into `to`, minting each position its pro-rata part. Profit is paid out as
+/// harvested yield rather than carried as principal, and unclaimed harvested
+/// yield moves with the position.
+///
+/// A batch is refused if `from`'s balance is short of its
+/// `StrategyTotalPrincipal` by more than `max_loss_bps`, or if the strategy
+/// returns less than requested by more than that.
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+/// * `InvalidAmount` - If the batch size is 0 or above `MAX_MIGRATION_BATCH`,
+///   or the tolerance exceeds 10,000 bps
+/// * `InvalidPlanConfig` - If `from == to`, `from` is still enabled, or a
+///   migration out of `from` to another strategy is still in progress
+/// * `StrategyNotFound` - If either strategy is not registered
+/// * `StrategyDisabled` - If `to` is disabled
+/// * `UnsupportedAsset` - If `to` takes another asset than `from`
+/// * `AmountExceedsLimit` - If a loss exceeds the tolerance, or `to` would
+///   exceed its deposit cap or its share of total value locked
+pub fn migrate_strategy(
+    env: &Env,
+    caller: Address,
+    from: Address,
+    to: Address,
+    max_loss_bps: u32,
+    batch_size: u32,
+) -> Result<StrategyMigration, SavingsError> {
+    caller.require_auth();
+    governance::validate_admin_or_governance(env, &caller)?;
+
+    if batch_size == 0 || batch_size > MAX_MIGRATION_BATCH || max_loss_bps > 10_000 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if from == to {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    if registry::get_strategy(env, from.clone())?.enabled {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    if !registry::get_strategy(env, to.clone())?.enabled {
+        return Err(SavingsError::StrategyDisabled);
+    }
+    if registry::get_strategy_asset(env, &from) != registry::get_strategy_asset(env, &to) {
+        return Err(SavingsError::UnsupportedAsset);
+    }
+
+    let mut migration = match get_migration(env, &from) {
+        Some(existing) if !existing.completed => {
+            if existing.to != to {
+                return Err(SavingsError::InvalidPlanConfig);
+            }
+            existing
+        }
+        _ => StrategyMigration {
+            from: from.clone(),
+            to: to.clone(),
+            max_loss_bps,
+            positions_migrated: 0,
+            amount_withdrawn: 0,
+            amount_redeposited: 0,
+            started_at: env.ledger().timestamp(),
+            completed: false,
+        },
+    };
+    migration.max_loss_bps = max_loss_bps;
+
+    // Moved positions leave the holder list, so each batch starts at slot 0
+    let batch = routing::get_holders(env, &from, 0, batch_size);
+
+    if !batch.is_empty() {
+        let (moving, withdrawn) = withdraw_batch(env, &from, &batch, max_loss_bps)?;
+        let redeposited = redeposit_batch(env, &to, &moving, withdrawn)?;
+
+        migration.positions_migrated += batch.len();
+        migration.amount_withdrawn = migration
+            .amount_withdrawn
+            .checked_add(withdrawn)
+            .ok_or(SavingsError::Overflow)?;
+        migration.amount_redeposited = migration
+            .amount_redeposited
+            .checked_add(redeposited)
+            .ok_or(SavingsError::Overflow)?;
+    }
+    migration.completed = routing::get_holder_count(env, &from) == 0;
+
+    let key = MigrationKey::Migration(from.clone());
+    env.storage().persistent().set(&key, &migration);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    env.events().publish(
+        (symbol_short!("strat"), symbol_short!("migrate")),
+        (from, to, batch.len(), migration.completed),
+    );
+
+    Ok(migration)
+}
+
+/// Returns true if `actual` falls short of `expected` by at most `max_loss_bps`.
+fn within_tolerance(actual: i128, expected: i128, max_loss_bps: u32) -> Result<bool, SavingsError> {
+    if actual >= expected {
+        return Ok(true);
+    }
+    let floor = expected
+        .checked_mul((10_000 - max_loss_bps) as i128)
+        .ok_or(SavingsError::Overflow)?
+        / 10_000;
+    Ok(actual >= floor)
+}
+
+/// Burns the batch's shares in `from` and withdraws their combined value into
+/// the contract.
+///
+/// # Returns
+/// The positions being moved and the amount `from` returned.
+fn withdraw_batch(
+    env: &Env,
+    from: &Address,
+    batch: &Vec<StrategyPositionKey>,
+    max_loss_bps: u32,
+) -> Result<(Vec<MovingPosition>, i128), SavingsError> {
+    let nestera = env.current_contract_address();
+    let client = YieldStrategyClient::new(env, from);
+
+    // Credit profit to the positions as yield so only principal moves on
+    routing::settle_profit(env, from)?;
+    let balance = client.strategy_balance(&nestera);
+    let total_principal = routing::get_total_principal(env, from);
+    if !within_tolerance(balance, total_principal, max_loss_bps)? {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+    let total_shares = routing::get_total_shares(env, from);
+    let share_assets = routing::share_assets(env, from);
+
+    let mut moving: Vec<MovingPosition> = Vec::new(env);
+    let mut value: i128 = 0;
+    let mut principal: i128 = 0;
+    let mut burned: i128 = 0;
+    for position_key in batch.iter() {
+        let mut position = routing::get_position(env, position_key.clone(), from.clone())
+            .ok_or(SavingsError::DataCorruption)?;
+        loss::absorb_loss(env, &position_key, &mut position)?;
+        routing::accrue_position(env, &mut position)?;
+
+        let position_value =
+            routing::shares_to_assets(position.shares, total_shares, share_assets)?;
+        value = value
+            .checked_add(position_value)
+            .ok_or(SavingsError::Overflow)?;
+        principal = principal
+            .checked_add(position.principal_deposited)
+            .ok_or(SavingsError::Overflow)?;
+        burned = burned
+            .checked_add(position.shares)
+            .ok_or(SavingsError::Overflow)?;
+        moving.push_back((
+            position_key.clone(),
+            position_value,
+            position.pending_yield,
+            position.principal_deposited,
+        ));
+
+        // Emptied positions drop out of `from`'s holder list
+        let emptied = StrategyPosition {
+            principal_deposited: 0,
+            strategy_shares: 0,
+            shares: 0,
+            pending_yield: 0,
+            ..position
+        };
+        routing::save_position(env, &position_key, &emptied);
+    }
+
+    routing::release_cover_shares(env, from, burned, total_shares)?;
+    let remaining_shares = total_shares
+        .checked_sub(burned)
+        .ok_or(SavingsError::Underflow)?;
+    routing::write_i128(
+        env,
+        &StrategyShareKey::TotalShares(from.clone()),
+        remaining_shares,
+    );
+    let principal_key = DataKey::StrategyTotalPrincipal(from.clone());
+    let recorded: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
+    if remaining_shares == 0 {
+        env.storage().persistent().set(&principal_key, &0_i128);
+        loss::clear_unabsorbed(env, from);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&principal_key, &(recorded - principal).max(0));
+    }
+
+    if value == 0 {
+        return Ok((moving, 0));
+    }
+
+    let mut perf = routing::load_performance(env, from);
+    perf.total_withdrawn = perf.total_withdrawn.checked_add(value).unwrap_or(i128::MAX);
+    routing::save_performance(env, from, &perf);
+
+    let returned = client.strategy_withdraw(&nestera, &value);
+    if returned <= 0 {
+        return Err(SavingsError::InvalidStrategyResponse);
+    }
+    if !within_tolerance(returned, value, max_loss_bps)? {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+
+    Ok((moving, returned))
+}
+
+/// Transfers `amount` to `to`, deposits it and credits each moving position with its
+/// part, in proportion to its value in the old strategy. Each plan's balance
+/// moves by the difference between its new and old principal.
+///
+/// # Returns
+/// The amount deposited into `to`.
+fn redeposit_batch(
+    env: &Env,
+    to: &Address,
+    moving: &Vec<MovingPosition>,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    let nestera = env.current_contract_address();
+    let client = YieldStrategyClient::new(env, to);
+
+    // Same limits as routing; `from`'s principal is already out of the TVL
+    let info = registry::get_strategy(env, to.clone())?;
+    let tvl_after = allocator::total_value_locked(env)
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    if amount > allocator::strategy_room(env, &info, tvl_after)? {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+
+    // Price new shares against the assets backing them before this deposit
+    routing::settle_profit(env, to)?;
+    let total_shares = routing::get_total_shares(env, to);
+    let total_assets = if total_shares > 0 {
+        routing::share_assets(env, to)
+    } else {
+        0
+    };
+
+    let strategy_shares = if amount > 0 {
+        custody::send(env, &registry::get_strategy_asset(env, to), to, amount)?;
+        let shares = client.strategy_deposit(&nestera, &amount);
+        if shares <= 0 {
+            return Err(SavingsError::InvalidStrategyResponse);
+        }
+        shares
+    } else {
+        0
+    };
+
+    let batch_value = moving
+        .iter()
+        .fold(0i128, |total, (_, value, _, _)| total.saturating_add(value));
+    let mut assigned: i128 = 0;
+    let mut assigned_strategy_shares: i128 = 0;
+    let mut minted_total: i128 = 0;
+    for (i, (position_key, value, pending_yield, principal)) in moving.iter().enumerate() {
+        let last = i as u32 + 1 == moving.len();
+        let (deposit, position_strategy_shares) = if batch_value == 0 {
+            (0, 0)
+        } else if last {
+            (
+                amount - assigned,
+                strategy_shares - assigned_strategy_shares,
+            )
+        } else {
+            (
+                pro_rata(amount, value, batch_value)?,
+                pro_rata(strategy_shares, value, batch_value)?,
+            )
+        };
+        assigned += deposit;
+        assigned_strategy_shares += position_strategy_shares;
+
+        let minted = if deposit > 0 {
+            routing::assets_to_shares(deposit, total_shares, total_assets)?
+        } else {
+            0
+        };
+        minted_total = minted_total
+            .checked_add(minted)
+            .ok_or(SavingsError::Overflow)?;
+
+        let mut position = routing::get_position(env, position_key.clone(), to.clone()).unwrap_or(
+            StrategyPosition {
+                strategy: to.clone(),
+                principal_deposited: 0,
+                strategy_shares: 0,
+                shares: 0,
+                yield_index: routing::get_yield_index(env, to),
+                pending_yield: 0,
+            },
+        );
+        loss::absorb_loss(env, &position_key, &mut position)?;
+        routing::accrue_position(env, &mut position)?;
+        position.principal_deposited = position
+            .principal_deposited
+            .checked_add(deposit)
+            .ok_or(SavingsError::Overflow)?;
+        position.shares = position
+            .shares
+            .checked_add(minted)
+            .ok_or(SavingsError::Overflow)?;
+        position.strategy_shares = position
+            .strategy_shares
+            .checked_add(position_strategy_shares)
+            .ok_or(SavingsError::Overflow)?;
+        position.pending_yield = position
+            .pending_yield
+            .checked_add(pending_yield)
+            .ok_or(SavingsError::Overflow)?;
+        routing::save_position(env, &position_key, &position);
+        routing::adjust_plan_balance(env, &position_key, deposit - principal)?;
+    }
+
+    routing::write_i128(
+        env,
+        &StrategyShareKey::TotalShares(to.clone()),
+        total_shares
+            .checked_add(minted_total)
+            .ok_or(SavingsError::Overflow)?,
+    );
+    let principal_key = DataKey::StrategyTotalPrincipal(to.clone());
+    let recorded: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
+    env.storage().persistent().set(
+        &principal_key,
+        &recorded.checked_add(amount).ok_or(SavingsError::Overflow)?,
+    );
+    env.storage()
+        .persistent()
+        .extend_ttl(&principal_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    if amount > 0 {
+        let mut perf = routing::load_performance(env, to);
+        perf.total_deposited = perf
+            .total_deposited
+            .checked_add(amount)
+            .unwrap_or(i128::MAX);
+        routing::save_performance(env, to, &perf);
+    }
+
+    Ok(amount)
+}
+
+/// `amount * part / whole`, rounded down.
+fn pro_rata(amount: i128, part: i128, whole: i128) -> Result<i128, SavingsError> {
+    Ok(amount.checked_mul(part).ok_or(SavingsError::Overflow)? / whole)
+}
@@ -0,0 +1,352 @@
+use crate::errors::SavingsError;
+use crate::strategy::interface::YieldStrategy;
+use crate::strategy::routing::{self, StrategyPositionKey};
+use crate::{NesteraContract, NesteraContractClient};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, BytesN, Env, Symbol};
+
+/// Strategy holding deposits 1:1 that can be made to lose funds or to return
+/// less than asked on withdrawal.
+#[contract]
+pub struct LeakyStrategy;
+
+fn read(env: &Env, name: &str) -> i128 {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, name))
+        .unwrap_or(0)
+}
+
+fn write(env: &Env, name: &str, value: i128) {
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, name), &value);
+}
+
+#[contractimpl]
+impl LeakyStrategy {
+    pub fn lose(env: Env, amount: i128) {
+        write(&env, "held", read(&env, "held") - amount);
+    }
+
+    pub fn add_yield(env: Env, amount: i128) {
+        write(&env, "yield", read(&env, "yield") + amount);
+    }
+
+    /// Share (bps) withheld from every withdrawal
+    pub fn set_haircut(env: Env, bps: i128) {
+        write(&env, "haircut", bps);
+    }
+}
+
+#[contractimpl]
+impl YieldStrategy for LeakyStrategy {
+    fn strategy_deposit(env: Env, _from: Address, amount: i128) -> i128 {
+        write(&env, "held", read(&env, "held") + amount);
+        amount
+    }
+
+    fn strategy_withdraw(env: Env, _to: Address, amount: i128) -> i128 {
+        write(&env, "held", read(&env, "held") - amount);
+        amount - amount * read(&env, "haircut") / 10_000
+    }
+
+    fn strategy_harvest(env: Env, _to: Address) -> i128 {
+        let harvested = read(&env, "yield");
+        write(&env, "yield", 0);
+        harvested
+    }
+
+    fn strategy_balance(env: Env, _addr: Address) -> i128 {
+        read(&env, "held") + read(&env, "yield")
+    }
+}
+
+struct Setup {
+    env: Env,
+    client: NesteraContractClient<'static>,
+    admin: Address,
+    owner: Address,
+    from: Address,
+    to: Address,
+}
+
+/// Like `setup_enabled`, with `from` disabled ready for migration.
+fn setup(amounts: &[i128]) -> (Setup, soroban_sdk::Vec<u64>) {
+    let (s, lock_ids) = setup_enabled(amounts);
+    s.client.disable_strategy(&s.admin, &s.from);
+    (s, lock_ids)
+}
+
+/// Two registered strategies and a saver with a lock per amount routed to `from`.
+fn setup_enabled(amounts: &[i128]) -> (Setup, soroban_sdk::Vec<u64>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
+
+    let from = env.register(LeakyStrategy, ());
+    let to = env.register(LeakyStrategy, ());
+    client.register_strategy(&admin, &from, &1);
+    client.register_strategy(&admin, &to, &1);
+
+    let owner = Address::generate(&env);
+    client.init_user(&owner);
+    let mut lock_ids = soroban_sdk::Vec::new(&env);
+    for amount in amounts {
+        let lock_id = client.create_lock_save(&owner, amount, &3600);
+        client.route_lock_to_strategy(&owner, &lock_id, &from, amount);
+        lock_ids.push_back(lock_id);
+    }
+
+    (
+        Setup {
+            env,
+            client,
+            admin,
+            owner,
+            from,
+            to,
+        },
+        lock_ids,
+    )
+}
+
+fn total_principal(s: &Setup, strategy: &Address) -> i128 {
+    s.env.as_contract(&s.client.address, || {
+        routing::get_total_principal(&s.env, strategy)
+    })
+}
+
+#[test]
+fn test_migration_moves_positions_in_resumable_batches() {
+    let (s, locks) = setup(&[1_000, 2_000, 3_000]);
+
+    let progress = s.client.migrate_strategy(&s.admin, &s.from, &s.to, &0, &2);
+    assert_eq!(progress.positions_migrated, 2);
+    assert!(!progress.completed);
+    assert_eq!(progress.amount_withdrawn, 3_000);
+    assert_eq!(
+        s.client.get_strategy_migration(&s.from),
+        Some(progress.clone())
+    );
+
+    let done = s.client.migrate_strategy(&s.admin, &s.from, &s.to, &0, &2);
+    assert_eq!(done.positions_migrated, 3);
+    assert!(done.completed);
+    assert_eq!(done.amount_redeposited, 6_000);
+
+    for (lock_id, amount) in locks.iter().zip([1_000i128, 2_000, 3_000]) {
+        let positions = s.client.get_lock_strategy_positions(&lock_id);
+        assert_eq!(positions.len(), 1);
+        let position = positions.get(0).unwrap();
+        assert_eq!(position.strategy, s.to);
+        assert_eq!(position.principal_deposited, amount);
+        assert_eq!(position.shares, amount);
+    }
+    assert_eq!(total_principal(&s, &s.from), 0);
+    assert_eq!(total_principal(&s, &s.to), 6_000);
+}
+
+#[test]
+fn test_in_progress_migration_keeps_its_target() {
+    let (s, _locks) = setup(&[1_000, 2_000]);
+    s.client.migrate_strategy(&s.admin, &s.from, &s.to, &0, &1);
+
+    let other = s.env.register(LeakyStrategy, ());
+    s.client.register_strategy(&s.admin, &other, &1);
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &other, &0, &1),
+        Err(Ok(SavingsError::InvalidPlanConfig))
+    );
+}
+
+#[test]
+fn test_migration_refuses_loss_beyond_tolerance() {
+    let (s, locks) = setup(&[1_000]);
+    LeakyStrategyClient::new(&s.env, &s.from).lose(&100);
+
+    // 10% below StrategyTotalPrincipal
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &500, &5),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+
+    s.client
+        .migrate_strategy(&s.admin, &s.from, &s.to, &1_000, &5);
+    let position = s
+        .client
+        .get_lock_strategy_position(&locks.get(0).unwrap(), &s.to)
+        .unwrap();
+    assert_eq!(position.principal_deposited, 900);
+}
+
+#[test]
+fn test_migration_refuses_short_withdrawal() {
+    let (s, _locks) = setup(&[1_000]);
+    LeakyStrategyClient::new(&s.env, &s.from).set_haircut(&300);
+
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &200, &5),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+    let done = s
+        .client
+        .migrate_strategy(&s.admin, &s.from, &s.to, &300, &5);
+    assert_eq!(done.amount_withdrawn, 970);
+    assert_eq!(total_principal(&s, &s.to), 970);
+}
+
+#[test]
+fn test_migration_carries_unclaimed_yield_and_tops_up() {
+    let (s, _locks) = setup_enabled(&[]);
+    let lock_id = s.client.create_lock_save(&s.owner, &1_500, &3600);
+    s.client
+        .route_lock_to_strategy(&s.owner, &lock_id, &s.from, &1_000);
+    s.client
+        .route_lock_to_strategy(&s.owner, &lock_id, &s.to, &500);
+    s.client.disable_strategy(&s.admin, &s.from);
+
+    LeakyStrategyClient::new(&s.env, &s.from).add_yield(&200);
+    s.client.harvest_strategy(&s.admin, &s.from);
+
+    s.client.migrate_strategy(&s.admin, &s.from, &s.to, &0, &5);
+
+    let positions = s.client.get_lock_strategy_positions(&lock_id);
+    assert_eq!(positions.len(), 1);
+    let position = positions.get(0).unwrap();
+    assert_eq!(position.strategy, s.to);
+    assert_eq!(position.principal_deposited, 1_500);
+    assert_eq!(position.pending_yield, 200);
+    assert_eq!(
+        s.client
+            .claim_lock_strategy_yield(&s.owner, &lock_id, &s.owner),
+        200
+    );
+}
+
+#[test]
+fn test_migration_rejects_bad_parameters() {
+    let (s, _locks) = setup(&[1_000]);
+
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &0, &0),
+        Err(Ok(SavingsError::InvalidAmount))
+    );
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.from, &0, &1),
+        Err(Ok(SavingsError::InvalidPlanConfig))
+    );
+
+    s.client.disable_strategy(&s.admin, &s.to);
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &0, &1),
+        Err(Ok(SavingsError::StrategyDisabled))
+    );
+}
+
+#[test]
+fn test_migration_requires_disabled_source() {
+    let (s, locks) = setup_enabled(&[1_000]);
+
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &0, &5),
+        Err(Ok(SavingsError::InvalidPlanConfig))
+    );
+
+    s.client.disable_strategy(&s.admin, &s.from);
+    let done = s.client.migrate_strategy(&s.admin, &s.from, &s.to, &0, &5);
+    assert!(done.completed);
+    assert!(s
+        .client
+        .get_lock_strategy_position(&locks.get(0).unwrap(), &s.from)
+        .is_none());
+}
+
+#[test]
+fn test_migration_pays_unharvested_profit_as_yield() {
+    let (s, locks) = setup(&[1_000, 3_000]);
+    LeakyStrategyClient::new(&s.env, &s.from).add_yield(&400);
+
+    let done = s.client.migrate_strategy(&s.admin, &s.from, &s.to, &0, &5);
+    assert_eq!(done.amount_withdrawn, 4_000);
+
+    let first = s
+        .client
+        .get_lock_strategy_position(&locks.get(0).unwrap(), &s.to)
+        .unwrap();
+    assert_eq!(first.principal_deposited, 1_000);
+    assert_eq!(first.pending_yield, 100);
+    let second = s
+        .client
+        .get_lock_strategy_position(&locks.get(1).unwrap(), &s.to)
+        .unwrap();
+    assert_eq!(second.principal_deposited, 3_000);
+    assert_eq!(second.pending_yield, 300);
+    assert_eq!(total_principal(&s, &s.to), 4_000);
+}
+
+#[test]
+fn test_holders_are_paginated() {
+    let (s, locks) = setup_enabled(&[1_000, 2_000, 3_000]);
+    let holders = |start: u32, limit: u32| {
+        s.env.as_contract(&s.client.address, || {
+            routing::get_holders(&s.env, &s.from, start, limit)
+        })
+    };
+    assert_eq!(holders(0, 2).len(), 2);
+    assert_eq!(
+        holders(2, 2),
+        soroban_sdk::vec![&s.env, StrategyPositionKey::Lock(locks.get(2).unwrap())]
+    );
+
+    // Removing a holder moves the last one into its slot
+    s.client
+        .withdraw_lock_strategy(&s.owner, &locks.get(0).unwrap(), &s.from);
+    assert_eq!(
+        holders(0, 5),
+        soroban_sdk::vec![
+            &s.env,
+            StrategyPositionKey::Lock(locks.get(2).unwrap()),
+            StrategyPositionKey::Lock(locks.get(1).unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn test_migration_respects_target_limits() {
+    let (s, _locks) = setup(&[1_000]);
+
+    // Only `to` would hold principal once the batch moves
+    s.client.set_strategy_limits(&s.admin, &s.to, &0, &5_000);
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &0, &5),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+
+    s.client.set_strategy_limits(&s.admin, &s.to, &999, &10_000);
+    assert_eq!(
+        s.client
+            .try_migrate_strategy(&s.admin, &s.from, &s.to, &0, &5),
+        Err(Ok(SavingsError::AmountExceedsLimit))
+    );
+
+    s.client
+        .set_strategy_limits(&s.admin, &s.to, &1_000, &10_000);
+    assert!(
+        s.client
+            .migrate_strategy(&s.admin, &s.from, &s.to, &0, &5)
+            .completed
+    );
+}
@@ -1,12 +1,21 @@
+pub mod allocator;
 pub mod interface;
+pub mod loss;
+pub mod migration;
 pub mod registry;
 pub mod routing;
 
+#[cfg(test)]
+mod allocator_tests;
 #[cfg(test)]
 mod harvest_tests;
 #[cfg(test)]
+mod loss_tests;
+#[cfg(test)]
 mod malicious_tests;
 #[cfg(test)]
+mod migration_tests;
+#[cfg(test)]
 mod tests;
 #[cfg(test)]
 mod withdraw_tests;
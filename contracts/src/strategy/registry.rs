@@ -1,7 +1,9 @@
+use crate::assets;
 use crate::errors::SavingsError;
 use crate::governance;
+use crate::storage_types::decode_versioned;
 use crate::ttl;
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Val, Vec};
 
 /// Information about a registered yield strategy.
 #[contracttype]
@@ -13,6 +15,19 @@ pub struct StrategyInfo {
     pub enabled: bool,
     /// Risk level indicator (0 = lowest risk, 255 = highest risk)
     pub risk_level: u32,
+    /// Most principal the strategy may hold; 0 means uncapped
+    pub deposit_cap: i128,
+    /// Most of the protocol's routed principal (basis points) the strategy may hold
+    pub max_tvl_share_bps: u32,
+}
+
+/// Strategy information as stored before strategies had deposit limits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyStrategyInfo {
+    pub address: Address,
+    pub enabled: bool,
+    pub risk_level: u32,
 }
 
 /// Storage keys for the strategy registry.
@@ -23,6 +38,8 @@ pub enum StrategyKey {
     Info(Address),
     /// List of all registered strategy addresses
     AllStrategies,
+    /// Maps a strategy address to the asset it takes, when not the base asset
+    Asset(Address),
 }
 
 // ========== Admin / Governance Guard ==========
@@ -56,7 +73,46 @@ pub fn register_strategy(
     risk_level: u32,
 ) -> Result<(), SavingsError> {
     require_admin_or_governance(env, &caller)?;
+    store_strategy(env, strategy_address, risk_level)
+}
 
+/// Registers a new yield strategy that takes `asset` rather than the base
+/// asset.
+///
+/// Only plans in `asset` can route to it, and its withdrawals and harvests
+/// are paid in `asset`.
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+/// * `UnsupportedAsset` - If `asset` is not whitelisted
+/// * `StrategyAlreadyRegistered` - If the strategy address is already registered
+pub fn register_asset_strategy(
+    env: &Env,
+    caller: Address,
+    strategy_address: Address,
+    risk_level: u32,
+    asset: Address,
+) -> Result<(), SavingsError> {
+    require_admin_or_governance(env, &caller)?;
+    assets::require_supported(env, &asset)?;
+    store_strategy(env, strategy_address.clone(), risk_level)?;
+
+    if asset != assets::base_asset(env) {
+        let key = StrategyKey::Asset(strategy_address);
+        env.storage().persistent().set(&key, &asset);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    }
+    Ok(())
+}
+
+/// Registers a strategy; callers are responsible for authorization.
+pub(crate) fn store_strategy(
+    env: &Env,
+    strategy_address: Address,
+    risk_level: u32,
+) -> Result<(), SavingsError> {
     let info_key = StrategyKey::Info(strategy_address.clone());
 
     // Prevent duplicate registration
@@ -68,6 +124,8 @@ pub fn register_strategy(
         address: strategy_address.clone(),
         enabled: true,
         risk_level,
+        deposit_cap: 0,
+        max_tvl_share_bps: 10_000,
     };
 
     // Store strategy info
@@ -118,13 +176,16 @@ pub fn disable_strategy(
     strategy_address: Address,
 ) -> Result<(), SavingsError> {
     require_admin_or_governance(env, &caller)?;
+    mark_strategy_disabled(env, strategy_address)
+}
 
+/// Disables a strategy; callers are responsible for authorization.
+pub(crate) fn mark_strategy_disabled(
+    env: &Env,
+    strategy_address: Address,
+) -> Result<(), SavingsError> {
     let info_key = StrategyKey::Info(strategy_address.clone());
-    let mut info: StrategyInfo = env
-        .storage()
-        .persistent()
-        .get(&info_key)
-        .ok_or(SavingsError::StrategyNotFound)?;
+    let mut info = get_strategy(env, strategy_address.clone())?;
 
     info.enabled = false;
     env.storage().persistent().set(&info_key, &info);
@@ -141,20 +202,86 @@ pub fn disable_strategy(
     Ok(())
 }
 
+/// Sets the deposit cap and TVL share limit of a registered strategy.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin or governance caller
+/// * `strategy_address` - Address of the strategy
+/// * `deposit_cap` - Most principal the strategy may hold (0 = uncapped)
+/// * `max_tvl_share_bps` - Most of all routed principal the strategy may hold
+///
+/// # Errors
+/// * `Unauthorized` - If caller is not admin / governance is not active
+/// * `StrategyNotFound` - If the strategy is not registered
+/// * `InvalidAmount` - If the cap is negative or the share exceeds 10,000 bps
+pub fn set_strategy_limits(
+    env: &Env,
+    caller: Address,
+    strategy_address: Address,
+    deposit_cap: i128,
+    max_tvl_share_bps: u32,
+) -> Result<(), SavingsError> {
+    require_admin_or_governance(env, &caller)?;
+
+    if deposit_cap < 0 || max_tvl_share_bps > 10_000 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let info_key = StrategyKey::Info(strategy_address.clone());
+    let mut info = get_strategy(env, strategy_address.clone())?;
+
+    info.deposit_cap = deposit_cap;
+    info.max_tvl_share_bps = max_tvl_share_bps;
+    env.storage().persistent().set(&info_key, &info);
+    env.storage()
+        .persistent()
+        .extend_ttl(&info_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+
+    env.events().publish(
+        (symbol_short!("strat"), symbol_short!("limits")),
+        (strategy_address, deposit_cap, max_tvl_share_bps),
+    );
+
+    Ok(())
+}
+
 /// Retrieves information about a registered strategy.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `strategy_address` - Address of the strategy to query
 ///
+/// Strategies registered before deposit limits existed read as uncapped.
+///
 /// # Returns
 /// `Ok(StrategyInfo)` with the strategy metadata, or `Err(StrategyNotFound)`
 pub fn get_strategy(env: &Env, strategy_address: Address) -> Result<StrategyInfo, SavingsError> {
     let info_key = StrategyKey::Info(strategy_address);
-    env.storage()
+    let raw: Map<Symbol, Val> = env
+        .storage()
         .persistent()
         .get(&info_key)
-        .ok_or(SavingsError::StrategyNotFound)
+        .ok_or(SavingsError::StrategyNotFound)?;
+    decode_versioned(env, raw, "deposit_cap", |legacy: LegacyStrategyInfo| {
+        StrategyInfo {
+            address: legacy.address,
+            enabled: legacy.enabled,
+            risk_level: legacy.risk_level,
+            deposit_cap: 0,
+            max_tvl_share_bps: 10_000,
+        }
+    })
+    .ok_or(SavingsError::StrategyNotFound)
+}
+
+/// Returns the asset a strategy takes: the one it was registered for, or the
+/// base asset.
+pub fn get_strategy_asset(env: &Env, strategy_address: &Address) -> Address {
+    env.storage()
+        .persistent()
+        .get(&StrategyKey::Asset(strategy_address.clone()))
+        .unwrap_or_else(|| assets::base_asset(env))
 }
 
 /// Returns the list of all registered strategy addresses.
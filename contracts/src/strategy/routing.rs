@@ -1,12 +1,28 @@
+use crate::custody;
 use crate::errors::SavingsError;
 use crate::security::release_reentrancy_guard;
-use crate::storage_types::{DataKey, StrategyPerformance};
+use crate::storage_types::{
+    decode_versioned, DataKey, LegacyStrategyPerformance, StrategyPerformance,
+};
+use crate::strategy::allocator;
 use crate::strategy::interface::YieldStrategyClient;
+use crate::strategy::loss;
 use crate::strategy::registry::{self, StrategyKey};
 use crate::ttl;
-use soroban_sdk::{contracttype, symbol_short, Address, Env};
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Fixed-point scale of the per-share harvest index (1e18 = one unit of yield per share)
+pub const YIELD_INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
 
 /// Tracks a deposit routed to a yield strategy.
+///
+/// Every position in a strategy holds `shares` of Nestera's combined balance
+/// there, minted ERC-4626 style against the assets backing the shares (see
+/// `share_assets`). Profit is harvested before shares are minted or burned and
+/// attributed pro rata to shares through a per-strategy index, so it is never
+/// also carried in the share price.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StrategyPosition {
@@ -16,35 +32,108 @@ pub struct StrategyPosition {
     pub principal_deposited: i128,
     /// Shares received from the strategy
     pub strategy_shares: i128,
+    /// Nestera shares of the combined balance held in the strategy
+    pub shares: i128,
+    /// Strategy yield index at the position's last accrual
+    pub yield_index: i128,
+    /// Harvested yield attributed to the position and not yet claimed
+    pub pending_yield: i128,
+}
+
+/// Point-in-time value of a strategy position
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyPositionValue {
+    pub strategy: Address,
+    pub principal_deposited: i128,
+    pub shares: i128,
+    /// The position's share of the strategy's current balance
+    pub current_value: i128,
+    /// Harvested yield that can be claimed
+    pub pending_yield: i128,
+}
+
+/// Storage keys for share-based strategy accounting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StrategyShareKey {
+    /// Total Nestera shares outstanding for a strategy
+    TotalShares(Address),
+    /// Cumulative harvested user yield per share (scaled by `YIELD_INDEX_SCALE`)
+    YieldIndex(Address),
+    /// A plan's position in one strategy
+    Position(StrategyPositionKey, Address),
+    /// Strategies a plan currently holds positions in
+    Positions(StrategyPositionKey),
+    /// Number of plans holding a position in a strategy
+    HolderCount(Address),
+    /// Plan at a slot of a strategy's holder list
+    HolderAt(Address, u32),
+    /// Slot of a plan in a strategy's holder list
+    HolderSlot(Address, StrategyPositionKey),
+    /// Strategy shares bought by reserve loss cover, owned by all holders
+    /// pro rata and released as their shares are burned
+    CoverShares(Address),
+}
+
+/// Strategy position as stored before share accounting.
+///
+/// Earlier versions kept a single position per plan directly under the
+/// plan's `StrategyPositionKey`; see `migrate_legacy_position`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyStrategyPosition {
+    pub strategy: Address,
+    pub principal_deposited: i128,
+    pub strategy_shares: i128,
 }
 
-/// Storage key for strategy positions keyed by (plan_type_tag, plan_id).
+/// Identifies the plan that owns strategy positions.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum StrategyPositionKey {
-    /// Position for a LockSave plan
+    /// Positions of a LockSave plan
     Lock(u64),
-    /// Position for a GroupSave plan
+    /// Positions of a GroupSave plan
     Group(u64),
 }
 
 // ========== Performance Tracking Helpers ==========
 
 /// Loads the current performance record for a strategy (defaults to zero).
-fn load_performance(env: &Env, strategy: &Address) -> StrategyPerformance {
-    env.storage()
+///
+/// Records stored before losses were tracked read as having no losses.
+pub(crate) fn load_performance(env: &Env, strategy: &Address) -> StrategyPerformance {
+    let raw: Option<Map<Symbol, Val>> = env
+        .storage()
         .persistent()
-        .get(&DataKey::StrategyPerformance(strategy.clone()))
-        .unwrap_or(StrategyPerformance {
-            total_deposited: 0,
-            total_withdrawn: 0,
-            total_harvested: 0,
-            apy_estimate_bps: 0,
+        .get(&DataKey::StrategyPerformance(strategy.clone()));
+    raw.and_then(|raw| {
+        decode_versioned(env, raw, "losses", |legacy: LegacyStrategyPerformance| {
+            StrategyPerformance {
+                total_deposited: legacy.total_deposited,
+                total_withdrawn: legacy.total_withdrawn,
+                total_harvested: legacy.total_harvested,
+                apy_estimate_bps: legacy.apy_estimate_bps,
+                total_losses: 0,
+                total_loss_covered: 0,
+                losses: Vec::new(env),
+            }
         })
+    })
+    .unwrap_or(StrategyPerformance {
+        total_deposited: 0,
+        total_withdrawn: 0,
+        total_harvested: 0,
+        apy_estimate_bps: 0,
+        total_losses: 0,
+        total_loss_covered: 0,
+        losses: Vec::new(env),
+    })
 }
 
 /// Saves a performance record and extends its TTL.
-fn save_performance(env: &Env, strategy: &Address, perf: &StrategyPerformance) {
+pub(crate) fn save_performance(env: &Env, strategy: &Address, perf: &StrategyPerformance) {
     let key = DataKey::StrategyPerformance(strategy.clone());
     env.storage().persistent().set(&key, perf);
     env.storage()
@@ -70,17 +159,425 @@ fn compute_apy_bps(total_deposited: i128, total_harvested: i128) -> u32 {
     }
 }
 
+// ========== Share Accounting Helpers ==========
+
+fn read_i128(env: &Env, key: &StrategyShareKey) -> i128 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+pub(crate) fn write_i128(env: &Env, key: &StrategyShareKey, value: i128) {
+    env.storage().persistent().set(key, &value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
+
+/// Total Nestera shares outstanding for a strategy.
+pub fn get_total_shares(env: &Env, strategy: &Address) -> i128 {
+    read_i128(env, &StrategyShareKey::TotalShares(strategy.clone()))
+}
+
+/// Strategy shares bought by reserve loss cover not yet released to holders.
+pub fn get_cover_shares(env: &Env, strategy: &Address) -> i128 {
+    read_i128(env, &StrategyShareKey::CoverShares(strategy.clone()))
+}
+
+/// Adds strategy shares bought by reserve loss cover to the holders' pool.
+pub(crate) fn add_cover_shares(
+    env: &Env,
+    strategy: &Address,
+    shares: i128,
+) -> Result<(), SavingsError> {
+    let total = get_cover_shares(env, strategy)
+        .checked_add(shares)
+        .ok_or(SavingsError::Overflow)?;
+    write_i128(env, &StrategyShareKey::CoverShares(strategy.clone()), total);
+    Ok(())
+}
+
+/// Releases the part of the cover shares backing `burned` out of
+/// `total_shares` Nestera shares; all of it once no shares remain.
+///
+/// # Returns
+/// The strategy shares released.
+pub(crate) fn release_cover_shares(
+    env: &Env,
+    strategy: &Address,
+    burned: i128,
+    total_shares: i128,
+) -> Result<i128, SavingsError> {
+    let cover = get_cover_shares(env, strategy);
+    if cover == 0 || burned <= 0 {
+        return Ok(0);
+    }
+    let released = if burned >= total_shares {
+        cover
+    } else {
+        cover.checked_mul(burned).ok_or(SavingsError::Overflow)? / total_shares
+    };
+    write_i128(
+        env,
+        &StrategyShareKey::CoverShares(strategy.clone()),
+        cover - released,
+    );
+    Ok(released)
+}
+
+/// Principal currently routed to a strategy, net of realized losses its
+/// positions have not absorbed yet.
+pub fn get_total_principal(env: &Env, strategy: &Address) -> i128 {
+    let recorded: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::StrategyTotalPrincipal(strategy.clone()))
+        .unwrap_or(0);
+    (recorded - loss::get_unabsorbed(env, strategy)).max(0)
+}
+
+/// Cumulative harvested user yield per share for a strategy.
+pub fn get_yield_index(env: &Env, strategy: &Address) -> i128 {
+    read_i128(env, &StrategyShareKey::YieldIndex(strategy.clone()))
+}
+
+/// Moves yield earned since the position's last checkpoint into `pending_yield`.
+pub(crate) fn accrue_position(
+    env: &Env,
+    position: &mut StrategyPosition,
+) -> Result<(), SavingsError> {
+    let index = get_yield_index(env, &position.strategy);
+    let delta = index
+        .checked_sub(position.yield_index)
+        .ok_or(SavingsError::Underflow)?;
+    let earned = position
+        .shares
+        .checked_mul(delta)
+        .ok_or(SavingsError::Overflow)?
+        / YIELD_INDEX_SCALE;
+    position.pending_yield = position
+        .pending_yield
+        .checked_add(earned)
+        .ok_or(SavingsError::Overflow)?;
+    position.yield_index = index;
+    Ok(())
+}
+
+/// Converts `shares` to their part of `total_assets` (rounding down).
+pub(crate) fn shares_to_assets(
+    shares: i128,
+    total_shares: i128,
+    total_assets: i128,
+) -> Result<i128, SavingsError> {
+    if total_shares <= 0 || total_assets <= 0 {
+        return Ok(0);
+    }
+    Ok(shares
+        .checked_mul(total_assets)
+        .ok_or(SavingsError::Overflow)?
+        / total_shares)
+}
+
+/// Shares minted for depositing `amount` when the strategy holds `total_assets`
+/// for `total_shares`. The first deposit mints shares 1:1.
+pub(crate) fn assets_to_shares(
+    amount: i128,
+    total_shares: i128,
+    total_assets: i128,
+) -> Result<i128, SavingsError> {
+    if total_shares <= 0 || total_assets <= 0 {
+        return Ok(amount);
+    }
+    Ok(amount
+        .checked_mul(total_shares)
+        .ok_or(SavingsError::Overflow)?
+        / total_assets)
+}
+
+/// Assets backing a strategy's shares: its balance, excluding profit not yet
+/// harvested. Profit reaches positions only through the yield index, so
+/// shares are priced and redeemed at no more than the recorded principal.
+pub(crate) fn share_assets(env: &Env, strategy: &Address) -> i128 {
+    let client = YieldStrategyClient::new(env, strategy);
+    client
+        .strategy_balance(&env.current_contract_address())
+        .min(get_total_principal(env, strategy))
+}
+
+/// Harvests a strategy's outstanding profit so it is credited to the shares
+/// that earned it before any shares are minted or burned.
+pub(crate) fn settle_profit(env: &Env, strategy: &Address) -> Result<(), SavingsError> {
+    let client = YieldStrategyClient::new(env, strategy);
+    let balance = client.strategy_balance(&env.current_contract_address());
+    if balance > get_total_principal(env, strategy) && get_total_shares(env, strategy) > 0 {
+        harvest_strategy(env, strategy.clone())?;
+    }
+    Ok(())
+}
+
 /// Returns the performance metrics for a strategy.
 pub fn get_strategy_performance(env: &Env, strategy_address: Address) -> StrategyPerformance {
     load_performance(env, &strategy_address)
 }
 
+// ========== Position Storage ==========
+
+/// Retrieves a plan's position in `strategy`, if any.
+///
+/// Falls back to a position stored by earlier versions that has not been
+/// migrated yet.
+pub fn get_position(
+    env: &Env,
+    position_key: StrategyPositionKey,
+    strategy: Address,
+) -> Option<StrategyPosition> {
+    let stored = env.storage().persistent().get(&StrategyShareKey::Position(
+        position_key.clone(),
+        strategy.clone(),
+    ));
+    if stored.is_some() {
+        return stored;
+    }
+    read_legacy_position(env, &position_key)
+        .map(|(position, _)| position)
+        .filter(|position| position.strategy == strategy)
+}
+
+/// Returns every strategy position held by a plan.
+pub fn get_positions(env: &Env, position_key: StrategyPositionKey) -> Vec<StrategyPosition> {
+    let mut positions = Vec::new(env);
+    for strategy in position_strategies(env, &position_key).iter() {
+        if let Some(position) = get_position(env, position_key.clone(), strategy) {
+            positions.push_back(position);
+        }
+    }
+    if let Some((position, _)) = read_legacy_position(env, &position_key) {
+        positions.push_back(position);
+    }
+    positions
+}
+
+/// Reads a position stored under the plan's `StrategyPositionKey` by earlier
+/// versions. The flag is set for positions that predate share accounting,
+/// whose shares are not yet counted in `TotalShares`.
+fn read_legacy_position(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+) -> Option<(StrategyPosition, bool)> {
+    let raw: Map<Symbol, Val> = env.storage().persistent().get(position_key)?;
+    if raw.contains_key(Symbol::new(env, "shares")) {
+        let position = StrategyPosition::try_from_val(env, &raw.to_val()).ok()?;
+        return Some((position, false));
+    }
+
+    let legacy = LegacyStrategyPosition::try_from_val(env, &raw.to_val()).ok()?;
+    Some((
+        StrategyPosition {
+            yield_index: get_yield_index(env, &legacy.strategy),
+            strategy: legacy.strategy,
+            principal_deposited: legacy.principal_deposited,
+            strategy_shares: legacy.strategy_shares,
+            shares: legacy.principal_deposited,
+            pending_yield: 0,
+        },
+        true,
+    ))
+}
+
+/// Moves a plan's position stored by earlier versions to its per-strategy
+/// key. Positions that predate share accounting get one share per unit of
+/// principal. Does nothing when there is no such position.
+pub(crate) fn migrate_legacy_position(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+) -> Result<(), SavingsError> {
+    let (position, unshared) = match read_legacy_position(env, position_key) {
+        Some(legacy) => legacy,
+        None => return Ok(()),
+    };
+    env.storage().persistent().remove(position_key);
+
+    if unshared && position.shares > 0 {
+        write_i128(
+            env,
+            &StrategyShareKey::TotalShares(position.strategy.clone()),
+            get_total_shares(env, &position.strategy)
+                .checked_add(position.shares)
+                .ok_or(SavingsError::Overflow)?,
+        );
+        // Shares outside the total took no part in losses realized so far
+        loss::reset_checkpoint(env, position_key, &position.strategy);
+    }
+    save_position(env, position_key, &position);
+    Ok(())
+}
+
+fn position_strategies(env: &Env, position_key: &StrategyPositionKey) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&StrategyShareKey::Positions(position_key.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Number of plans currently holding a position in `strategy`.
+pub fn get_holder_count(env: &Env, strategy: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&StrategyShareKey::HolderCount(strategy.clone()))
+        .unwrap_or(0)
+}
+
+/// Returns up to `limit` of the plans holding a position in `strategy`,
+/// starting at slot `start`. Removing a holder moves the last one into its
+/// slot, so the order is not stable across removals.
+pub fn get_holders(
+    env: &Env,
+    strategy: &Address,
+    start: u32,
+    limit: u32,
+) -> Vec<StrategyPositionKey> {
+    let end = get_holder_count(env, strategy).min(start.saturating_add(limit));
+    let mut holders = Vec::new(env);
+    for slot in start..end {
+        if let Some(position_key) = env
+            .storage()
+            .persistent()
+            .get(&StrategyShareKey::HolderAt(strategy.clone(), slot))
+        {
+            holders.push_back(position_key);
+        }
+    }
+    holders
+}
+
+/// Adds or removes a plan in a strategy's holder list, one storage entry per
+/// holder so the list can grow without bound.
+fn update_holders(
+    env: &Env,
+    strategy: &Address,
+    position_key: &StrategyPositionKey,
+    present: bool,
+) {
+    let storage = env.storage().persistent();
+    let slot_key = StrategyShareKey::HolderSlot(strategy.clone(), position_key.clone());
+    let slot: Option<u32> = storage.get(&slot_key);
+    let count_key = StrategyShareKey::HolderCount(strategy.clone());
+    let count = get_holder_count(env, strategy);
+
+    match (slot, present) {
+        (None, true) => {
+            let at_key = StrategyShareKey::HolderAt(strategy.clone(), count);
+            storage.set(&at_key, position_key);
+            storage.extend_ttl(&at_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+            storage.set(&slot_key, &count);
+            storage.extend_ttl(&slot_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+            storage.set(&count_key, &(count + 1));
+            storage.extend_ttl(&count_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+        }
+        (Some(slot), false) => {
+            // Move the last holder into the freed slot
+            let last = count - 1;
+            let last_key = StrategyShareKey::HolderAt(strategy.clone(), last);
+            if slot != last {
+                let moved: StrategyPositionKey =
+                    storage.get(&last_key).expect("holder slot missing");
+                let at_key = StrategyShareKey::HolderAt(strategy.clone(), slot);
+                storage.set(&at_key, &moved);
+                storage.set(
+                    &StrategyShareKey::HolderSlot(strategy.clone(), moved),
+                    &slot,
+                );
+            }
+            storage.remove(&last_key);
+            storage.remove(&slot_key);
+            if last == 0 {
+                storage.remove(&count_key);
+            } else {
+                storage.set(&count_key, &last);
+            }
+        }
+        (Some(slot), true) => {
+            storage.extend_ttl(&slot_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+            storage.extend_ttl(
+                &StrategyShareKey::HolderAt(strategy.clone(), slot),
+                ttl::LOW_THRESHOLD,
+                ttl::EXTEND_TO,
+            );
+        }
+        (None, false) => {}
+    }
+}
+
+/// Adds or removes `item` in the list stored under `key`.
+fn update_index<T>(env: &Env, key: &StrategyShareKey, item: T, present: bool)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    let mut list: Vec<T> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    match (list.first_index_of(&item), present) {
+        (None, true) => list.push_back(item),
+        (Some(i), false) => {
+            list.remove(i);
+        }
+        _ => {
+            if present {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+            }
+            return;
+        }
+    }
+
+    if list.is_empty() {
+        env.storage().persistent().remove(key);
+    } else {
+        env.storage().persistent().set(key, &list);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    }
+}
+
+/// Persists a position and keeps the plan's strategy list and the strategy's
+/// holder list in step. A position with no shares and nothing left to claim
+/// is removed.
+pub(crate) fn save_position(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+    position: &StrategyPosition,
+) {
+    let key = StrategyShareKey::Position(position_key.clone(), position.strategy.clone());
+    let present = position.shares != 0 || position.pending_yield != 0;
+
+    if present {
+        env.storage().persistent().set(&key, position);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    if position.shares == 0 {
+        loss::clear_checkpoint(env, position_key, &position.strategy);
+    }
+
+    update_index(
+        env,
+        &StrategyShareKey::Positions(position_key.clone()),
+        position.strategy.clone(),
+        present,
+    );
+    update_holders(env, &position.strategy, position_key, present);
+}
+
 /// Routes eligible deposit funds to a registered yield strategy.
 ///
+/// A plan can hold positions in several strategies; routing to a strategy the
+/// plan already holds tops up that position.
+///
 /// Follows the Checks-Effects-Interactions (CEI) pattern:
 /// 1. **Checks** – validates strategy exists & is enabled, amount > 0, no reentrancy
 /// 2. **Effects** – persists `StrategyPosition` and performance state
-/// 3. **Interactions** – calls the external strategy contract
+/// 3. **Interactions** – transfers the tokens to the strategy, then calls it
 ///
 /// A reentrancy guard prevents malicious strategy callbacks from re-entering this
 /// function before the first call completes.
@@ -90,43 +587,121 @@ pub fn get_strategy_performance(env: &Env, strategy_address: Address) -> Strateg
 ///
 /// # Arguments
 /// * `env` - The contract environment
+/// * `caller` - The plan owner (see `require_position_owner`)
 /// * `strategy_address` - Address of the target strategy contract
-/// * `position_key` - Storage key for this position (Lock or Group)
+/// * `position_key` - The plan that owns the position (Lock or Group)
 /// * `amount` - Amount to deposit into the strategy
 ///
 /// # Returns
 /// The number of strategy shares received.
 ///
 /// # Errors
+/// * `PlanNotFound` - The plan does not exist
+/// * `Unauthorized` - `caller` does not own the plan
 /// * `StrategyNotFound` - Strategy not registered
 /// * `StrategyDisabled` - Strategy is disabled
 /// * `InvalidAmount` - amount <= 0
+/// * `UnsupportedAsset` - The strategy takes another asset than the plan holds
+/// * `InsufficientBalance` - amount exceeds the plan's unallocated balance
+/// * `AmountExceedsLimit` - The deposit would take the strategy over its cap
+///   or its share of total value locked
 /// * `ReentrancyDetected` - A reentrant call was attempted
 /// * `InvalidStrategyResponse` - Strategy returned 0 or negative shares
 pub fn route_to_strategy(
     env: &Env,
+    caller: &Address,
     strategy_address: Address,
     position_key: StrategyPositionKey,
     amount: i128,
+) -> Result<i128, SavingsError> {
+    require_position_owner(env, &position_key, caller)?;
+    let tvl_after = allocator::total_value_locked(env)
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    route_with_tvl(env, strategy_address, position_key, amount, tvl_after)
+}
+
+/// `route_to_strategy` without the ownership check, judging the strategy's
+/// TVL share limit against `tvl_after`, the protocol's routed principal once
+/// the deposit (or the whole allocation it belongs to) is in.
+///
+/// Callers must have checked that the plan belongs to the caller. The
+/// amount is always limited to the plan's unallocated balance, so only the
+/// plan's own funds can be routed.
+pub(crate) fn route_with_tvl(
+    env: &Env,
+    strategy_address: Address,
+    position_key: StrategyPositionKey,
+    amount: i128,
+    tvl_after: i128,
 ) -> Result<i128, SavingsError> {
     // --- CHECKS ---
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
     }
+    if amount > allocator::unallocated_balance(env, &position_key)? {
+        return Err(SavingsError::InsufficientBalance);
+    }
 
     let info = registry::get_strategy(env, strategy_address.clone())?;
     if !info.enabled {
         return Err(SavingsError::StrategyDisabled);
     }
+    let asset = registry::get_strategy_asset(env, &strategy_address);
+    if plan_asset(env, &position_key)? != asset {
+        return Err(SavingsError::UnsupportedAsset);
+    }
+    if amount > allocator::strategy_room(env, &info, tvl_after)? {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+    migrate_legacy_position(env, &position_key)?;
+    settle_profit(env, &strategy_address)?;
+
+    let mut position = get_position(env, position_key.clone(), strategy_address.clone()).unwrap_or(
+        StrategyPosition {
+            strategy: strategy_address.clone(),
+            principal_deposited: 0,
+            strategy_shares: 0,
+            shares: 0,
+            yield_index: get_yield_index(env, &strategy_address),
+            pending_yield: 0,
+        },
+    );
+
+    // Price new shares against the assets backing them before this deposit
+    let client = YieldStrategyClient::new(env, &strategy_address);
+    let total_shares = get_total_shares(env, &strategy_address);
+    let total_assets = if total_shares > 0 {
+        share_assets(env, &strategy_address)
+    } else {
+        0
+    };
+    let minted = assets_to_shares(amount, total_shares, total_assets)?;
+    if minted <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
 
     // --- EFFECTS (state update BEFORE external call) ---
     // Optimistically record the position; Soroban atomically reverts on failure.
-    let position = StrategyPosition {
-        strategy: strategy_address.clone(),
-        principal_deposited: amount,
-        strategy_shares: 0, // placeholder, updated after call
-    };
-    env.storage().persistent().set(&position_key, &position);
+    loss::absorb_loss(env, &position_key, &mut position)?;
+    accrue_position(env, &mut position)?;
+    position.principal_deposited = position
+        .principal_deposited
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    position.shares = position
+        .shares
+        .checked_add(minted)
+        .ok_or(SavingsError::Overflow)?;
+    save_position(env, &position_key, &position);
+
+    write_i128(
+        env,
+        &StrategyShareKey::TotalShares(strategy_address.clone()),
+        total_shares
+            .checked_add(minted)
+            .ok_or(SavingsError::Overflow)?,
+    );
 
     // Update global strategy principal
     let principal_key = DataKey::StrategyTotalPrincipal(strategy_address.clone());
@@ -148,7 +723,8 @@ pub fn route_to_strategy(
     perf.apy_estimate_bps = compute_apy_bps(perf.total_deposited, perf.total_harvested);
     save_performance(env, &strategy_address, &perf);
 
-    let client = YieldStrategyClient::new(env, &strategy_address);
+    // The strategy takes custody of the tokens before accounting for them
+    custody::send(env, &asset, &strategy_address, amount)?;
     let shares = client.strategy_deposit(&env.current_contract_address(), &amount);
 
     // Validate external call response
@@ -157,19 +733,11 @@ pub fn route_to_strategy(
     }
 
     // Update shares after successful call
-    let final_position = StrategyPosition {
-        strategy: strategy_address.clone(),
-        principal_deposited: amount,
-        strategy_shares: shares,
-    };
-    env.storage()
-        .persistent()
-        .set(&position_key, &final_position);
-
-    // Extend TTL
-    env.storage()
-        .persistent()
-        .extend_ttl(&position_key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    position.strategy_shares = position
+        .strategy_shares
+        .checked_add(shares)
+        .ok_or(SavingsError::Overflow)?;
+    save_position(env, &position_key, &position);
 
     // Emit event
     env.events().publish(
@@ -180,12 +748,225 @@ pub fn route_to_strategy(
     Ok(shares)
 }
 
-/// Retrieves the strategy position for a plan, if any.
-pub fn get_position(env: &Env, position_key: StrategyPositionKey) -> Option<StrategyPosition> {
-    env.storage().persistent().get(&position_key)
+/// Values a plan's position in `strategy` at the assets backing its shares.
+///
+/// `pending_yield` includes harvests since the position's last accrual;
+/// profit not harvested yet is in neither figure. `principal_deposited` is net
+/// of realized losses the position has not absorbed yet.
+pub fn get_position_value(
+    env: &Env,
+    position_key: StrategyPositionKey,
+    strategy: Address,
+) -> Result<Option<StrategyPositionValue>, SavingsError> {
+    let mut position = match get_position(env, position_key.clone(), strategy) {
+        Some(position) => position,
+        None => return Ok(None),
+    };
+    accrue_position(env, &mut position)?;
+    position.principal_deposited -= loss::pending_loss(env, &position_key, &position)?;
+
+    let current_value = if position.shares > 0 {
+        shares_to_assets(
+            position.shares,
+            get_total_shares(env, &position.strategy),
+            share_assets(env, &position.strategy),
+        )?
+    } else {
+        0
+    };
+
+    Ok(Some(StrategyPositionValue {
+        strategy: position.strategy,
+        principal_deposited: position.principal_deposited,
+        shares: position.shares,
+        current_value,
+        pending_yield: position.pending_yield,
+    }))
+}
+
+/// Pays the harvested yield of all of a lock's positions to `to` from the
+/// contract's custody, each in the asset of the strategy it came from.
+///
+/// # Returns
+/// The amount of yield claimed.
+pub fn claim_position_yield(env: &Env, lock_id: u64, to: Address) -> Result<i128, SavingsError> {
+    let mut claimed: i128 = 0;
+    for (strategy, amount) in take_yield(env, StrategyPositionKey::Lock(lock_id), &to)?.iter() {
+        claimed = claimed.checked_add(amount).ok_or(SavingsError::Overflow)?;
+        custody::send(
+            env,
+            &registry::get_strategy_asset(env, &strategy),
+            &to,
+            amount,
+        )?;
+    }
+    Ok(claimed)
+}
+
+/// Adds the harvested yield of all of a group's positions to its pool, so
+/// members share it when the group settles. The yield is already in the
+/// contract's custody.
+///
+/// # Returns
+/// The amount of yield added to the pool.
+pub fn pool_group_yield(env: &Env, group_id: u64) -> Result<i128, SavingsError> {
+    let mut claimed: i128 = 0;
+    for (_, amount) in take_yield(
+        env,
+        StrategyPositionKey::Group(group_id),
+        &env.current_contract_address(),
+    )?
+    .iter()
+    {
+        claimed = claimed.checked_add(amount).ok_or(SavingsError::Overflow)?;
+    }
+    adjust_plan_balance(env, &StrategyPositionKey::Group(group_id), claimed)?;
+    Ok(claimed)
+}
+
+/// Zeroes the harvested yield of all of a plan's positions, returning each
+/// strategy's amount. `to` is the recipient reported in the claim events.
+fn take_yield(
+    env: &Env,
+    position_key: StrategyPositionKey,
+    to: &Address,
+) -> Result<Vec<(Address, i128)>, SavingsError> {
+    migrate_legacy_position(env, &position_key)?;
+    let mut claimed = Vec::new(env);
+    for mut position in get_positions(env, position_key.clone()).iter() {
+        accrue_position(env, &mut position)?;
+        let amount = position.pending_yield;
+        if amount == 0 {
+            continue;
+        }
+        claimed.push_back((position.strategy.clone(), amount));
+        position.pending_yield = 0;
+        save_position(env, &position_key, &position);
+
+        env.events().publish(
+            (symbol_short!("strat"), symbol_short!("claim")),
+            (position.strategy, to.clone(), amount),
+        );
+    }
+
+    Ok(claimed)
+}
+
+/// Returns the asset a plan holds.
+///
+/// # Errors
+/// * `PlanNotFound` - If the plan does not exist
+pub fn plan_asset(env: &Env, position_key: &StrategyPositionKey) -> Result<Address, SavingsError> {
+    Ok(match position_key {
+        StrategyPositionKey::Lock(lock_id) => {
+            crate::lock::get_lock_save(env, *lock_id)
+                .ok_or(SavingsError::PlanNotFound)?
+                .asset
+        }
+        StrategyPositionKey::Group(group_id) => {
+            crate::group::get_group_save(env, *group_id)
+                .ok_or(SavingsError::PlanNotFound)?
+                .asset
+        }
+    })
 }
 
-/// Withdraws funds from a strategy position.
+/// Refuses while a plan still holds strategy positions, including ones with
+/// only unclaimed yield: part of its balance is not in the contract's
+/// custody, and positions are keyed by the plan so they would be stranded.
+///
+/// # Errors
+/// * `InvalidPlanConfig` - If the plan holds any strategy position
+pub fn require_no_positions(
+    env: &Env,
+    position_key: StrategyPositionKey,
+) -> Result<(), SavingsError> {
+    if !get_positions(env, position_key).is_empty() {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    Ok(())
+}
+
+/// Moves a plan's balance by what its strategy positions gained (positive)
+/// or lost (negative) against the principal routed: a lock's amount, along
+/// with its owner's total balance, or a group's pool.
+pub(crate) fn adjust_plan_balance(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+    delta: i128,
+) -> Result<(), SavingsError> {
+    if delta == 0 {
+        return Ok(());
+    }
+    match position_key {
+        StrategyPositionKey::Lock(lock_id) => {
+            let mut lock =
+                crate::lock::get_lock_save(env, *lock_id).ok_or(SavingsError::PlanNotFound)?;
+            let amount = lock
+                .amount
+                .checked_add(delta)
+                .ok_or(SavingsError::Overflow)?
+                .max(0);
+            let change = amount - lock.amount;
+            lock.amount = amount;
+            env.storage()
+                .persistent()
+                .set(&DataKey::LockSave(*lock_id), &lock);
+
+            let user_key = DataKey::User(lock.owner.clone());
+            if let Some(mut user) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, crate::storage_types::User>(&user_key)
+            {
+                user.total_balance = user.total_balance.saturating_add(change);
+                env.storage().persistent().set(&user_key, &user);
+            }
+        }
+        StrategyPositionKey::Group(group_id) => {
+            crate::group::adjust_pool(env, *group_id, delta)?;
+        }
+    }
+    Ok(())
+}
+
+/// Ensures `caller` owns the plan behind a strategy position: the lock owner
+/// for a LockSave, the group creator for a GroupSave.
+///
+/// # Errors
+/// * `PlanNotFound` - If the plan does not exist
+/// * `Unauthorized` - If `caller` does not own the plan
+pub fn require_position_owner(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+    caller: &Address,
+) -> Result<(), SavingsError> {
+    let owner = match position_key {
+        StrategyPositionKey::Lock(lock_id) => {
+            crate::lock::get_lock_save(env, *lock_id)
+                .ok_or(SavingsError::PlanNotFound)?
+                .owner
+        }
+        StrategyPositionKey::Group(group_id) => {
+            crate::group::get_group_save(env, *group_id)
+                .ok_or(SavingsError::PlanNotFound)?
+                .creator
+        }
+    };
+    if owner != *caller {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Withdraws a plan's whole position in `strategy`.
+///
+/// Redeems all of the position's shares for their part of the strategy's
+/// current balance, back into the contract's custody. Harvested yield stays
+/// claimable on the position. While the strategy is short of its recorded
+/// principal, the shortfall is borne pro rata by the shares withdrawn and
+/// comes off the plan's balance; for everyone else it is only written off
+/// once realized (see `loss::realize_loss`).
 ///
 /// Follows CEI: state is updated before the external call. A reentrancy guard
 /// prevents malicious strategy callbacks from re-entering while withdrawal
@@ -194,23 +975,21 @@ pub fn get_position(env: &Env, position_key: StrategyPositionKey) -> Option<Stra
 ///
 /// # Arguments
 /// * `env` - The contract environment
-/// * `position_key` - The position to withdraw from
-/// * `to` - The recipient address
+/// * `position_key` - The plan that owns the position
+/// * `strategy` - The strategy to withdraw from
 ///
 /// # Returns
-/// The amount of tokens received from the strategy.
+/// The amount of tokens received back into the contract's custody.
 pub fn withdraw_from_strategy(
     env: &Env,
     position_key: StrategyPositionKey,
-    to: Address,
+    strategy: Address,
 ) -> Result<i128, SavingsError> {
-    let mut position: StrategyPosition = env
-        .storage()
-        .persistent()
-        .get(&position_key)
+    migrate_legacy_position(env, &position_key)?;
+    let position = get_position(env, position_key.clone(), strategy.clone())
         .ok_or(SavingsError::StrategyNotFound)?;
 
-    if position.principal_deposited == 0 {
+    if position.shares == 0 {
         return Ok(0);
     }
 
@@ -220,31 +999,128 @@ pub fn withdraw_from_strategy(
         return Err(SavingsError::StrategyNotFound);
     }
 
+    settle_profit(env, &strategy)?;
+    let position =
+        get_position(env, position_key.clone(), strategy).ok_or(SavingsError::StrategyNotFound)?;
+
     // External call: check actual balance
-    let client = YieldStrategyClient::new(env, &position.strategy);
-    let strategy_balance = client.strategy_balance(&env.current_contract_address());
-    let withdraw_amount = position.principal_deposited.min(strategy_balance);
+    let strategy_balance = share_assets(env, &position.strategy);
+    let total_shares = get_total_shares(env, &position.strategy);
+    let withdraw_amount = shares_to_assets(position.shares, total_shares, strategy_balance)?;
     if withdraw_amount <= 0 {
         release_reentrancy_guard(env);
         return Err(SavingsError::InsufficientBalance);
     }
 
+    let shares = position.shares;
+    redeem(env, &position_key, position, shares, withdraw_amount)
+}
+
+/// Withdraws `amount` from a plan's position in `strategy`, burning the
+/// shares worth that much at the strategy's current balance (rounded up).
+///
+/// # Errors
+/// * `InvalidAmount` - If amount <= 0
+/// * `StrategyNotFound` - If there is no such position or the strategy is unregistered
+/// * `InsufficientBalance` - If the position is worth less than `amount`
+pub fn withdraw_amount_from_strategy(
+    env: &Env,
+    position_key: StrategyPositionKey,
+    strategy: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    migrate_legacy_position(env, &position_key)?;
+    if get_position(env, position_key.clone(), strategy.clone()).is_none() {
+        return Err(SavingsError::StrategyNotFound);
+    }
+    settle_profit(env, &strategy)?;
+    let position =
+        get_position(env, position_key.clone(), strategy).ok_or(SavingsError::StrategyNotFound)?;
+
+    let strategy_balance = share_assets(env, &position.strategy);
+    let total_shares = get_total_shares(env, &position.strategy);
+    let value = shares_to_assets(position.shares, total_shares, strategy_balance)?;
+    if amount > value {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    let numerator = amount
+        .checked_mul(total_shares)
+        .ok_or(SavingsError::Overflow)?;
+    let shares = (numerator / strategy_balance + i128::from(numerator % strategy_balance != 0))
+        .min(position.shares);
+
+    redeem(env, &position_key, position, shares, amount)
+}
+
+/// Burns `shares` of a position and withdraws `amount` from its strategy
+/// back into the contract's custody, where it is part of the plan's balance
+/// again.
+///
+/// Principal is released in proportion to the shares burned. When the
+/// strategy returns more or less than that principal, the plan's balance
+/// moves by the difference.
+fn redeem(
+    env: &Env,
+    position_key: &StrategyPositionKey,
+    mut position: StrategyPosition,
+    shares: i128,
+    amount: i128,
+) -> Result<i128, SavingsError> {
     // Update state BEFORE external call (CEI)
     let strategy_addr = position.strategy.clone();
-    position.principal_deposited = position
-        .principal_deposited
-        .checked_sub(withdraw_amount)
+    loss::absorb_loss(env, position_key, &mut position)?;
+    accrue_position(env, &mut position)?;
+
+    let redeemed_principal = if shares == position.shares {
+        position.principal_deposited
+    } else {
+        position
+            .principal_deposited
+            .checked_mul(shares)
+            .ok_or(SavingsError::Overflow)?
+            / position.shares
+    };
+    let redeemed_strategy_shares = if shares == position.shares {
+        position.strategy_shares
+    } else {
+        position
+            .strategy_shares
+            .checked_mul(shares)
+            .ok_or(SavingsError::Overflow)?
+            / position.shares
+    };
+
+    let shares_before = get_total_shares(env, &strategy_addr);
+    release_cover_shares(env, &strategy_addr, shares, shares_before)?;
+    let total_shares = shares_before
+        .checked_sub(shares)
         .ok_or(SavingsError::Underflow)?;
-    position.strategy_shares = 0;
-    env.storage().persistent().set(&position_key, &position);
+    write_i128(
+        env,
+        &StrategyShareKey::TotalShares(strategy_addr.clone()),
+        total_shares,
+    );
+    position.principal_deposited -= redeemed_principal;
+    position.strategy_shares -= redeemed_strategy_shares;
+    position.shares -= shares;
+    save_position(env, position_key, &position);
 
-    // Update global strategy principal
+    // Update global strategy principal; with no shares left any remainder is
+    // rounding residue
     let principal_key = DataKey::StrategyTotalPrincipal(strategy_addr.clone());
     let current_principal: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
-    if current_principal >= withdraw_amount {
+    if total_shares == 0 {
+        env.storage().persistent().set(&principal_key, &0_i128);
+        loss::clear_unabsorbed(env, &strategy_addr);
+    } else if current_principal >= redeemed_principal {
         env.storage()
             .persistent()
-            .set(&principal_key, &(current_principal - withdraw_amount));
+            .set(&principal_key, &(current_principal - redeemed_principal));
     } else {
         env.storage().persistent().set(&principal_key, &0_i128);
     }
@@ -253,28 +1129,31 @@ pub fn withdraw_from_strategy(
     let mut perf = load_performance(env, &strategy_addr);
     perf.total_withdrawn = perf
         .total_withdrawn
-        .checked_add(withdraw_amount)
+        .checked_add(amount)
         .unwrap_or(i128::MAX);
     save_performance(env, &strategy_addr, &perf);
 
     // Call strategy withdraw (INTERACTION)
-    let returned = client.strategy_withdraw(&to, &withdraw_amount);
+    let client = YieldStrategyClient::new(env, &strategy_addr);
+    let returned = client.strategy_withdraw(&env.current_contract_address(), &amount);
 
     // Validate response
     if returned <= 0 {
         return Err(SavingsError::InvalidStrategyResponse);
     }
+    adjust_plan_balance(env, position_key, returned - redeemed_principal)?;
 
     env.events().publish(
         (symbol_short!("strat"), symbol_short!("withdraw")),
-        (strategy_addr, withdraw_amount, returned),
+        (strategy_addr, amount, returned),
     );
 
     Ok(returned)
 }
 
 /// Harvests yield from a given strategy, calculates profit,
-/// allocates protocol fee to treasury, and credits the rest to users.
+/// allocates protocol fee to treasury, and credits the rest to the strategy's
+/// positions pro rata to their shares.
 ///
 /// A reentrancy guard prevents re-entrant calls during the harvest interaction.
 pub fn harvest_strategy(env: &Env, strategy_address: Address) -> Result<i128, SavingsError> {
@@ -291,8 +1170,7 @@ pub fn harvest_strategy(env: &Env, strategy_address: Address) -> Result<i128, Sa
     let strategy_balance = client.strategy_balance(&nestera_addr);
 
     // 2. Retrieve recorded principal
-    let principal_key = DataKey::StrategyTotalPrincipal(strategy_address.clone());
-    let principal: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
+    let principal = get_total_principal(env, &strategy_address);
 
     // 3. Calculate profit (no double counting)
     if strategy_balance <= principal {
@@ -327,7 +1205,23 @@ pub fn harvest_strategy(env: &Env, strategy_address: Address) -> Result<i128, Sa
         .checked_sub(treasury_fee)
         .ok_or(SavingsError::Underflow)?;
 
-    // 6. Update accounting records
+    // 6. Update accounting records, attributing user yield to shares
+    let total_shares = get_total_shares(env, &strategy_address);
+    if user_yield > 0 && total_shares > 0 {
+        let index = get_yield_index(env, &strategy_address)
+            .checked_add(
+                user_yield
+                    .checked_mul(YIELD_INDEX_SCALE)
+                    .ok_or(SavingsError::Overflow)?
+                    / total_shares,
+            )
+            .ok_or(SavingsError::Overflow)?;
+        write_i128(
+            env,
+            &StrategyShareKey::YieldIndex(strategy_address.clone()),
+            index,
+        );
+    }
     if user_yield > 0 {
         let yield_key = DataKey::StrategyYield(strategy_address.clone());
         let current_yield: i128 = env.storage().persistent().get(&yield_key).unwrap_or(0);
@@ -370,12 +1264,18 @@ pub fn harvest_strategy(env: &Env, strategy_address: Address) -> Result<i128, Sa
         ),
     );
 
-    // Record performance fee and yield in treasury
+    // Record performance fee and yield in the treasury of the strategy's asset
+    let asset = registry::get_strategy_asset(env, &strategy_address);
     if treasury_fee > 0 {
-        crate::treasury::record_fee(env, treasury_fee, soroban_sdk::Symbol::new(env, "perf"));
+        crate::treasury::record_fee(
+            env,
+            &asset,
+            treasury_fee,
+            soroban_sdk::Symbol::new(env, "perf"),
+        );
     }
     if user_yield > 0 {
-        crate::treasury::record_yield(env, user_yield);
+        crate::treasury::record_yield(env, &asset, user_yield);
     }
 
     Ok(actual_yield)
@@ -1,4 +1,5 @@
 use crate::errors::SavingsError;
+use crate::strategy::registry::{LegacyStrategyInfo, StrategyKey};
 use crate::strategy::routing::{self, StrategyPositionKey};
 use crate::{NesteraContract, NesteraContractClient};
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
@@ -14,6 +15,8 @@ fn setup() -> (Env, NesteraContractClient<'static>, Address, Address) {
 
     env.mock_all_auths();
     client.initialize(&admin, &admin_pk);
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
 
     (env, client, admin, contract_id)
 }
@@ -74,6 +77,36 @@ fn test_get_strategy_not_found() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_strategy_registered_before_limits_reads_as_uncapped() {
+    let (env, client, admin, contract_id) = setup();
+    let strategy_addr = Address::generate(&env);
+
+    // Stored in the shape used before strategies had deposit limits
+    env.as_contract(&contract_id, || {
+        let legacy = LegacyStrategyInfo {
+            address: strategy_addr.clone(),
+            enabled: true,
+            risk_level: 2,
+        };
+        env.storage()
+            .persistent()
+            .set(&StrategyKey::Info(strategy_addr.clone()), &legacy);
+    });
+
+    let info = client.get_strategy(&strategy_addr);
+    assert_eq!(info.risk_level, 2);
+    assert_eq!(info.deposit_cap, 0);
+    assert_eq!(info.max_tvl_share_bps, 10_000);
+
+    client.set_strategy_limits(&admin, &strategy_addr, &5_000, &4_000);
+    client.disable_strategy(&admin, &strategy_addr);
+    let info = client.get_strategy(&strategy_addr);
+    assert!(!info.enabled);
+    assert_eq!(info.deposit_cap, 5_000);
+    assert_eq!(info.max_tvl_share_bps, 4_000);
+}
+
 #[test]
 fn test_get_all_strategies() {
     let (env, client, admin, _) = setup();
@@ -99,31 +132,43 @@ fn test_register_strategy_unauthorized() {
 
 // ========== Routing Unit Tests ==========
 
+/// Opens a 1,000 lock for a fresh user; returns (owner, lock_id)
+fn open_lock(env: &Env, client: &NesteraContractClient<'static>) -> (Address, u64) {
+    let owner = Address::generate(env);
+    client.initialize_user(&owner);
+    let lock_id = client.create_lock_save(&owner, &1000, &3600);
+    (owner, lock_id)
+}
+
 #[test]
 fn test_route_to_strategy_invalid_amount() {
-    let (env, _client, _admin, contract_id) = setup();
+    let (env, client, _admin, contract_id) = setup();
     let strategy_addr = Address::generate(&env);
+    let (owner, lock_id) = open_lock(&env, &client);
 
     env.as_contract(&contract_id, || {
-        let position_key = StrategyPositionKey::Lock(1);
-        let result = routing::route_to_strategy(&env, strategy_addr.clone(), position_key, 0);
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        let result =
+            routing::route_to_strategy(&env, &owner, strategy_addr.clone(), position_key, 0);
         assert_eq!(result, Err(SavingsError::InvalidAmount));
 
-        let position_key2 = StrategyPositionKey::Lock(2);
+        let position_key2 = StrategyPositionKey::Lock(lock_id);
         let result2 =
-            routing::route_to_strategy(&env, Address::generate(&env), position_key2, -100);
+            routing::route_to_strategy(&env, &owner, Address::generate(&env), position_key2, -100);
         assert_eq!(result2, Err(SavingsError::InvalidAmount));
     });
 }
 
 #[test]
 fn test_route_to_unregistered_strategy_fails() {
-    let (env, _client, _admin, contract_id) = setup();
+    let (env, client, _admin, contract_id) = setup();
     let strategy_addr = Address::generate(&env);
+    let (owner, lock_id) = open_lock(&env, &client);
 
     env.as_contract(&contract_id, || {
-        let position_key = StrategyPositionKey::Lock(1);
-        let result = routing::route_to_strategy(&env, strategy_addr.clone(), position_key, 1000);
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        let result =
+            routing::route_to_strategy(&env, &owner, strategy_addr.clone(), position_key, 1000);
         assert_eq!(result, Err(SavingsError::StrategyNotFound));
     });
 }
@@ -132,23 +177,117 @@ fn test_route_to_unregistered_strategy_fails() {
 fn test_route_to_disabled_strategy_fails() {
     let (env, client, admin, contract_id) = setup();
     let strategy_addr = Address::generate(&env);
+    let (owner, lock_id) = open_lock(&env, &client);
 
     client.register_strategy(&admin, &strategy_addr, &1u32);
     client.disable_strategy(&admin, &strategy_addr);
 
     env.as_contract(&contract_id, || {
-        let position_key = StrategyPositionKey::Lock(1);
-        let result = routing::route_to_strategy(&env, strategy_addr.clone(), position_key, 1000);
+        let position_key = StrategyPositionKey::Lock(lock_id);
+        let result =
+            routing::route_to_strategy(&env, &owner, strategy_addr.clone(), position_key, 1000);
         assert_eq!(result, Err(SavingsError::StrategyDisabled));
     });
 }
 
+#[test]
+fn test_route_is_limited_to_the_owners_unallocated_balance() {
+    let (env, client, admin, contract_id) = setup();
+    let strategy_addr = Address::generate(&env);
+    let (owner, lock_id) = open_lock(&env, &client);
+    client.register_strategy(&admin, &strategy_addr, &1u32);
+
+    env.as_contract(&contract_id, || {
+        let stranger = Address::generate(&env);
+        let result = routing::route_to_strategy(
+            &env,
+            &stranger,
+            strategy_addr.clone(),
+            StrategyPositionKey::Lock(lock_id),
+            500,
+        );
+        assert_eq!(result, Err(SavingsError::Unauthorized));
+
+        // A 1,000 lock can't route other savers' funds
+        let result = routing::route_to_strategy(
+            &env,
+            &owner,
+            strategy_addr.clone(),
+            StrategyPositionKey::Lock(lock_id),
+            10_000,
+        );
+        assert_eq!(result, Err(SavingsError::InsufficientBalance));
+    });
+}
+
 #[test]
 fn test_get_position_none_when_empty() {
     let (env, _client, _admin, contract_id) = setup();
 
     env.as_contract(&contract_id, || {
-        let pos = routing::get_position(&env, StrategyPositionKey::Lock(999));
+        let pos = routing::get_position(
+            &env,
+            StrategyPositionKey::Lock(999),
+            Address::generate(&env),
+        );
         assert!(pos.is_none());
+        assert!(routing::get_positions(&env, StrategyPositionKey::Lock(999)).is_empty());
+    });
+}
+
+#[test]
+fn test_legacy_position_is_read_and_migrated() {
+    use crate::storage_types::DataKey;
+    use crate::strategy::migration_tests::{LeakyStrategy, LeakyStrategyClient};
+    use crate::strategy::routing::{LegacyStrategyPosition, StrategyShareKey};
+
+    let (env, client, admin, contract_id) = setup();
+    let strategy = env.register(LeakyStrategy, ());
+    client.register_strategy(&admin, &strategy, &1u32);
+
+    let owner = Address::generate(&env);
+    client.init_user(&owner);
+    let lock_id = client.create_lock_save(&owner, &1_000, &3600);
+
+    // A position written before share accounting, under the plan key itself
+    let legacy_key = StrategyPositionKey::Lock(lock_id);
+    LeakyStrategyClient::new(&env, &strategy).strategy_deposit(&contract_id, &1_000);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &legacy_key,
+            &LegacyStrategyPosition {
+                strategy: strategy.clone(),
+                principal_deposited: 1_000,
+                strategy_shares: 1_000,
+            },
+        );
+        env.storage().persistent().set(
+            &DataKey::StrategyTotalPrincipal(strategy.clone()),
+            &1_000i128,
+        );
+    });
+
+    let position = client
+        .get_lock_strategy_position(&lock_id, &strategy)
+        .unwrap();
+    assert_eq!(position.principal_deposited, 1_000);
+    assert_eq!(position.shares, 1_000);
+    assert_eq!(client.get_lock_strategy_positions(&lock_id).len(), 1);
+
+    assert_eq!(
+        client.withdraw_lock_strategy(&owner, &lock_id, &strategy),
+        1_000
+    );
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&legacy_key));
+        assert_eq!(
+            env.storage()
+                .persistent()
+                .get::<_, i128>(&StrategyShareKey::TotalShares(strategy.clone())),
+            Some(0)
+        );
     });
+    assert!(client
+        .get_lock_strategy_position(&lock_id, &strategy)
+        .is_none());
 }
@@ -1,6 +1,6 @@
 use crate::errors::SavingsError;
 use crate::storage_types::DataKey;
-use crate::strategy::routing::{self, StrategyPosition, StrategyPositionKey};
+use crate::strategy::routing::{self, StrategyPosition, StrategyPositionKey, StrategyShareKey};
 use crate::{NesteraContract, NesteraContractClient};
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
 
@@ -28,8 +28,14 @@ fn seed_position(
         strategy: strat_addr.clone(),
         principal_deposited: principal,
         strategy_shares: 0,
+        shares: principal,
+        yield_index: 0,
+        pending_yield: 0,
     };
-    env.storage().persistent().set(&position_key, &position);
+    env.storage().persistent().set(
+        &StrategyShareKey::Position(position_key, strat_addr.clone()),
+        &position,
+    );
 
     if also_set_principal {
         env.storage()
@@ -69,11 +75,8 @@ fn test_withdraw_from_zero_principal_returns_zero() {
             false,
         );
 
-        let result = routing::withdraw_from_strategy(
-            &env,
-            StrategyPositionKey::Lock(5),
-            Address::generate(&env),
-        );
+        let result =
+            routing::withdraw_from_strategy(&env, StrategyPositionKey::Lock(5), strat_addr.clone());
         // Should short-circuit and return Ok(0) before cross-contract call
         assert_eq!(result, Ok(0));
     });
@@ -94,11 +97,8 @@ fn test_withdraw_strategy_not_registered_errors() {
             false,
         );
 
-        let result = routing::withdraw_from_strategy(
-            &env,
-            StrategyPositionKey::Lock(6),
-            Address::generate(&env),
-        );
+        let result =
+            routing::withdraw_from_strategy(&env, StrategyPositionKey::Lock(6), strat_addr.clone());
         // Strategy not in registry → StrategyNotFound
         assert_eq!(result, Err(SavingsError::StrategyNotFound));
     });
@@ -1,5 +1,8 @@
 #![cfg(test)]
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String,
+};
 
 use crate::{NesteraContract, NesteraContractClient, SavingsError};
 
@@ -37,7 +40,7 @@ fn test_successful_break_group_save() {
         &String::from_str(&env, "Test Description"),
         &String::from_str(&env, "savings"),
         &10000, // target_amount
-        &0,     // contribution_type
+        &1,     // contribution_type
         &100,   // contribution_amount
         &true,  // is_public
         &1,     // start_time
@@ -126,7 +129,7 @@ fn test_break_group_updates_user_groups_list() {
         &String::from_str(&env, "Description 2"),
         &String::from_str(&env, "savings"),
         &3000,
-        &0,
+        &1,
         &50,
         &true,
         &1,
@@ -157,7 +160,7 @@ fn test_break_group_refunds_contribution() {
         &String::from_str(&env, "Description"),
         &String::from_str(&env, "savings"),
         &5000,
-        &0,
+        &1,
         &100,
         &true,
         &1,
@@ -248,7 +251,7 @@ fn test_error_group_completed() {
         &String::from_str(&env, "Description"),
         &String::from_str(&env, "savings"),
         &100, // Low target to easily complete
-        &0,
+        &1,
         &10,
         &true,
         &1,
@@ -302,3 +305,297 @@ fn test_error_user_not_found() {
     let result = client.try_break_group_save(&non_existent_user, &group_id);
     assert_eq!(result.unwrap_err(), Ok(SavingsError::UserNotFound));
 }
+
+fn create_group(
+    env: &Env,
+    client: &NesteraContractClient,
+    creator: &Address,
+    is_public: bool,
+) -> u64 {
+    client.create_group_save(
+        creator,
+        &String::from_str(env, "Test Group"),
+        &String::from_str(env, "Description"),
+        &String::from_str(env, "savings"),
+        &5000,
+        &0,
+        &100,
+        &is_public,
+        &1,
+        &500,
+    )
+}
+
+fn new_user(env: &Env, client: &NesteraContractClient) -> Address {
+    let user = Address::generate(env);
+    client.initialize_user(&user);
+    user
+}
+
+#[test]
+fn test_private_group_requires_invite() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let outsider = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, false);
+
+    let result = client.try_join_group_save(&outsider, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    client.invite_to_group(&creator, &group_id, &outsider);
+    assert!(client.is_invited_to_group(&group_id, &outsider));
+
+    client.join_group_save(&outsider, &group_id);
+    assert!(!client.is_invited_to_group(&group_id, &outsider));
+    assert_eq!(client.get_group_members(&group_id).len(), 2);
+}
+
+#[test]
+fn test_only_creator_can_invite() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let stranger = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, false);
+
+    let result = client.try_invite_to_group(&stranger, &group_id, &stranger);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+}
+
+#[test]
+fn test_join_request_approval_flow() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let alice = new_user(&env, &client);
+    let bob = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, false);
+
+    client.request_to_join_group(&alice, &group_id);
+    client.request_to_join_group(&bob, &group_id);
+    assert_eq!(client.get_group_join_requests(&group_id).len(), 2);
+
+    let result = client.try_request_to_join_group(&alice, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    client.approve_join_request(&creator, &group_id, &alice);
+    client.reject_join_request(&creator, &group_id, &bob);
+
+    assert!(client.get_group_join_requests(&group_id).is_empty());
+    let members = client.get_group_members(&group_id);
+    assert!(members.contains(&alice));
+    assert!(!members.contains(&bob));
+
+    let result = client.try_approve_join_request(&creator, &group_id, &bob);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_public_group_rejects_join_requests() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let user = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, true);
+
+    let result = client.try_request_to_join_group(&user, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_max_members_returns_group_full() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let member = new_user(&env, &client);
+    let late = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, true);
+
+    client.set_group_max_members(&creator, &group_id, &2);
+    client.join_group_save(&member, &group_id);
+
+    let result = client.try_join_group_save(&late, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::GroupFull));
+
+    // Cap cannot drop below the current member count
+    let result = client.try_set_group_max_members(&creator, &group_id, &1);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    // Lifting the cap reopens the group
+    client.set_group_max_members(&creator, &group_id, &0);
+    client.join_group_save(&late, &group_id);
+    assert_eq!(client.get_group_save(&group_id).member_count, 3);
+}
+
+#[test]
+fn test_remove_member_without_contributions() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let idle = new_user(&env, &client);
+    let saver = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, true);
+
+    client.join_group_save(&idle, &group_id);
+    client.join_group_save(&saver, &group_id);
+    client.contribute_to_group_save(&saver, &group_id, &100);
+
+    let result = client.try_remove_group_member(&creator, &group_id, &saver);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    client.remove_group_member(&creator, &group_id, &idle);
+    let members = client.get_group_members(&group_id);
+    assert!(!members.contains(&idle));
+    assert_eq!(client.get_group_save(&group_id).member_count, 2);
+
+    let result = client.try_remove_group_member(&creator, &group_id, &idle);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::NotGroupMember));
+}
+
+#[test]
+fn test_transfer_group_creator() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let member = new_user(&env, &client);
+    let outsider = new_user(&env, &client);
+    let group_id = create_group(&env, &client, &creator, false);
+
+    client.invite_to_group(&creator, &group_id, &member);
+    client.join_group_save(&member, &group_id);
+
+    let result = client.try_transfer_group_creator(&creator, &group_id, &outsider);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::NotGroupMember));
+
+    client.transfer_group_creator(&creator, &group_id, &member);
+    assert_eq!(client.get_group_save(&group_id).creator, member);
+
+    // The previous creator has lost management rights
+    let result = client.try_invite_to_group(&creator, &group_id, &outsider);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+    client.invite_to_group(&member, &group_id, &outsider);
+}
+
+fn create_typed_group(
+    env: &Env,
+    client: &NesteraContractClient,
+    creator: &Address,
+    contribution_type: u32,
+    contribution_amount: i128,
+) -> u64 {
+    client.create_group_save(
+        creator,
+        &String::from_str(env, "Typed Group"),
+        &String::from_str(env, "Description"),
+        &String::from_str(env, "savings"),
+        &10_000,
+        &contribution_type,
+        &contribution_amount,
+        &true,
+        &1,
+        &500,
+    )
+}
+
+#[test]
+fn test_fixed_group_requires_exact_amount() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let group_id = create_typed_group(&env, &client, &creator, 0, 100);
+
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &150);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidAmount));
+
+    client.contribute_to_group_save(&creator, &group_id, &100);
+    assert_eq!(
+        client
+            .get_group_member_compliance(&group_id, &creator)
+            .total_contributed,
+        100
+    );
+}
+
+#[test]
+fn test_flexible_group_enforces_minimum() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let group_id = create_typed_group(&env, &client, &creator, 1, 100);
+
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &99);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::AmountBelowMinimum));
+
+    client.contribute_to_group_save(&creator, &group_id, &250);
+    assert_eq!(client.get_group_save(&group_id).current_amount, 250);
+}
+
+#[test]
+fn test_percentage_group_charges_share_of_remaining_target() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    // 10% of whatever is left of the 10,000 target
+    let group_id = create_typed_group(&env, &client, &creator, 2, 1_000);
+
+    assert_eq!(client.get_group_contribution_due(&group_id), 1_000);
+    client.contribute_to_group_save(&creator, &group_id, &5_000);
+    assert_eq!(client.get_group_save(&group_id).current_amount, 1_000);
+
+    assert_eq!(client.get_group_contribution_due(&group_id), 900);
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &500);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::AmountExceedsLimit));
+}
+
+#[test]
+fn test_percentage_group_rejects_rate_above_100_percent() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+
+    let result = client.try_create_group_save(
+        &creator,
+        &String::from_str(&env, "Typed Group"),
+        &String::from_str(&env, "Description"),
+        &String::from_str(&env, "savings"),
+        &10_000,
+        &2,
+        &10_001,
+        &true,
+        &1,
+        &500,
+    );
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_periodic_group_limits_one_contribution_per_period() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let member = new_user(&env, &client);
+    let group_id = create_typed_group(&env, &client, &creator, 0, 100);
+    client.join_group_save(&member, &group_id);
+    client.set_group_period(&creator, &group_id, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = 1);
+    client.contribute_to_group_save(&member, &group_id, &100);
+    let result = client.try_contribute_to_group_save(&member, &group_id, &100);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.contribute_to_group_save(&member, &group_id, &100);
+
+    // Period 2 passes without a contribution
+    env.ledger().with_mut(|li| li.timestamp = 350);
+    let compliance = client.get_group_member_compliance(&group_id, &member);
+    assert_eq!(compliance.periods_elapsed, 4);
+    assert_eq!(compliance.periods_paid, 2);
+    assert_eq!(compliance.periods_missed, 1);
+    assert!(!compliance.paid_current_period);
+    assert!(!compliance.is_compliant);
+
+    // Creator never contributed in any closed period
+    let compliance = client.get_group_member_compliance(&group_id, &creator);
+    assert_eq!(compliance.periods_missed, 3);
+}
+
+#[test]
+fn test_group_period_locked_after_first_contribution() {
+    let (env, client, _admin) = setup();
+    let creator = new_user(&env, &client);
+    let group_id = create_typed_group(&env, &client, &creator, 0, 100);
+
+    client.contribute_to_group_save(&creator, &group_id, &100);
+    let result = client.try_set_group_period(&creator, &group_id, &100);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
@@ -3,14 +3,24 @@ pub mod types;
 #[cfg(test)]
 mod views_tests;
 
+use crate::assets;
+use crate::custody;
 use crate::errors::SavingsError;
 use crate::storage_types::DataKey;
-use soroban_sdk::{symbol_short, Address, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
 use types::{AllocationConfig, Treasury};
 
+/// Storage keys for treasury data beyond the base-asset treasury
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasuryKey {
+    /// Treasury balances held in an asset other than the base asset
+    Asset(Address),
+}
+
 // ========== Treasury Storage Helpers ==========
 
-/// Retrieves the Treasury struct from persistent storage.
+/// Retrieves the base-asset Treasury struct from persistent storage.
 pub fn get_treasury(env: &Env) -> Treasury {
     env.storage()
         .persistent()
@@ -18,9 +28,29 @@ pub fn get_treasury(env: &Env) -> Treasury {
         .unwrap_or(Treasury::new())
 }
 
-/// Saves the Treasury struct to persistent storage.
-fn set_treasury(env: &Env, treasury: &Treasury) {
-    env.storage().persistent().set(&DataKey::Treasury, treasury);
+/// Retrieves the Treasury struct of `asset`.
+///
+/// The base asset keeps its treasury under `DataKey::Treasury`, where it was
+/// stored before treasuries were kept per asset.
+pub fn get_asset_treasury(env: &Env, asset: &Address) -> Treasury {
+    if *asset == assets::base_asset(env) {
+        return get_treasury(env);
+    }
+    env.storage()
+        .persistent()
+        .get(&TreasuryKey::Asset(asset.clone()))
+        .unwrap_or_default()
+}
+
+/// Saves the Treasury struct of `asset` to persistent storage.
+fn set_asset_treasury(env: &Env, asset: &Address, treasury: &Treasury) {
+    if *asset == assets::base_asset(env) {
+        env.storage().persistent().set(&DataKey::Treasury, treasury);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&TreasuryKey::Asset(asset.clone()), treasury);
+    }
 }
 
 // ========== Treasury Initialization ==========
@@ -29,22 +59,26 @@ fn set_treasury(env: &Env, treasury: &Treasury) {
 /// Called during `initialize_config`.
 pub fn initialize_treasury(env: &Env) {
     let treasury = Treasury::new();
-    set_treasury(env, &treasury);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Treasury, &treasury);
 }
 
 // ========== Fee Recording ==========
 
-/// Records a collected fee into the treasury and emits a FeeCollected event.
+/// Records a fee collected in `asset` into that asset's treasury and emits a
+/// FeeCollected event.
 ///
 /// # Arguments
 /// * `env` - The contract environment
+/// * `asset` - The asset the fee was collected in
 /// * `amount` - The fee amount collected
 /// * `fee_type` - A short symbol describing the fee type (e.g., "dep", "wth", "perf")
-pub fn record_fee(env: &Env, amount: i128, fee_type: soroban_sdk::Symbol) {
+pub fn record_fee(env: &Env, asset: &Address, amount: i128, fee_type: soroban_sdk::Symbol) {
     if amount <= 0 {
         return;
     }
-    let mut treasury = get_treasury(env);
+    let mut treasury = get_asset_treasury(env, asset);
     treasury.total_fees_collected = treasury
         .total_fees_collected
         .checked_add(amount)
@@ -53,26 +87,97 @@ pub fn record_fee(env: &Env, amount: i128, fee_type: soroban_sdk::Symbol) {
         .treasury_balance
         .checked_add(amount)
         .unwrap_or(treasury.treasury_balance);
-    set_treasury(env, &treasury);
+    set_asset_treasury(env, asset, &treasury);
 
     env.events()
-        .publish((symbol_short!("fee_col"), fee_type), amount);
+        .publish((symbol_short!("fee_col"), fee_type, asset.clone()), amount);
 }
 
-/// Records yield earned into the treasury.
-pub fn record_yield(env: &Env, amount: i128) {
+/// Records yield earned in `asset` into that asset's treasury.
+pub fn record_yield(env: &Env, asset: &Address, amount: i128) {
     if amount <= 0 {
         return;
     }
-    let mut treasury = get_treasury(env);
+    let mut treasury = get_asset_treasury(env, asset);
     treasury.total_yield_earned = treasury
         .total_yield_earned
         .checked_add(amount)
         .unwrap_or(treasury.total_yield_earned);
-    set_treasury(env, &treasury);
+    set_asset_treasury(env, asset, &treasury);
+}
+
+/// Adds `amount` of `asset` from `funder` to that asset's reserve, which
+/// backs interest and yield payouts in the asset.
+///
+/// # Returns
+/// The new reserve balance of the asset.
+///
+/// # Errors
+/// * `InvalidAmount` - If amount <= 0
+/// * `UnsupportedAsset` - If the asset is not accepted for deposits
+pub fn fund_reserve(
+    env: &Env,
+    funder: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    funder.require_auth();
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    assets::require_supported(env, &asset)?;
+
+    let mut treasury = get_asset_treasury(env, &asset);
+    treasury.reserve_balance = treasury
+        .reserve_balance
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    set_asset_treasury(env, &asset, &treasury);
+
+    custody::receive(env, &asset, &funder, amount)?;
+
+    env.events()
+        .publish((symbol_short!("res_fund"), funder, asset), amount);
+    Ok(treasury.reserve_balance)
+}
+
+/// Takes up to `amount` out of the reserve of `asset` to pay out in it.
+///
+/// # Returns
+/// The amount actually drawn, limited by the asset's reserve balance.
+pub(crate) fn draw_reserve_in(env: &Env, asset: &Address, amount: i128) -> i128 {
+    let mut treasury = get_asset_treasury(env, asset);
+    let drawn = amount.min(treasury.reserve_balance).max(0);
+    if drawn == 0 {
+        return 0;
+    }
+    treasury.reserve_balance -= drawn;
+    set_asset_treasury(env, asset, &treasury);
+
+    env.events()
+        .publish((symbol_short!("res_draw"), asset.clone()), drawn);
+    drawn
+}
+
+/// Returns to the reserve of `asset` the part of an earlier draw that was not
+/// paid out.
+pub(crate) fn restore_reserve_in(env: &Env, asset: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let mut treasury = get_asset_treasury(env, asset);
+    treasury.reserve_balance = treasury.reserve_balance.saturating_add(amount);
+    set_asset_treasury(env, asset, &treasury);
+}
+
+/// Most the reserve can pay out in `asset` right now (see `draw_reserve_in`).
+pub(crate) fn reserve_available_in(env: &Env, asset: &Address) -> i128 {
+    get_asset_treasury(env, asset).reserve_balance
 }
 
 // ========== Read-Only Treasury Views ==========
+//
+// These report the base-asset treasury; see `get_asset_treasury` for others.
 
 /// Returns only the unallocated treasury balance (fees awaiting allocation).
 pub fn get_treasury_balance(env: &Env) -> i128 {
@@ -96,7 +201,8 @@ pub fn get_reserve_balance(env: &Env) -> i128 {
 
 // ========== Allocation Logic ==========
 
-/// Allocates the unallocated treasury balance into reserves, rewards, and operations.
+/// Allocates the unallocated treasury balance of every asset into reserves,
+/// rewards, and operations.
 ///
 /// The allocation percentages are provided as basis points (e.g., 4000 = 40%).
 /// They MUST sum to exactly 10_000 (100%).
@@ -111,6 +217,9 @@ pub fn get_reserve_balance(env: &Env) -> i128 {
 /// # Errors
 /// * `SavingsError::Unauthorized` - If caller is not admin
 /// * `SavingsError::InvalidAmount` - If percentages don't sum to 10_000
+///
+/// # Returns
+/// The base-asset treasury after allocation.
 pub fn allocate_treasury(
     env: &Env,
     admin: &Address,
@@ -129,6 +238,17 @@ pub fn allocate_treasury(
     }
     admin.require_auth();
 
+    apply_allocation(env, reserve_percent, rewards_percent, operations_percent)
+}
+
+/// Splits the unallocated treasury balance of every asset; callers are
+/// responsible for authorization.
+pub(crate) fn apply_allocation(
+    env: &Env,
+    reserve_percent: u32,
+    rewards_percent: u32,
+    operations_percent: u32,
+) -> Result<Treasury, SavingsError> {
     // Validate percentages sum to 100%
     let total = reserve_percent
         .checked_add(rewards_percent)
@@ -139,20 +259,48 @@ pub fn allocate_treasury(
         return Err(SavingsError::InvalidAmount);
     }
 
-    let mut treasury = get_treasury(env);
+    // Store the allocation config for reference
+    let alloc_config = AllocationConfig {
+        reserve_percent,
+        rewards_percent,
+        operations_percent,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::AllocationConfig, &alloc_config);
+
+    // Every asset that ever collected fees, base asset first
+    let base = assets::base_asset(env);
+    allocate_asset(env, &base, &alloc_config)?;
+    for asset in assets::get_all_assets(env).iter() {
+        if asset != base {
+            allocate_asset(env, &asset, &alloc_config)?;
+        }
+    }
+
+    Ok(get_treasury(env))
+}
+
+/// Splits the unallocated treasury balance of one asset.
+fn allocate_asset(
+    env: &Env,
+    asset: &Address,
+    config: &AllocationConfig,
+) -> Result<(), SavingsError> {
+    let mut treasury = get_asset_treasury(env, asset);
     let available = treasury.treasury_balance;
 
     if available <= 0 {
-        return Ok(treasury);
+        return Ok(());
     }
 
     // Calculate splits
     let reserve_amount = available
-        .checked_mul(reserve_percent as i128)
+        .checked_mul(config.reserve_percent as i128)
         .ok_or(SavingsError::Overflow)?
         / 10_000;
     let rewards_amount = available
-        .checked_mul(rewards_percent as i128)
+        .checked_mul(config.rewards_percent as i128)
         .ok_or(SavingsError::Overflow)?
         / 10_000;
     // Operations gets the remainder to avoid rounding dust
@@ -178,22 +326,12 @@ pub fn allocate_treasury(
     // Zero out the unallocated balance
     treasury.treasury_balance = 0;
 
-    // Store the allocation config for reference
-    let alloc_config = AllocationConfig {
-        reserve_percent,
-        rewards_percent,
-        operations_percent,
-    };
-    env.storage()
-        .persistent()
-        .set(&DataKey::AllocationConfig, &alloc_config);
-
-    set_treasury(env, &treasury);
+    set_asset_treasury(env, asset, &treasury);
 
     env.events().publish(
-        (symbol_short!("alloc"),),
+        (symbol_short!("alloc"), asset.clone()),
         (reserve_amount, rewards_amount, operations_amount),
     );
 
-    Ok(treasury)
+    Ok(())
 }
@@ -6,7 +6,8 @@
 /// 3. get_total_fees accumulates across multiple fee recordings
 /// 4. get_total_yield accumulates across multiple yield recordings
 /// 5. get_reserve_balance reflects post-allocation reserve amounts
-/// 6. No state mutation occurs when calling view functions
+/// 6. Fees and reserves are kept per asset
+/// 7. No state mutation occurs when calling view functions
 use crate::{NesteraContract, NesteraContractClient};
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
 
@@ -54,7 +55,12 @@ fn test_get_treasury_balance_reflects_fees() {
 
     env.as_contract(&contract_id, || {
         // Simulate fee recording (as happens during deposit/harvest)
-        crate::treasury::record_fee(&env, 2_000, soroban_sdk::Symbol::new(&env, "dep"));
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            2_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
     });
 
     // Use a fresh client pointed at the same contract
@@ -67,7 +73,12 @@ fn test_get_treasury_balance_reflects_fees() {
     // Direct storage check via as_contract on the original registered contract
     let orig_id = env.register(NesteraContract, ());
     env.as_contract(&orig_id, || {
-        crate::treasury::record_fee(&env, 3_000, soroban_sdk::Symbol::new(&env, "dep"));
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            3_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
         let bal = crate::treasury::get_treasury_balance(&env);
         assert_eq!(
             bal, 3_000,
@@ -92,7 +103,12 @@ fn test_get_treasury_balance_via_internal_storage() {
     client.initialize_config(&admin2, &treasury2, &500u32, &500u32, &1_000u32);
 
     env.as_contract(&contract_id, || {
-        crate::treasury::record_fee(&env, 5_000, soroban_sdk::Symbol::new(&env, "dep"));
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            5_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
     });
 
     assert_eq!(
@@ -118,9 +134,24 @@ fn test_get_total_fees_accumulates() {
     client.initialize_config(&admin2, &treasury2, &500u32, &500u32, &1_000u32);
 
     env.as_contract(&contract_id, || {
-        crate::treasury::record_fee(&env, 1_000, soroban_sdk::Symbol::new(&env, "dep"));
-        crate::treasury::record_fee(&env, 2_000, soroban_sdk::Symbol::new(&env, "wth"));
-        crate::treasury::record_fee(&env, 500, soroban_sdk::Symbol::new(&env, "perf"));
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            1_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            2_000,
+            soroban_sdk::Symbol::new(&env, "wth"),
+        );
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            500,
+            soroban_sdk::Symbol::new(&env, "perf"),
+        );
     });
 
     assert_eq!(
@@ -146,8 +177,8 @@ fn test_get_total_yield_accumulates() {
     client.initialize_config(&admin2, &treasury2, &500u32, &500u32, &1_000u32);
 
     env.as_contract(&contract_id, || {
-        crate::treasury::record_yield(&env, 4_000);
-        crate::treasury::record_yield(&env, 6_000);
+        crate::treasury::record_yield(&env, &crate::assets::base_asset(&env), 4_000);
+        crate::treasury::record_yield(&env, &crate::assets::base_asset(&env), 6_000);
     });
 
     assert_eq!(
@@ -174,7 +205,12 @@ fn test_get_reserve_balance_after_allocation() {
 
     // Seed treasury_balance so allocation has something to split
     env.as_contract(&contract_id, || {
-        crate::treasury::record_fee(&env, 10_000, soroban_sdk::Symbol::new(&env, "dep"));
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            10_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
     });
 
     assert_eq!(
@@ -193,6 +229,43 @@ fn test_get_reserve_balance_after_allocation() {
     );
 }
 
+// ========== Per-Asset Treasuries ==========
+
+#[test]
+fn test_treasury_balances_are_kept_per_asset() {
+    let (env, client, admin, _treasury) = setup();
+    let other = Address::generate(&env);
+    client.add_supported_asset(&admin, &other);
+
+    env.as_contract(&client.address, || {
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            1_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
+        crate::treasury::record_fee(&env, &other, 5_000, soroban_sdk::Symbol::new(&env, "dep"));
+    });
+    assert_eq!(client.get_treasury_balance(), 1_000);
+    assert_eq!(client.get_asset_treasury(&other).treasury_balance, 5_000);
+
+    client.allocate_treasury(&admin, &4_000u32, &4_000u32, &2_000u32);
+    assert_eq!(client.get_reserve_balance(), 400);
+    assert_eq!(client.get_asset_treasury(&other).reserve_balance, 2_000);
+    assert_eq!(client.get_asset_treasury(&other).treasury_balance, 0);
+
+    // Each reserve only pays out in its own asset
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::treasury::draw_reserve_in(&env, &other, 2_500), 2_000);
+        assert_eq!(
+            crate::treasury::draw_reserve_in(&env, &crate::assets::base_asset(&env), 100),
+            100
+        );
+    });
+    assert_eq!(client.get_reserve_balance(), 300);
+    assert_eq!(client.get_asset_treasury(&other).reserve_balance, 0);
+}
+
 // ========== No State Mutation ==========
 
 #[test]
@@ -209,7 +282,12 @@ fn test_views_do_not_mutate_state() {
     client.initialize_config(&admin2, &treasury2, &500u32, &500u32, &1_000u32);
 
     env.as_contract(&contract_id, || {
-        crate::treasury::record_fee(&env, 8_000, soroban_sdk::Symbol::new(&env, "dep"));
+        crate::treasury::record_fee(
+            &env,
+            &crate::assets::base_asset(&env),
+            8_000,
+            soroban_sdk::Symbol::new(&env, "dep"),
+        );
     });
 
     // Call each view multiple times — state must not change
@@ -1,6 +1,6 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Map, Symbol, Val};
 
-use crate::storage_types::{DataKey, GoalSave, LockSave, SavingsPlan};
+use crate::storage_types::{stored_flag, DataKey, SavingsPlan};
 
 // TTL Constants (in ledgers)
 // Assuming ~5 seconds per ledger:
@@ -29,10 +29,9 @@ pub fn extend_instance_ttl(env: &Env) {
 }
 
 /// Extends TTL for user-related storage entries
-/// Includes: User data, FlexiBalance, TotalBalance
+/// Includes: User data, base-asset FlexiBalance, TotalBalance
 pub fn extend_user_ttl(env: &Env, user: &Address) {
     let user_key = DataKey::User(user.clone());
-    let flexi_key = DataKey::FlexiBalance(user.clone());
     let total_key = DataKey::TotalBalance(user.clone());
 
     // Only extend TTL if the key exists
@@ -42,16 +41,22 @@ pub fn extend_user_ttl(env: &Env, user: &Address) {
             .extend_ttl(&user_key, LOW_THRESHOLD, EXTEND_TO);
     }
 
-    if env.storage().persistent().has(&flexi_key) {
+    extend_flexi_ttl(env, user, &crate::assets::base_asset(env));
+
+    if env.storage().persistent().has(&total_key) {
         env.storage()
             .persistent()
-            .extend_ttl(&flexi_key, LOW_THRESHOLD, EXTEND_TO);
+            .extend_ttl(&total_key, LOW_THRESHOLD, EXTEND_TO);
     }
+}
 
-    if env.storage().persistent().has(&total_key) {
+/// Extends TTL for a user's Flexi Save balance in a single asset
+pub fn extend_flexi_ttl(env: &Env, user: &Address, asset: &Address) {
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
+    if env.storage().persistent().has(&flexi_key) {
         env.storage()
             .persistent()
-            .extend_ttl(&total_key, LOW_THRESHOLD, EXTEND_TO);
+            .extend_ttl(&flexi_key, LOW_THRESHOLD, EXTEND_TO);
     }
 }
 
@@ -75,12 +80,13 @@ pub fn extend_plan_ttl(env: &Env, plan_key: &DataKey) {
 pub fn extend_lock_ttl(env: &Env, lock_id: u64) {
     let lock_key = DataKey::LockSave(lock_id);
 
-    if let Some(lock_save) = env
+    // Read the flag directly so plans in any stored shape are handled
+    if let Some(raw) = env
         .storage()
         .persistent()
-        .get::<DataKey, LockSave>(&lock_key)
+        .get::<DataKey, Map<Symbol, Val>>(&lock_key)
     {
-        if lock_save.is_withdrawn {
+        if stored_flag(env, &raw, "is_withdrawn") {
             // Already withdrawn - use shorter extension
             env.storage()
                 .persistent()
@@ -98,12 +104,12 @@ pub fn extend_lock_ttl(env: &Env, lock_id: u64) {
 pub fn extend_goal_ttl(env: &Env, goal_id: u64) {
     let goal_key = DataKey::GoalSave(goal_id);
 
-    if let Some(goal_save) = env
+    if let Some(raw) = env
         .storage()
         .persistent()
-        .get::<DataKey, GoalSave>(&goal_key)
+        .get::<DataKey, Map<Symbol, Val>>(&goal_key)
     {
-        if goal_save.is_completed || goal_save.is_withdrawn {
+        if stored_flag(env, &raw, "is_completed") || stored_flag(env, &raw, "is_withdrawn") {
             // Completed/withdrawn - use shorter extension
             env.storage()
                 .persistent()
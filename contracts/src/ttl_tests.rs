@@ -58,6 +58,9 @@ mod tests {
         let user = Address::generate(&env);
 
         env.mock_all_auths();
+        let admin = Address::generate(&env);
+        client.initialize(&admin, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+        client.set_lock_rate(&admin, &0, &500);
 
         // Initialize user
         client.initialize_user(&user);
@@ -193,6 +196,9 @@ mod tests {
         let user = Address::generate(&env);
 
         env.mock_all_auths();
+        let admin = Address::generate(&env);
+        client.initialize(&admin, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+        client.set_lock_rate(&admin, &0, &500);
 
         // Initialize user
         client.initialize_user(&user);
@@ -25,6 +25,11 @@ pub fn upgrade_contract(env: &Env, admin: Address, new_wasm_hash: BytesN<32>) {
     // 1. Verify Authorization
     admin.require_auth();
 
+    apply_upgrade(env, new_wasm_hash);
+}
+
+/// Swaps in new WASM and migrates storage; callers are responsible for authorization.
+pub(crate) fn apply_upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
     // 2. Perform Version Validation (Migration Safety)
     let current_version = get_version(env);
     let new_version = CONTRACT_VERSION; // This would typically come from the new WASM logic
@@ -1,6 +1,7 @@
 use crate::errors::SavingsError;
+use crate::flexi;
 use crate::storage_types::{
-    DataKey, GoalSaveView, GroupSaveView, LockSaveView, PlanType, SavingsPlan, User,
+    AssetBalance, DataKey, GoalSaveView, GroupSaveView, LockSaveView, PlanType, SavingsPlan, User,
 };
 use crate::ttl;
 use soroban_sdk::{Address, Env, Vec};
@@ -347,3 +348,84 @@ pub fn get_group_member_contribution(
 
     Err(SavingsError::PlanNotFound)
 }
+
+// ===========================================================================
+// Per-Asset Totals
+// ===========================================================================
+
+/// Returns the user's open balances grouped by asset.
+///
+/// One entry is returned for the base asset and for every asset that was ever
+/// whitelisted, so balances in a removed asset remain visible. Withdrawn lock
+/// and goal plans are excluded.
+pub fn get_user_asset_totals(env: &Env, user: Address) -> Result<Vec<AssetBalance>, SavingsError> {
+    if !crate::users::user_exists(env, &user) {
+        return Err(SavingsError::UserNotFound);
+    }
+
+    let base = crate::assets::base_asset(env);
+    let mut asset_list = Vec::new(env);
+    asset_list.push_back(base.clone());
+    for asset in crate::assets::get_all_assets(env).iter() {
+        if asset != base {
+            asset_list.push_back(asset);
+        }
+    }
+
+    let mut totals = Vec::new(env);
+    for asset in asset_list.iter() {
+        let flexi_balance = flexi::stored_balance(env, &user, &asset);
+        totals.push_back(AssetBalance {
+            asset,
+            flexi_balance,
+            lock_balance: 0,
+            goal_balance: 0,
+            group_balance: 0,
+        });
+    }
+
+    for lock_id in crate::lock::get_user_lock_saves(env, &user).iter() {
+        if let Some(lock) = crate::lock::get_lock_save(env, lock_id) {
+            if !lock.is_withdrawn {
+                add_to_asset(&mut totals, &lock.asset, |t| t.lock_balance += lock.amount);
+            }
+        }
+    }
+
+    for goal_id in crate::goal::get_user_goal_saves(env, &user).iter() {
+        if let Some(goal) = crate::goal::get_goal_save(env, goal_id) {
+            if !goal.is_withdrawn {
+                add_to_asset(&mut totals, &goal.asset, |t| {
+                    t.goal_balance += goal.current_amount
+                });
+            }
+        }
+    }
+
+    for group_id in crate::group::get_user_groups(env, &user).iter() {
+        if let Some(group) = crate::group::get_group_save(env, group_id) {
+            let contribution = crate::group::get_member_contribution(env, group_id, &user);
+            add_to_asset(&mut totals, &group.asset, |t| {
+                t.group_balance += contribution
+            });
+        }
+    }
+
+    Ok(totals)
+}
+
+fn add_to_asset(
+    totals: &mut Vec<AssetBalance>,
+    asset: &Address,
+    f: impl FnOnce(&mut AssetBalance),
+) {
+    for i in 0..totals.len() {
+        if let Some(mut entry) = totals.get(i) {
+            if entry.asset == *asset {
+                f(&mut entry);
+                totals.set(i, entry);
+                return;
+            }
+        }
+    }
+}
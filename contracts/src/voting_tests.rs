@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod voting_tests {
 
+    use crate::governance::{GovernanceKey, VoteWeighting};
     use crate::rewards::storage_types::RewardsConfig;
-    use crate::{NesteraContract, NesteraContractClient, PlanType};
+    use crate::{NesteraContract, NesteraContractClient, PlanType, SavingsError};
     use soroban_sdk::{
         testutils::{Address as _, Ledger},
         Address, BytesN, Env, String,
@@ -17,6 +18,7 @@ mod voting_tests {
 
         env.mock_all_auths();
         client.initialize(&admin, &admin_pk);
+        client.set_lock_rate(&admin, &0, &500);
 
         let config = RewardsConfig {
             points_per_token: 10,
@@ -34,13 +36,21 @@ mod voting_tests {
         (env, client, admin)
     }
 
+    /// A user with enough deposits to meet the proposal threshold
+    fn funded_creator(env: &Env, client: &NesteraContractClient<'static>) -> Address {
+        let creator = Address::generate(env);
+        client.initialize_user(&creator);
+        let _ = client.create_savings_plan(&creator, &PlanType::Flexi, &100);
+        creator
+    }
+
     fn setup_with_proposal() -> (Env, NesteraContractClient<'static>, Address, Address, u64) {
         let (env, client, admin) = setup_contract();
         env.mock_all_auths();
 
         client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
 
-        let creator = Address::generate(&env);
+        let creator = funded_creator(&env, &client);
         let description = String::from_str(&env, "Test proposal");
         let proposal_id = client
             .try_create_proposal(&creator, &description)
@@ -244,4 +254,339 @@ mod voting_tests {
         let proposal = client.get_proposal(&proposal_id).unwrap();
         assert_eq!(proposal.for_votes, 8000);
     }
+
+    #[test]
+    fn test_deposits_after_proposal_start_do_not_count() {
+        let (env, client, _admin, _creator, proposal_id) = setup_with_proposal();
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &1000);
+
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &4000);
+        assert_eq!(client.get_voting_power(&voter), 5000);
+
+        client.vote(&proposal_id, &1, &voter);
+        assert_eq!(client.get_proposal(&proposal_id).unwrap().for_votes, 1000);
+    }
+
+    #[test]
+    fn test_late_depositor_cannot_vote() {
+        let (env, client, _admin, _creator, proposal_id) = setup_with_proposal();
+        env.ledger().with_mut(|li| li.timestamp += 60);
+
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &1000);
+
+        assert_eq!(
+            client.try_vote(&proposal_id, &1, &voter).unwrap_err(),
+            Ok(SavingsError::InsufficientBalance)
+        );
+    }
+
+    #[test]
+    fn test_past_voting_power_follows_checkpoints() {
+        let (env, client, _admin) = setup_contract();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+
+        env.ledger().with_mut(|li| li.timestamp = 100);
+        let _ = client.create_savings_plan(&user, &PlanType::Flexi, &1000);
+        env.ledger().with_mut(|li| li.timestamp = 200);
+        let _ = client.create_savings_plan(&user, &PlanType::Flexi, &500);
+
+        assert_eq!(client.get_past_voting_power(&user, &99), 0);
+        assert_eq!(client.get_past_voting_power(&user, &100), 1000);
+        assert_eq!(client.get_past_voting_power(&user, &199), 1000);
+        assert_eq!(client.get_past_voting_power(&user, &200), 1500);
+    }
+
+    #[test]
+    fn test_delegated_power_votes_through_delegate() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+
+        let saver = Address::generate(&env);
+        let delegate = Address::generate(&env);
+        for user in [&saver, &delegate] {
+            client.initialize_user(user);
+        }
+        let _ = client.create_savings_plan(&saver, &PlanType::Flexi, &3000);
+        let _ = client.create_savings_plan(&delegate, &PlanType::Flexi, &1000);
+
+        client.delegate(&saver, &delegate);
+        assert_eq!(client.get_delegate(&saver), Some(delegate.clone()));
+        assert_eq!(client.get_voting_power(&saver), 0);
+        assert_eq!(client.get_voting_power(&delegate), 4000);
+
+        // Later deposits follow the delegation
+        let _ = client.create_savings_plan(&saver, &PlanType::Flexi, &500);
+        assert_eq!(client.get_voting_power(&delegate), 4500);
+
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Delegated"));
+        client.vote(&proposal_id, &1, &delegate);
+        assert_eq!(client.get_proposal(&proposal_id).unwrap().for_votes, 4500);
+        assert_eq!(
+            client.try_vote(&proposal_id, &1, &saver).unwrap_err(),
+            Ok(SavingsError::InsufficientBalance)
+        );
+    }
+
+    #[test]
+    fn test_undelegate_and_redelegate() {
+        let (env, client, _admin) = setup_contract();
+        let saver = Address::generate(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        client.initialize_user(&saver);
+        let _ = client.create_savings_plan(&saver, &PlanType::Flexi, &2000);
+
+        assert_eq!(
+            client.try_delegate(&saver, &saver).unwrap_err(),
+            Ok(SavingsError::InvalidPlanConfig)
+        );
+        assert_eq!(
+            client.try_undelegate(&saver).unwrap_err(),
+            Ok(SavingsError::PlanNotFound)
+        );
+
+        client.delegate(&saver, &first);
+        client.delegate(&saver, &second);
+        assert_eq!(client.get_voting_power(&first), 0);
+        assert_eq!(client.get_voting_power(&second), 2000);
+
+        client.undelegate(&saver);
+        assert_eq!(client.get_delegate(&saver), None);
+        assert_eq!(client.get_voting_power(&second), 0);
+        assert_eq!(client.get_voting_power(&saver), 2000);
+    }
+
+    #[test]
+    fn test_vote_record_stores_choice_weight_and_reason() {
+        let (env, client, _admin, _creator, proposal_id) = setup_with_proposal();
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &1000);
+
+        let reason = String::from_str(&env, "Rates are too low");
+        client.vote_with_reason(&proposal_id, &1, &voter, &reason);
+
+        let record = client.get_vote_record(&proposal_id, &voter).unwrap();
+        assert_eq!(record.choice, 1);
+        assert_eq!(record.weight, 1000);
+        assert_eq!(record.reason, reason);
+        assert!(client.has_voted(&proposal_id, &voter));
+    }
+
+    #[test]
+    fn test_change_vote_moves_weight_while_open() {
+        let (env, client, _admin, _creator, proposal_id) = setup_with_proposal();
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &1000);
+
+        let reason = String::from_str(&env, "Changed my mind");
+        assert_eq!(
+            client
+                .try_change_vote(&proposal_id, &2, &voter, &reason)
+                .unwrap_err(),
+            Ok(SavingsError::PlanNotFound)
+        );
+
+        client.vote(&proposal_id, &1, &voter);
+        client.change_vote(&proposal_id, &2, &voter, &reason);
+
+        let proposal = client.get_proposal(&proposal_id).unwrap();
+        assert_eq!(proposal.for_votes, 0);
+        assert_eq!(proposal.against_votes, 1000);
+        assert_eq!(
+            client.get_vote_record(&proposal_id, &voter).unwrap().choice,
+            2
+        );
+
+        // Voting has closed
+        env.ledger().with_mut(|li| li.timestamp += 604800 + 1);
+        assert_eq!(
+            client
+                .try_change_vote(&proposal_id, &1, &voter, &reason)
+                .unwrap_err(),
+            Ok(SavingsError::TooLate)
+        );
+    }
+
+    #[test]
+    fn test_quadratic_weighting() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+        client.set_vote_weighting(&admin, &VoteWeighting::Quadratic);
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Quadratic"));
+
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &10_000);
+
+        client.vote(&proposal_id, &1, &voter);
+        assert_eq!(client.get_proposal(&proposal_id).unwrap().for_votes, 100);
+    }
+
+    #[test]
+    fn test_conviction_weighting_favours_long_locks() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &1_000_000);
+        client.set_vote_weighting(&admin, &VoteWeighting::Conviction);
+
+        let locker = Address::generate(&env);
+        let saver = Address::generate(&env);
+        for user in [&locker, &saver] {
+            client.initialize_user(user);
+        }
+        client.create_lock_save(&locker, &1000, &(365 * 86400));
+        let _ = client.create_savings_plan(&saver, &PlanType::Flexi, &1000);
+
+        let creator = funded_creator(&env, &client);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Conviction"));
+        client.vote(&proposal_id, &1, &locker);
+        client.vote(&proposal_id, &2, &saver);
+
+        let proposal = client.get_proposal(&proposal_id).unwrap();
+        assert_eq!(proposal.for_votes, 2000);
+        assert_eq!(proposal.against_votes, 1000);
+    }
+
+    #[test]
+    fn test_weighting_is_fixed_when_proposal_is_created() {
+        let (env, client, admin, _creator, proposal_id) = setup_with_proposal();
+        client.set_vote_weighting(&admin, &VoteWeighting::Quadratic);
+        assert_eq!(
+            client.get_proposal_weighting(&proposal_id),
+            VoteWeighting::Linear
+        );
+
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &10_000);
+
+        client.vote(&proposal_id, &1, &voter);
+        assert_eq!(client.get_proposal(&proposal_id).unwrap().for_votes, 10_000);
+    }
+
+    #[test]
+    fn test_conviction_uses_locks_held_at_snapshot() {
+        let (env, client, admin) = setup_contract();
+        // Voting stays open for 100 days
+        client.init_voting_config(&admin, &5000, &8_640_000, &86400, &100, &1_000_000);
+        client.set_vote_weighting(&admin, &VoteWeighting::Conviction);
+
+        let locker = Address::generate(&env);
+        client.initialize_user(&locker);
+        let _ = client.create_savings_plan(&locker, &PlanType::Flexi, &1000);
+        let lock_id = client.create_lock_save(&locker, &1000, &(73 * 86400));
+
+        let creator = funded_creator(&env, &client);
+        env.ledger().with_mut(|li| li.timestamp += 1);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Open"));
+
+        // Closing the lock after the snapshot does not change the bonus
+        env.ledger().with_mut(|li| li.timestamp += 73 * 86400);
+        client.withdraw_lock_save(&locker, &lock_id);
+        env.ledger().with_mut(|li| li.timestamp += 1);
+        let proposal_id_late = client.create_proposal(&creator, &String::from_str(&env, "Late"));
+
+        client.vote(&proposal_id, &1, &locker);
+        client.vote(&proposal_id_late, &1, &locker);
+
+        // 1000 * 73 / 365 days of conviction bonus on top of both deposits,
+        // then only the Flexi deposit once the lock is closed
+        let proposal = client.get_proposal(&proposal_id).unwrap();
+        assert_eq!(proposal.for_votes, 2000 + 200);
+        let proposal_late = client.get_proposal(&proposal_id_late).unwrap();
+        assert_eq!(proposal_late.for_votes, 1000);
+    }
+
+    #[test]
+    fn test_legacy_vote_flag_blocks_revoting() {
+        let (env, client, _admin, _creator, proposal_id) = setup_with_proposal();
+        let voter = Address::generate(&env);
+        client.initialize_user(&voter);
+        let _ = client.create_savings_plan(&voter, &PlanType::Flexi, &1000);
+
+        // Votes cast before ballots were recorded were stored as `true`
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(
+                &GovernanceKey::VoterRecord(proposal_id, voter.clone()),
+                &true,
+            );
+        });
+
+        assert!(client.has_voted(&proposal_id, &voter));
+        assert_eq!(client.get_vote_record(&proposal_id, &voter), None);
+        assert_eq!(
+            client.try_vote(&proposal_id, &1, &voter).unwrap_err(),
+            Ok(SavingsError::DuplicatePlanId)
+        );
+        let reason = String::from_str(&env, "");
+        assert_eq!(
+            client
+                .try_change_vote(&proposal_id, &1, &voter, &reason)
+                .unwrap_err(),
+            Ok(SavingsError::DuplicatePlanId)
+        );
+    }
+
+    #[test]
+    fn test_legacy_depositor_can_delegate() {
+        let (env, client, _admin) = setup_contract();
+        let legacy = Address::generate(&env);
+        client.initialize_user(&legacy);
+        let _ = client.create_savings_plan(&legacy, &PlanType::Flexi, &1000);
+
+        // Deposits made before power was checkpointed left only `lifetime_deposited`
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            storage.remove(&GovernanceKey::OwnPower(legacy.clone()));
+            storage.remove(&GovernanceKey::Checkpoints(legacy.clone()));
+            storage.remove(&GovernanceKey::TotalSupply);
+        });
+
+        let delegatee = Address::generate(&env);
+        client.delegate(&legacy, &delegatee);
+        assert_eq!(client.get_voting_power(&delegatee), 1000);
+        assert_eq!(client.get_voting_power(&legacy), 0);
+        assert_eq!(client.get_total_voting_supply(), 1000);
+    }
+
+    #[test]
+    fn test_withdrawal_reduces_voting_power() {
+        let (env, client, _admin) = setup_contract();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        client.deposit_flexi(&user, &1000);
+        let delegatee = Address::generate(&env);
+        client.delegate(&user, &delegatee);
+
+        client.withdraw_flexi(&user, &400);
+        assert_eq!(client.get_voting_power(&delegatee), 600);
+        assert_eq!(client.get_total_voting_supply(), 600);
+    }
+
+    #[test]
+    fn test_deposit_in_proposal_ledger_does_not_count() {
+        let (env, client, admin) = setup_contract();
+        client.init_voting_config(&admin, &5000, &604800, &86400, &100, &10_000);
+        let creator = funded_creator(&env, &client);
+        env.ledger().with_mut(|li| li.timestamp += 1);
+
+        let late = Address::generate(&env);
+        client.initialize_user(&late);
+        let _ = client.create_savings_plan(&late, &PlanType::Flexi, &1000);
+        let proposal_id = client.create_proposal(&creator, &String::from_str(&env, "Open"));
+
+        assert_eq!(
+            client.try_vote(&proposal_id, &1, &late).unwrap_err(),
+            Ok(SavingsError::InsufficientBalance)
+        );
+    }
 }
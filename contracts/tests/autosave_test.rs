@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod autosave_tests {
-    use soroban_sdk::{testutils::Address as _, Address, Env};
-    use Nestera::{NesteraContract, NesteraContractClient};
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{Address as _, Ledger},
+        Address, BytesN, Env, String,
+    };
+    use Nestera::{AutoSaveTarget, NesteraContract, NesteraContractClient, SavingsError};
 
     fn setup_test_contract() -> (Env, NesteraContractClient<'static>, Address) {
         let env = Env::default();
@@ -350,4 +354,369 @@ mod autosave_tests {
         assert_eq!(client.get_flexi_balance(&user1), 500);
         assert_eq!(client.get_flexi_balance(&user2), 800);
     }
+
+    fn create_group(env: &Env, client: &NesteraContractClient, creator: &Address) -> u64 {
+        create_group_of_type(env, client, creator, 1)
+    }
+
+    /// Creates a group whose contributions are 100 (fixed) or at least 100 (flexible)
+    fn create_group_of_type(
+        env: &Env,
+        client: &NesteraContractClient,
+        creator: &Address,
+        contribution_type: u32,
+    ) -> u64 {
+        let now = env.ledger().timestamp();
+        client.create_group_save(
+            creator,
+            &String::from_str(env, "Savers"),
+            &String::from_str(env, "Monthly pot"),
+            &String::from_str(env, "general"),
+            &10_000,
+            &contribution_type,
+            &100,
+            &true,
+            &now,
+            &(now + 365 * 86400),
+        )
+    }
+
+    #[test]
+    fn test_targeted_autosave_into_goal() {
+        let (env, client, user) = setup_test_contract();
+        let goal_id = client.create_goal_save(&user, &symbol_short!("car"), &10_000, &0);
+
+        let schedule_id = client.create_targeted_autosave(
+            &user,
+            &AutoSaveTarget::Goal(goal_id),
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+        );
+        assert_eq!(
+            client.get_autosave(&schedule_id).unwrap().target,
+            AutoSaveTarget::Goal(goal_id)
+        );
+
+        let results = client.execute_due_autosaves(&soroban_sdk::vec![&env, schedule_id]);
+        assert!(results.get(0).unwrap());
+        assert_eq!(client.get_goal_save_detail(&goal_id).current_amount, 1000);
+        assert_eq!(client.get_flexi_balance(&user), 0);
+    }
+
+    #[test]
+    fn test_targeted_autosave_opens_lock_each_run() {
+        let (env, client, user) = setup_test_contract();
+        let admin = Address::generate(&env);
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        client.set_lock_rate(&admin, &30, &500);
+
+        let schedule_id = client.create_targeted_autosave(
+            &user,
+            &AutoSaveTarget::Lock(30 * 86400),
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+        );
+        client.execute_autosave(&schedule_id);
+
+        let locks = client.get_user_lock_saves(&user);
+        assert_eq!(locks.len(), 1);
+        let lock = client.get_lock_save_detail(&locks.get(0).unwrap());
+        assert_eq!(lock.amount, 1000);
+        assert_eq!(lock.maturity_time, lock.start_time + 30 * 86400);
+    }
+
+    #[test]
+    fn test_targeted_autosave_into_group() {
+        let (env, client, user) = setup_test_contract();
+        let group_id = create_group(&env, &client, &user);
+
+        let schedule_id = client.create_targeted_autosave(
+            &user,
+            &AutoSaveTarget::Group(group_id),
+            &500,
+            &86400,
+            &env.ledger().timestamp(),
+        );
+        let results = client.execute_due_autosaves(&soroban_sdk::vec![&env, schedule_id]);
+        assert!(results.get(0).unwrap());
+        assert_eq!(client.get_group_save(&group_id).current_amount, 500);
+    }
+
+    #[test]
+    fn test_targeted_autosave_rejects_foreign_goal() {
+        let (env, client, user) = setup_test_contract();
+        let owner = Address::generate(&env);
+        client.initialize_user(&owner);
+        let goal_id = client.create_goal_save(&owner, &symbol_short!("car"), &10_000, &0);
+
+        let result = client.try_create_targeted_autosave(
+            &user,
+            &AutoSaveTarget::Goal(goal_id),
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+        );
+        assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_targeted_autosave_rejects_non_member_and_bad_lock() {
+        let (env, client, user) = setup_test_contract();
+        let creator = Address::generate(&env);
+        client.initialize_user(&creator);
+        let group_id = create_group(&env, &client, &creator);
+        let now = env.ledger().timestamp();
+
+        assert_eq!(
+            client.try_create_targeted_autosave(
+                &user,
+                &AutoSaveTarget::Group(group_id),
+                &500,
+                &86400,
+                &now
+            ),
+            Err(Ok(SavingsError::NotGroupMember))
+        );
+        assert_eq!(
+            client.try_create_targeted_autosave(
+                &user,
+                &AutoSaveTarget::Group(99),
+                &500,
+                &86400,
+                &now
+            ),
+            Err(Ok(SavingsError::PlanNotFound))
+        );
+        assert_eq!(
+            client.try_create_targeted_autosave(
+                &user,
+                &AutoSaveTarget::Lock(0),
+                &500,
+                &86400,
+                &now
+            ),
+            Err(Ok(SavingsError::InvalidTimestamp))
+        );
+    }
+
+    #[test]
+    fn test_targeted_autosave_checks_group_contribution_amount() {
+        let (env, client, user) = setup_test_contract();
+        let now = env.ledger().timestamp();
+
+        let fixed_id = create_group_of_type(&env, &client, &user, 0);
+        assert_eq!(
+            client.try_create_targeted_autosave(
+                &user,
+                &AutoSaveTarget::Group(fixed_id),
+                &500,
+                &86400,
+                &now
+            ),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        client.create_targeted_autosave(
+            &user,
+            &AutoSaveTarget::Group(fixed_id),
+            &100,
+            &86400,
+            &now,
+        );
+
+        let flexible_id = create_group_of_type(&env, &client, &user, 1);
+        assert_eq!(
+            client.try_create_targeted_autosave(
+                &user,
+                &AutoSaveTarget::Group(flexible_id),
+                &50,
+                &86400,
+                &now
+            ),
+            Err(Ok(SavingsError::AmountBelowMinimum))
+        );
+    }
+
+    #[test]
+    fn test_targeted_autosave_rejects_lock_without_rate_tier() {
+        let (env, client, user) = setup_test_contract();
+        let admin = Address::generate(&env);
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        client.set_lock_rate(&admin, &30, &500);
+
+        assert_eq!(
+            client.try_create_targeted_autosave(
+                &user,
+                &AutoSaveTarget::Lock(7 * 86400),
+                &1000,
+                &86400,
+                &env.ledger().timestamp()
+            ),
+            Err(Ok(SavingsError::InvalidPlanConfig))
+        );
+    }
+
+    #[test]
+    fn test_due_index_lists_only_due_schedules() {
+        let (env, client, user) = setup_test_contract();
+        let now = env.ledger().timestamp();
+
+        let later = client.create_autosave(&user, &100, &86400, &(now + 2 * 86400));
+        let id1 = client.create_autosave(&user, &100, &86400, &now);
+        let id2 = client.create_autosave(&user, &100, &86400, &now);
+        let cancelled = client.create_autosave(&user, &100, &86400, &now);
+        client.cancel_autosave(&user, &cancelled);
+
+        let due = client.get_due_autosaves(&0, &10);
+        assert_eq!(due, soroban_sdk::vec![&env, id1, id2]);
+
+        // Paging
+        assert_eq!(
+            client.get_due_autosaves(&0, &1),
+            soroban_sdk::vec![&env, id1]
+        );
+        assert_eq!(
+            client.get_due_autosaves(&1, &10),
+            soroban_sdk::vec![&env, id2]
+        );
+
+        // Executed schedules move to their next bucket
+        client.execute_due_autosaves(&due);
+        assert!(client.get_due_autosaves(&0, &10).is_empty());
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 86400);
+        let due = client.get_due_autosaves(&0, &10);
+        assert_eq!(due.len(), 3);
+        assert!(due.contains(later));
+    }
+
+    #[test]
+    fn test_pause_and_resume_autosave() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let id = client.create_autosave(&user, &100, &86400, &start);
+
+        client.pause_autosave(&user, &id);
+        assert!(client.get_due_autosaves(&0, &10).is_empty());
+        assert_eq!(
+            client.try_execute_autosave(&id),
+            Err(Ok(SavingsError::InvalidPlanConfig))
+        );
+        assert_eq!(
+            client.try_pause_autosave(&user, &id),
+            Err(Ok(SavingsError::InvalidPlanConfig))
+        );
+
+        // Resuming after three missed days moves to the next slot
+        env.ledger().with_mut(|li| li.timestamp += 3 * 86400 + 60);
+        client.resume_autosave(&user, &id);
+        assert_eq!(
+            client.get_autosave(&id).unwrap().next_execution_time,
+            start + 4 * 86400
+        );
+        assert!(client.get_due_autosaves(&0, &10).is_empty());
+    }
+
+    #[test]
+    fn test_update_autosave_amount_and_interval() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let id = client.create_autosave(&user, &100, &86400, &start);
+
+        client.update_autosave(&user, &id, &250, &3600);
+        client.execute_autosave(&id);
+
+        let schedule = client.get_autosave(&id).unwrap();
+        assert_eq!(schedule.amount, 250);
+        assert_eq!(schedule.next_execution_time, start + 3600);
+        assert_eq!(client.get_flexi_balance(&user), 250);
+
+        assert_eq!(
+            client.try_update_autosave(&user, &id, &0, &3600),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_update_autosave(&user, &id, &100, &0),
+            Err(Ok(SavingsError::InvalidTimestamp))
+        );
+    }
+
+    #[test]
+    fn test_stalled_schedule_skips_missed_intervals_by_default() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let id = client.create_autosave(&user, &100, &86400, &start);
+
+        env.ledger().with_mut(|li| li.timestamp += 5 * 86400);
+        let ids = soroban_sdk::vec![&env, id];
+        assert!(client.execute_due_autosaves(&ids).get(0).unwrap());
+        assert!(!client.execute_due_autosaves(&ids).get(0).unwrap());
+
+        assert_eq!(client.get_flexi_balance(&user), 100);
+        assert_eq!(
+            client.get_autosave(&id).unwrap().next_execution_time,
+            start + 6 * 86400
+        );
+    }
+
+    #[test]
+    fn test_catch_up_runs_every_missed_interval() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let id = client.create_autosave(&user, &100, &86400, &start);
+        client.set_autosave_catch_up(&user, &id, &true);
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 86400);
+        let ids = soroban_sdk::vec![&env, id];
+        for _ in 0..3 {
+            assert!(client.execute_due_autosaves(&ids).get(0).unwrap());
+        }
+        assert!(!client.execute_due_autosaves(&ids).get(0).unwrap());
+        assert_eq!(client.get_flexi_balance(&user), 300);
+    }
+
+    #[test]
+    fn test_autosave_finishes_at_max_executions() {
+        let (env, client, user) = setup_test_contract();
+        let id = client.create_autosave(&user, &100, &86400, &env.ledger().timestamp());
+        client.set_autosave_limits(&user, &id, &0, &2);
+
+        client.execute_autosave(&id);
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        client.execute_autosave(&id);
+
+        let schedule = client.get_autosave(&id).unwrap();
+        assert_eq!(schedule.executions, 2);
+        assert!(!schedule.is_active);
+        assert!(client.get_due_autosaves(&0, &10).is_empty());
+
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        assert_eq!(
+            client.try_execute_autosave(&id),
+            Err(Ok(SavingsError::InvalidPlanConfig))
+        );
+        assert_eq!(client.get_flexi_balance(&user), 200);
+    }
+
+    #[test]
+    fn test_autosave_finishes_after_end_time() {
+        let (env, client, user) = setup_test_contract();
+        env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+        let start = env.ledger().timestamp();
+        let id = client.create_autosave(&user, &100, &86400, &start);
+
+        assert_eq!(
+            client.try_set_autosave_limits(&user, &id, &(start - 1), &0),
+            Err(Ok(SavingsError::InvalidTimestamp))
+        );
+        client.set_autosave_limits(&user, &id, &(start + 86400), &0);
+
+        client.execute_autosave(&id);
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        client.execute_autosave(&id);
+
+        assert!(!client.get_autosave(&id).unwrap().is_active);
+        assert_eq!(client.get_flexi_balance(&user), 200);
+    }
 }
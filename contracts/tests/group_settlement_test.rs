@@ -0,0 +1,230 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, IntoVal, String, Val,
+};
+
+use Nestera::{NesteraContract, NesteraContractClient, SavingsError};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+struct Setup {
+    env: Env,
+    client: NesteraContractClient<'static>,
+    token: TokenClient<'static>,
+    group_id: u64,
+    creator: Address,
+    member: Address,
+}
+
+/// Two-member group targeting 3,000 over one year at a 10% group rate.
+fn setup() -> Setup {
+    setup_with_reserve(1_000)
+}
+
+/// Like `setup`, with `reserve` in the treasury reserve to pay yield from.
+fn setup_with_reserve(reserve: i128) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    client.set_underlying_asset(&admin, &sac.address());
+    client.set_group_rate(&admin, &1_000);
+    let token_admin = StellarAssetClient::new(&env, &sac.address());
+
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    for user in [&creator, &member] {
+        client.initialize_user(user);
+        token_admin.mint(user, &5_000);
+    }
+    // Reserve backing group yield
+    token_admin.mint(&admin, &reserve);
+    client.fund_reserve(&admin, &reserve);
+
+    let now = env.ledger().timestamp();
+    let group_id = client.create_group_save(
+        &creator,
+        &String::from_str(&env, "Holiday"),
+        &String::from_str(&env, "Shared trip fund"),
+        &String::from_str(&env, "travel"),
+        &3_000,
+        &1,
+        &100,
+        &true,
+        &now,
+        &(now + YEAR),
+    );
+    client.join_group_save(&member, &group_id);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        env,
+        client,
+        group_id,
+        creator,
+        member,
+    }
+}
+
+#[test]
+fn test_settle_before_target_or_end_fails() {
+    let s = setup();
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &1_000);
+
+    assert_eq!(
+        s.client.try_settle_group_save(&s.group_id),
+        Err(Ok(SavingsError::GroupCycleIncomplete))
+    );
+}
+
+#[test]
+fn test_settle_after_end_time_pays_contribution_plus_share() {
+    let s = setup();
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &1_000);
+    s.client
+        .contribute_to_group_save(&s.member, &s.group_id, &1_000);
+    s.client
+        .contribute_to_group_save(&s.member, &s.group_id, &1_000);
+
+    s.env.ledger().with_mut(|li| li.timestamp += 2 * YEAR);
+
+    // Yield stops accruing at end_time: 10% of 3,000 for one year
+    let paid = s.client.settle_group_save(&s.group_id);
+    assert_eq!(paid, 3_300);
+    assert_eq!(s.token.balance(&s.creator), 5_000 - 1_000 + 1_100);
+    assert_eq!(s.token.balance(&s.member), 5_000 - 2_000 + 2_200);
+
+    assert!(s.client.is_group_settled(&s.group_id));
+    assert!(s.client.get_group_save(&s.group_id).is_completed);
+}
+
+#[test]
+fn test_settle_once_target_reached_is_early() {
+    let s = setup();
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &1_500);
+    s.env.ledger().with_mut(|li| li.timestamp += YEAR / 2);
+    s.client
+        .contribute_to_group_save(&s.member, &s.group_id, &1_500);
+
+    // Only the first contribution was in the pool for the half year
+    assert_eq!(s.client.settle_group_save(&s.group_id), 3_075);
+    assert_eq!(s.token.balance(&s.creator), 5_000 + 75);
+    assert_eq!(s.token.balance(&s.member), 5_000);
+    assert_eq!(s.client.get_reserve_balance(), 1_000 - 75);
+}
+
+#[test]
+fn test_yield_is_capped_at_the_reserve() {
+    let s = setup_with_reserve(100);
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &1_000);
+    s.client
+        .contribute_to_group_save(&s.member, &s.group_id, &2_000);
+    s.env.ledger().with_mut(|li| li.timestamp += YEAR);
+
+    // 300 earned but only 100 funded: each member's yield is cut pro rata
+    assert_eq!(s.client.settle_group_save(&s.group_id), 3_099);
+    assert_eq!(s.token.balance(&s.creator), 5_000 + 33);
+    assert_eq!(s.token.balance(&s.member), 5_000 + 66);
+    assert_eq!(s.client.get_reserve_balance(), 1);
+}
+
+#[test]
+fn test_settle_emits_event_per_member() {
+    let s = setup();
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &3_000);
+
+    s.client.settle_group_save(&s.group_id);
+
+    let settle_topic: Val = symbol_short!("grp_stl").into_val(&s.env);
+    let settled = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| topics.get(0).is_some_and(|t| t.shallow_eq(&settle_topic)))
+        .count();
+    assert_eq!(settled, 2);
+}
+
+#[test]
+fn test_settled_group_is_closed() {
+    let s = setup();
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &3_000);
+    s.client.settle_group_save(&s.group_id);
+
+    assert_eq!(
+        s.client.try_settle_group_save(&s.group_id),
+        Err(Ok(SavingsError::AlreadyWithdrawn))
+    );
+    assert_eq!(
+        s.client
+            .try_contribute_to_group_save(&s.member, &s.group_id, &100),
+        Err(Ok(SavingsError::PlanCompleted))
+    );
+    assert_eq!(
+        s.client.try_break_group_save(&s.member, &s.group_id),
+        Err(Ok(SavingsError::PlanCompleted))
+    );
+}
+
+#[test]
+fn test_large_group_settles_in_batches() {
+    let s = setup();
+    let token_admin = StellarAssetClient::new(&s.env, &s.token.address);
+    s.client
+        .contribute_to_group_save(&s.creator, &s.group_id, &100);
+    s.client
+        .contribute_to_group_save(&s.member, &s.group_id, &100);
+    let mut members = soroban_sdk::Vec::new(&s.env);
+    for _ in 0..28 {
+        let saver = Address::generate(&s.env);
+        s.client.initialize_user(&saver);
+        token_admin.mint(&saver, &100);
+        s.client.join_group_save(&saver, &s.group_id);
+        s.client.contribute_to_group_save(&saver, &s.group_id, &100);
+        members.push_back(saver);
+    }
+    s.env.ledger().with_mut(|li| li.timestamp += YEAR);
+
+    // The first call only tallies the first batch, closing the group
+    assert_eq!(s.client.settle_group_save(&s.group_id), 0);
+    let progress = s.client.get_group_settlement(&s.group_id).unwrap();
+    assert!(!progress.tallied);
+    assert_eq!(progress.next_member, 25);
+    let late = Address::generate(&s.env);
+    s.client.initialize_user(&late);
+    assert_eq!(
+        s.client.try_join_group_save(&late, &s.group_id),
+        Err(Ok(SavingsError::PlanCompleted))
+    );
+
+    // Then each call pays out a batch: 100 plus 10% yield per member
+    assert_eq!(s.client.settle_group_save(&s.group_id), 25 * 110);
+    assert_eq!(s.client.settle_group_save(&s.group_id), 5 * 110);
+    let progress = s.client.get_group_settlement(&s.group_id).unwrap();
+    assert!(progress.completed);
+    assert_eq!(progress.total_paid, 30 * 110);
+
+    assert_eq!(s.token.balance(&members.get(27).unwrap()), 110);
+    assert_eq!(s.client.get_reserve_balance(), 1_000 - 300);
+    assert_eq!(
+        s.client.try_settle_group_save(&s.group_id),
+        Err(Ok(SavingsError::AlreadyWithdrawn))
+    );
+}
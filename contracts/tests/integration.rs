@@ -24,6 +24,8 @@ fn setup_env() -> (
     let admin = Address::generate(&env);
     let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
     client.initialize(&admin, &admin_pk);
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
 
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
@@ -306,7 +308,7 @@ fn test_group_save_full_flow() {
         &SorobanString::from_str(&env, "Saving for team retreat"),
         &SorobanString::from_str(&env, "travel"),
         &9000, // target_amount
-        &0,    // contribution_type
+        &1,    // contribution_type
         &1000, // contribution_amount
         &true, // is_public
         &env.ledger().timestamp(),
@@ -641,7 +643,7 @@ fn test_multi_user_multi_plan_scenario() {
         &SorobanString::from_str(&env, "Collaborative savings"),
         &SorobanString::from_str(&env, "general"),
         &15000,
-        &0,
+        &1,
         &1000,
         &true,
         &env.ledger().timestamp(),
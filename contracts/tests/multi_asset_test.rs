@@ -0,0 +1,283 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String, Symbol,
+};
+
+use Nestera::{NesteraContract, NesteraContractClient, PlanType, SavingsError};
+
+struct Setup {
+    env: Env,
+    client: NesteraContractClient<'static>,
+    admin: Address,
+    usdc: TokenClient<'static>,
+    usdc_admin: StellarAssetClient<'static>,
+    xlm: TokenClient<'static>,
+    xlm_admin: StellarAssetClient<'static>,
+}
+
+/// USDC is the base (underlying) asset; XLM is whitelisted on top of it.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
+    client.initialize(&admin, &admin_pk);
+
+    let usdc_sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let xlm_sac = env.register_stellar_asset_contract_v2(admin.clone());
+    client.set_underlying_asset(&admin, &usdc_sac.address());
+    client.add_supported_asset(&admin, &xlm_sac.address());
+
+    Setup {
+        usdc: TokenClient::new(&env, &usdc_sac.address()),
+        usdc_admin: StellarAssetClient::new(&env, &usdc_sac.address()),
+        xlm: TokenClient::new(&env, &xlm_sac.address()),
+        xlm_admin: StellarAssetClient::new(&env, &xlm_sac.address()),
+        env,
+        client,
+        admin,
+    }
+}
+
+fn funded_user(s: &Setup, usdc: i128, xlm: i128) -> Address {
+    let user = Address::generate(&s.env);
+    s.client.initialize_user(&user);
+    s.usdc_admin.mint(&user, &usdc);
+    s.xlm_admin.mint(&user, &xlm);
+    user
+}
+
+#[test]
+fn test_supported_assets_lists_base_first() {
+    let s = setup();
+    let assets = s.client.get_supported_assets();
+    assert_eq!(assets.len(), 2);
+    assert_eq!(assets.get(0).unwrap(), s.usdc.address);
+    assert_eq!(assets.get(1).unwrap(), s.xlm.address);
+}
+
+#[test]
+fn test_add_supported_asset_requires_admin() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let other = s.env.register_stellar_asset_contract_v2(s.admin.clone());
+
+    let result = s
+        .client
+        .try_add_supported_asset(&stranger, &other.address());
+    assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+    assert!(!s.client.is_asset_supported(&other.address()));
+}
+
+#[test]
+fn test_deposit_in_unlisted_asset_fails() {
+    let s = setup();
+    let user = funded_user(&s, 0, 0);
+    let other = s.env.register_stellar_asset_contract_v2(s.admin.clone());
+
+    let result = s
+        .client
+        .try_deposit_flexi_asset(&user, &other.address(), &100);
+    assert_eq!(result, Err(Ok(SavingsError::UnsupportedAsset)));
+}
+
+#[test]
+fn test_flexi_balances_are_tracked_per_asset() {
+    let s = setup();
+    let user = funded_user(&s, 1_000, 5_000);
+
+    s.client.deposit_flexi(&user, &600);
+    s.client.deposit_flexi_asset(&user, &s.xlm.address, &2_000);
+
+    assert_eq!(s.client.get_flexi_balance(&user), 600);
+    assert_eq!(
+        s.client.get_flexi_asset_balance(&user, &s.usdc.address),
+        600
+    );
+    assert_eq!(
+        s.client.get_flexi_asset_balance(&user, &s.xlm.address),
+        2_000
+    );
+    assert_eq!(s.usdc.balance(&s.client.address), 600);
+    assert_eq!(s.xlm.balance(&s.client.address), 2_000);
+
+    s.client.withdraw_flexi_asset(&user, &s.xlm.address, &500);
+    assert_eq!(s.xlm.balance(&user), 3_500);
+    assert_eq!(
+        s.client.get_flexi_asset_balance(&user, &s.xlm.address),
+        1_500
+    );
+    assert_eq!(s.client.get_flexi_balance(&user), 600);
+}
+
+#[test]
+fn test_flexi_emergency_withdraw_covers_every_asset() {
+    let s = setup();
+    let user = funded_user(&s, 1_000, 5_000);
+
+    s.client.deposit_flexi(&user, &600);
+    s.client.deposit_flexi_asset(&user, &s.xlm.address, &2_000);
+
+    let withdrawn = s
+        .client
+        .emergency_withdraw(&s.admin, &user, &PlanType::Flexi, &0);
+    assert_eq!(withdrawn, 2_600);
+    assert_eq!(s.usdc.balance(&user), 1_000);
+    assert_eq!(s.xlm.balance(&user), 5_000);
+    assert_eq!(s.client.get_flexi_asset_balance(&user, &s.xlm.address), 0);
+    assert_eq!(s.client.get_flexi_balance(&user), 0);
+}
+
+#[test]
+fn test_removed_asset_blocks_deposits_but_allows_withdrawals() {
+    let s = setup();
+    let user = funded_user(&s, 0, 1_000);
+
+    s.client.deposit_flexi_asset(&user, &s.xlm.address, &400);
+    s.client.remove_supported_asset(&s.admin, &s.xlm.address);
+
+    assert!(!s.client.is_asset_supported(&s.xlm.address));
+    let result = s
+        .client
+        .try_deposit_flexi_asset(&user, &s.xlm.address, &100);
+    assert_eq!(result, Err(Ok(SavingsError::UnsupportedAsset)));
+
+    s.client.withdraw_flexi_asset(&user, &s.xlm.address, &400);
+    assert_eq!(s.xlm.balance(&user), 1_000);
+}
+
+#[test]
+fn test_base_asset_cannot_be_removed() {
+    let s = setup();
+    let result = s
+        .client
+        .try_remove_supported_asset(&s.admin, &s.usdc.address);
+    assert_eq!(result, Err(Ok(SavingsError::UnsupportedAsset)));
+}
+
+#[test]
+fn test_lock_save_in_asset_pays_out_same_asset() {
+    let s = setup();
+    let user = funded_user(&s, 0, 10_000);
+    let duration = 30 * 86_400;
+    s.client
+        .set_lock_rate_for(&s.admin, &s.xlm.address, &30, &500);
+
+    let lock_id = s
+        .client
+        .create_asset_lock_save(&user, &s.xlm.address, &10_000, &duration);
+    assert_eq!(s.xlm.balance(&s.client.address), 10_000);
+
+    // Liquidity for accrued interest
+    s.xlm_admin.mint(&s.client.address, &1_000);
+    s.env.ledger().with_mut(|li| li.timestamp += duration);
+
+    let paid = s.client.withdraw_lock_save(&user, &lock_id);
+    assert_eq!(s.xlm.balance(&user), paid);
+    assert_eq!(s.usdc.balance(&user), 0);
+}
+
+#[test]
+fn test_goal_save_in_asset() {
+    let s = setup();
+    let user = funded_user(&s, 0, 3_000);
+
+    let goal_id = s.client.create_asset_goal_save(
+        &user,
+        &s.xlm.address,
+        &Symbol::new(&s.env, "bike"),
+        &3_000,
+        &1_000,
+    );
+    s.client.deposit_to_goal_save(&user, &goal_id, &2_000);
+    assert_eq!(s.client.get_goal_save_detail(&goal_id).asset, s.xlm.address);
+
+    let paid = s.client.withdraw_completed_goal_save(&user, &goal_id);
+    assert_eq!(paid, 3_000);
+    assert_eq!(s.xlm.balance(&user), 3_000);
+}
+
+#[test]
+fn test_group_asset_locked_after_first_contribution() {
+    let s = setup();
+    let creator = funded_user(&s, 0, 1_000);
+
+    let group_id = s.client.create_group_save(
+        &creator,
+        &String::from_str(&s.env, "Trip"),
+        &String::from_str(&s.env, "Pool"),
+        &String::from_str(&s.env, "travel"),
+        &5_000,
+        &1,
+        &100,
+        &true,
+        &0,
+        &1_000_000,
+    );
+    s.client
+        .set_group_asset(&creator, &group_id, &s.xlm.address);
+    s.client.contribute_to_group_save(&creator, &group_id, &300);
+    assert_eq!(s.xlm.balance(&s.client.address), 300);
+
+    let result = s
+        .client
+        .try_set_group_asset(&creator, &group_id, &s.usdc.address);
+    assert_eq!(result, Err(Ok(SavingsError::InvalidGroupConfig)));
+}
+
+#[test]
+fn test_rates_are_independent_per_asset() {
+    let s = setup();
+
+    s.client.set_flexi_rate(&s.admin, &400);
+    s.client.set_flexi_rate_for(&s.admin, &s.xlm.address, &900);
+    s.client
+        .set_lock_rate_for(&s.admin, &s.xlm.address, &30, &1_200);
+
+    assert_eq!(s.client.get_flexi_rate(), 400);
+    assert_eq!(s.client.get_flexi_rate_for(&s.usdc.address), 400);
+    assert_eq!(s.client.get_flexi_rate_for(&s.xlm.address), 900);
+    assert_eq!(s.client.get_lock_rate_for(&s.xlm.address, &30), 1_200);
+    assert_eq!(
+        s.client.try_get_lock_rate(&30),
+        Err(Ok(SavingsError::PlanNotFound))
+    );
+}
+
+#[test]
+fn test_user_asset_totals() {
+    let s = setup();
+    let user = funded_user(&s, 5_000, 5_000);
+
+    s.client.deposit_flexi(&user, &1_000);
+    s.client.deposit_flexi_asset(&user, &s.xlm.address, &500);
+    s.client
+        .set_lock_rate_for(&s.admin, &s.xlm.address, &1, &500);
+    s.client
+        .create_asset_lock_save(&user, &s.xlm.address, &2_000, &86_400);
+    s.client
+        .create_goal_save(&user, &Symbol::new(&s.env, "car"), &10_000, &1_500);
+
+    let totals = s.client.get_user_asset_totals(&user);
+    assert_eq!(totals.len(), 2);
+
+    let usdc = totals.get(0).unwrap();
+    assert_eq!(usdc.asset, s.usdc.address);
+    assert_eq!(usdc.flexi_balance, 1_000);
+    assert_eq!(usdc.lock_balance, 0);
+    assert_eq!(usdc.goal_balance, 1_500);
+
+    let xlm = totals.get(1).unwrap();
+    assert_eq!(xlm.asset, s.xlm.address);
+    assert_eq!(xlm.flexi_balance, 500);
+    assert_eq!(xlm.lock_balance, 2_000);
+    assert_eq!(xlm.goal_balance, 0);
+    assert_eq!(xlm.group_balance, 0);
+}
@@ -0,0 +1,293 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String,
+};
+
+use Nestera::{NesteraContract, NesteraContractClient, SavingsError};
+
+const CYCLE: u64 = 7 * 86_400;
+const CONTRIBUTION: i128 = 100;
+
+struct Setup {
+    env: Env,
+    client: NesteraContractClient<'static>,
+    token: TokenClient<'static>,
+    group_id: u64,
+    members: [Address; 3],
+}
+
+/// A public group of three funded members, not yet rotating.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    client.set_underlying_asset(&admin, &sac.address());
+    let token_admin = StellarAssetClient::new(&env, &sac.address());
+
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for member in members.iter() {
+        client.initialize_user(member);
+        token_admin.mint(member, &1_000);
+    }
+
+    let group_id = client.create_group_save(
+        &members[0],
+        &String::from_str(&env, "Ajo"),
+        &String::from_str(&env, "Weekly rotation"),
+        &String::from_str(&env, "rosca"),
+        &300,
+        &0,
+        &CONTRIBUTION,
+        &true,
+        &env.ledger().timestamp(),
+        &(env.ledger().timestamp() + 3 * CYCLE),
+    );
+    client.join_group_save(&members[1], &group_id);
+    client.join_group_save(&members[2], &group_id);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        env,
+        client,
+        group_id,
+        members,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_start_rotation_freezes_member_order() {
+    let s = setup();
+    let rotation = s
+        .client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    assert_eq!(rotation.total_rounds, 3);
+    assert_eq!(rotation.contribution_amount, CONTRIBUTION);
+
+    let order = s.client.get_group_payout_order(&s.group_id);
+    for (i, member) in s.members.iter().enumerate() {
+        assert_eq!(order.get(i as u32).unwrap(), *member);
+    }
+
+    let round = s.client.get_group_current_round(&s.group_id);
+    assert_eq!(round.round, 0);
+    assert_eq!(round.recipient, s.members[0]);
+    assert_eq!(round.member_count, 3);
+}
+
+#[test]
+fn test_start_rotation_requires_creator() {
+    let s = setup();
+    let result = s
+        .client
+        .try_start_group_rotation(&s.members[1], &s.group_id, &CYCLE, &false);
+    assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+}
+
+#[test]
+fn test_randomized_order_is_a_permutation_of_members() {
+    let s = setup();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &true);
+
+    let order = s.client.get_group_payout_order(&s.group_id);
+    assert_eq!(order.len(), 3);
+    for member in s.members.iter() {
+        assert!(order.contains(member));
+    }
+}
+
+#[test]
+fn test_rotating_group_blocks_regular_flows() {
+    let s = setup();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    let late = Address::generate(&s.env);
+    s.client.initialize_user(&late);
+    assert_eq!(
+        s.client.try_join_group_save(&late, &s.group_id),
+        Err(Ok(SavingsError::InvalidGroupConfig))
+    );
+    assert_eq!(
+        s.client
+            .try_contribute_to_group_save(&s.members[1], &s.group_id, &50),
+        Err(Ok(SavingsError::InvalidGroupConfig))
+    );
+    assert_eq!(
+        s.client.try_break_group_save(&s.members[1], &s.group_id),
+        Err(Ok(SavingsError::InvalidGroupConfig))
+    );
+}
+
+#[test]
+fn test_full_rotation_pays_each_member_once() {
+    let s = setup();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    for round in 0..3u32 {
+        for member in s.members.iter() {
+            assert_eq!(s.client.contribute_to_round(member, &s.group_id), round);
+        }
+        assert_eq!(s.client.close_group_round(&s.group_id), 300);
+    }
+
+    for member in s.members.iter() {
+        assert_eq!(s.token.balance(member), 1_000);
+    }
+    assert!(
+        s.client
+            .get_group_rotation(&s.group_id)
+            .unwrap()
+            .is_finished
+    );
+    assert!(s.client.get_group_save(&s.group_id).is_completed);
+    assert_eq!(
+        s.client.try_contribute_to_round(&s.members[0], &s.group_id),
+        Err(Ok(SavingsError::PlanCompleted))
+    );
+}
+
+#[test]
+fn test_round_cannot_close_early_or_take_double_payment() {
+    let s = setup();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    s.client.contribute_to_round(&s.members[0], &s.group_id);
+    assert_eq!(
+        s.client.try_contribute_to_round(&s.members[0], &s.group_id),
+        Err(Ok(SavingsError::RoundAlreadyPaid))
+    );
+    assert_eq!(
+        s.client.try_close_group_round(&s.group_id),
+        Err(Ok(SavingsError::GroupCycleIncomplete))
+    );
+    assert!(s
+        .client
+        .has_paid_group_round(&s.group_id, &0, &s.members[0]));
+    assert!(!s
+        .client
+        .has_paid_group_round(&s.group_id, &0, &s.members[1]));
+}
+
+#[test]
+fn test_missed_payment_is_recorded_and_settled_by_member() {
+    let s = setup();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    s.client.contribute_to_round(&s.members[0], &s.group_id);
+    s.client.contribute_to_round(&s.members[1], &s.group_id);
+    advance(&s.env, CYCLE);
+
+    assert_eq!(
+        s.client.try_contribute_to_round(&s.members[2], &s.group_id),
+        Err(Ok(SavingsError::TooLate))
+    );
+
+    // Round 0 recipient only gets what was collected
+    assert_eq!(s.client.close_group_round(&s.group_id), 200);
+    assert_eq!(s.token.balance(&s.members[0]), 1_100);
+
+    let arrears = s.client.get_group_arrears(&s.group_id, &s.members[2]);
+    assert_eq!(arrears.len(), 1);
+    assert_eq!(arrears.get(0).unwrap(), 0);
+
+    // Paying arrears forwards the missed contribution to round 0's recipient
+    assert_eq!(s.client.pay_group_arrears(&s.members[2], &s.group_id), 100);
+    assert_eq!(s.token.balance(&s.members[0]), 1_200);
+    assert_eq!(s.token.balance(&s.members[2]), 900);
+    assert!(s
+        .client
+        .get_group_arrears(&s.group_id, &s.members[2])
+        .is_empty());
+}
+
+#[test]
+fn test_arrears_are_withheld_from_defaulters_payout() {
+    let s = setup();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    // Member 1 skips round 0
+    s.client.contribute_to_round(&s.members[0], &s.group_id);
+    s.client.contribute_to_round(&s.members[2], &s.group_id);
+    advance(&s.env, CYCLE);
+    s.client.close_group_round(&s.group_id);
+    assert_eq!(s.token.balance(&s.members[0]), 1_100);
+
+    // Round 1 pays member 1, minus the contribution owed to member 0
+    for member in s.members.iter() {
+        s.client.contribute_to_round(member, &s.group_id);
+    }
+    assert_eq!(s.client.close_group_round(&s.group_id), 200);
+    assert_eq!(s.token.balance(&s.members[0]), 1_100);
+    assert_eq!(s.token.balance(&s.members[1]), 1_100);
+    assert!(s
+        .client
+        .get_group_arrears(&s.group_id, &s.members[1])
+        .is_empty());
+}
+
+#[test]
+fn test_next_deadline_runs_from_late_close() {
+    let s = setup();
+    let start = s.env.ledger().timestamp();
+    s.client
+        .start_group_rotation(&s.members[0], &s.group_id, &CYCLE, &false);
+
+    s.client.contribute_to_round(&s.members[0], &s.group_id);
+    advance(&s.env, 2 * CYCLE);
+    s.client.close_group_round(&s.group_id);
+
+    // Round 1 gets a full cycle from the late close, not from the calendar
+    let info = s.client.get_group_current_round(&s.group_id);
+    assert_eq!(info.deadline, start + 3 * CYCLE);
+    advance(&s.env, CYCLE - 1);
+    assert_eq!(s.client.contribute_to_round(&s.members[1], &s.group_id), 1);
+}
+
+#[test]
+fn test_only_fixed_contribution_groups_rotate() {
+    let s = setup();
+    let now = s.env.ledger().timestamp();
+    let flexible_id = s.client.create_group_save(
+        &s.members[0],
+        &String::from_str(&s.env, "Flexible"),
+        &String::from_str(&s.env, "Any amount"),
+        &String::from_str(&s.env, "rosca"),
+        &300,
+        &1,
+        &CONTRIBUTION,
+        &true,
+        &now,
+        &(now + 3 * CYCLE),
+    );
+    s.client.join_group_save(&s.members[1], &flexible_id);
+
+    assert_eq!(
+        s.client
+            .try_start_group_rotation(&s.members[0], &flexible_id, &CYCLE, &false),
+        Err(Ok(SavingsError::InvalidGroupConfig))
+    );
+}
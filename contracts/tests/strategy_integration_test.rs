@@ -2,12 +2,12 @@
 
 use soroban_sdk::{
     contract, contractimpl, testutils::Address as _, testutils::Ledger, Address, BytesN, Env,
-    Symbol,
+    String, Symbol,
 };
 
 use Nestera::strategy::interface::YieldStrategy;
 use Nestera::PlanType;
-use Nestera::{NesteraContract, NesteraContractClient};
+use Nestera::{NesteraContract, NesteraContractClient, SavingsError};
 
 // --- Mock Yield Strategy ---
 
@@ -77,6 +77,9 @@ fn setup_env() -> (
     client.initialize(&admin, &admin_pk);
     client.initialize_config(&admin, &treasury, &1_000u32, &1_000u32, &1_000u32); // 10% fee
 
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
+
     let user1 = Address::generate(&env);
     let strategy_id = env.register(MockYieldStrategy, ());
 
@@ -113,7 +116,9 @@ fn test_strategy_full_lifecycle() {
     });
     assert_eq!(principal, lock_amount);
 
-    let position = client.get_lock_strategy_position(&lock_id).unwrap();
+    let position = client
+        .get_lock_strategy_position(&lock_id, &strategy_id)
+        .unwrap();
     assert_eq!(position.strategy, strategy_id);
     assert_eq!(position.principal_deposited, lock_amount);
 
@@ -159,10 +164,12 @@ fn test_strategy_full_lifecycle() {
     );
 
     // 6. Withdraw user funds (strategy position)
-    let withdrawn_from_strat = client.withdraw_lock_strategy(&user1, &lock_id, &user1);
+    let withdrawn_from_strat = client.withdraw_lock_strategy(&user1, &lock_id, &strategy_id);
     assert_eq!(withdrawn_from_strat, lock_amount);
 
-    let empty_position = client.get_lock_strategy_position(&lock_id).unwrap();
+    let empty_position = client
+        .get_lock_strategy_position(&lock_id, &strategy_id)
+        .unwrap();
     assert_eq!(empty_position.principal_deposited, 0);
     let new_principal = env.as_contract(&client.address, || {
         let key = Nestera::DataKey::StrategyTotalPrincipal(strategy_id.clone());
@@ -170,10 +177,16 @@ fn test_strategy_full_lifecycle() {
     });
     assert_eq!(new_principal, 0);
 
-    // 7. Advance time & withdraw lock completely
+    // 7. Advance time & withdraw lock completely, once the harvested yield
+    // left on the position is claimed
     env.ledger().with_mut(|li| {
         li.timestamp += lock_duration + 1;
     });
+    assert!(client.try_withdraw_lock_save(&user1, &lock_id).is_err());
+    assert_eq!(
+        client.claim_lock_strategy_yield(&user1, &lock_id, &user1),
+        900
+    );
 
     let final_returned = client.withdraw_lock_save(&user1, &lock_id);
     // Lock save yield is calculated off lock rate config, not directly 1:1 with strategy yield,
@@ -206,7 +219,7 @@ fn test_governance_disable_strategy() {
 
 #[test]
 fn test_emergency_withdraw_scenario() {
-    let (_env, client, admin, user1, _treasury, strategy_id) = setup_env();
+    let (env, client, admin, user1, _treasury, strategy_id) = setup_env();
 
     // 1. Register & Route
     client.register_strategy(&admin, &strategy_id, &1u32);
@@ -216,13 +229,311 @@ fn test_emergency_withdraw_scenario() {
     let lock_id = client.create_lock_save(&user1, &10_000, &30);
     client.route_lock_to_strategy(&user1, &lock_id, &strategy_id, &10_000);
 
-    // 2. Trigger Emergency Withdraw (Governance action affecting Nestera lock_save)
+    // 2. Funds still in the strategy can't be paid out of custody
+    assert_eq!(
+        client.try_emergency_withdraw(&admin, &user1, &PlanType::Lock(lock_id), &lock_id),
+        Err(Ok(SavingsError::InvalidPlanConfig))
+    );
+    client.withdraw_lock_strategy(&user1, &lock_id, &strategy_id);
+
+    // Plans are only paid out to their owner
+    let stranger = Address::generate(&env);
+    client.initialize_user(&stranger);
+    assert_eq!(
+        client.try_emergency_withdraw(&admin, &stranger, &PlanType::Lock(lock_id), &lock_id),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    let goal_id = client.create_goal_save(&user1, &Symbol::new(&env, "car"), &5_000, &1_000);
+    let goal_plan = PlanType::Goal(Symbol::new(&env, "car"), 5_000, 0);
+    assert_eq!(
+        client.try_emergency_withdraw(&admin, &stranger, &goal_plan, &goal_id),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+
+    // 3. Trigger Emergency Withdraw (Governance action affecting Nestera lock_save)
     let withdrawn = client.emergency_withdraw(&admin, &user1, &PlanType::Lock(lock_id), &lock_id);
     assert_eq!(withdrawn, 10_000); // 10_000 lock amount
 
-    // 3. Ensure plan is disabled & withdrawn
+    // 4. Ensure plan is disabled & withdrawn
     assert!(client.is_strategy_disabled(&PlanType::Lock(lock_id), &lock_id));
 
     let res = client.try_withdraw_lock_save(&user1, &lock_id);
     assert!(res.is_err(), "already withdrawn");
 }
+
+/// Registers the mock strategy and creates a 30-day lock for each amount.
+fn route_locks(
+    env: &Env,
+    client: &NesteraContractClient<'static>,
+    admin: &Address,
+    strategy_id: &Address,
+    amounts: &[i128],
+) -> (Address, [u64; 2]) {
+    client.register_strategy(admin, strategy_id, &1u32);
+
+    let owner = Address::generate(env);
+    client.initialize_user(&owner);
+    let mut lock_ids = [0u64; 2];
+    for (i, amount) in amounts.iter().enumerate() {
+        lock_ids[i] = client.create_lock_save(&owner, amount, &(30 * 86400));
+        client.route_lock_to_strategy(&owner, &lock_ids[i], strategy_id, amount);
+    }
+    (owner, lock_ids)
+}
+
+#[test]
+fn test_harvest_is_split_pro_rata_across_positions() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (owner, [a, b]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000, 30_000]);
+
+    MockYieldStrategyClient::new(&env, &strategy_id).simulate_yield(&4_000);
+    client.harvest_strategy(&admin, &strategy_id);
+
+    // 3,600 user yield after the 10% performance fee, split 1:3
+    assert_eq!(
+        client
+            .get_lock_strategy_value(&a, &strategy_id)
+            .unwrap()
+            .pending_yield,
+        900
+    );
+    assert_eq!(
+        client
+            .get_lock_strategy_value(&b, &strategy_id)
+            .unwrap()
+            .pending_yield,
+        2_700
+    );
+
+    assert_eq!(client.claim_lock_strategy_yield(&owner, &a, &owner), 900);
+    assert_eq!(client.claim_lock_strategy_yield(&owner, &a, &owner), 0);
+    assert_eq!(
+        client
+            .get_lock_strategy_value(&b, &strategy_id)
+            .unwrap()
+            .pending_yield,
+        2_700
+    );
+}
+
+#[test]
+fn test_late_position_does_not_share_earlier_profit() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (owner, [a, _]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000]);
+    let mock = MockYieldStrategyClient::new(&env, &strategy_id);
+
+    // Unharvested profit is not part of a position's value
+    mock.simulate_yield(&1_000);
+    assert_eq!(
+        client
+            .get_lock_strategy_value(&a, &strategy_id)
+            .unwrap()
+            .current_value,
+        10_000
+    );
+
+    // Routing harvests it for the existing position before minting B's shares
+    let b = client.create_lock_save(&owner, &11_000, &(30 * 86400));
+    client.route_lock_to_strategy(&owner, &b, &strategy_id, &11_000);
+    let early = client.get_lock_strategy_value(&a, &strategy_id).unwrap();
+    let late = client.get_lock_strategy_value(&b, &strategy_id).unwrap();
+    assert_eq!(early.pending_yield, 900);
+    assert_eq!(late.shares, 11_000);
+    assert_eq!(late.pending_yield, 0);
+
+    // Later profit splits by shares, 10:11
+    mock.simulate_yield(&2_100);
+    client.harvest_strategy(&admin, &strategy_id);
+    assert_eq!(
+        client
+            .get_lock_strategy_value(&a, &strategy_id)
+            .unwrap()
+            .pending_yield,
+        1_800
+    );
+    assert_eq!(
+        client
+            .get_lock_strategy_value(&b, &strategy_id)
+            .unwrap()
+            .pending_yield,
+        990
+    );
+
+    // Each redeems exactly its principal
+    assert_eq!(
+        client.withdraw_lock_strategy(&owner, &a, &strategy_id),
+        10_000
+    );
+    assert_eq!(
+        client.withdraw_lock_strategy(&owner, &b, &strategy_id),
+        11_000
+    );
+    assert_eq!(
+        client.get_strategy_performance(&strategy_id).total_losses,
+        0
+    );
+}
+
+#[test]
+fn test_withdraw_keeps_harvested_yield_claimable() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (owner, [a, _]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000]);
+
+    MockYieldStrategyClient::new(&env, &strategy_id).simulate_yield(&1_000);
+    client.harvest_strategy(&admin, &strategy_id);
+
+    assert_eq!(
+        client.withdraw_lock_strategy(&owner, &a, &strategy_id),
+        10_000
+    );
+    let position = client.get_lock_strategy_value(&a, &strategy_id).unwrap();
+    assert_eq!(position.shares, 0);
+    assert_eq!(position.current_value, 0);
+    assert_eq!(position.pending_yield, 900);
+    assert_eq!(client.claim_lock_strategy_yield(&owner, &a, &owner), 900);
+}
+
+#[test]
+fn test_only_plan_owner_can_claim_strategy_yield() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (_owner, [a, _]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000]);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_claim_lock_strategy_yield(&stranger, &a, &stranger),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_only_plan_owner_can_withdraw_strategy_position() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (owner, [a, _]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000]);
+
+    let now = env.ledger().timestamp();
+    let group_id = client.create_group_save(
+        &owner,
+        &String::from_str(&env, "Pool"),
+        &String::from_str(&env, "Shared savings"),
+        &String::from_str(&env, "general"),
+        &5_000,
+        &0u32,
+        &500,
+        &true,
+        &now,
+        &(now + 86400),
+    );
+    client.contribute_to_group_save(&owner, &group_id, &500);
+    client.route_group_to_strategy(&owner, &group_id, &strategy_id, &500);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_withdraw_lock_strategy(&stranger, &a, &strategy_id),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_withdraw_lock_strategy_amount(&stranger, &a, &strategy_id, &1),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_withdraw_group_strategy(&stranger, &group_id, &strategy_id),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_withdraw_group_strategy_amount(&stranger, &group_id, &strategy_id, &1),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+
+    assert_eq!(
+        client.withdraw_group_strategy_amount(&owner, &group_id, &strategy_id, &200),
+        200
+    );
+}
+
+#[test]
+fn test_plan_holds_positions_in_several_strategies() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    client.register_strategy(&admin, &strategy_id, &1u32);
+    let owner = Address::generate(&env);
+    client.initialize_user(&owner);
+    let a = client.create_lock_save(&owner, &14_000, &(30 * 86400));
+    client.route_lock_to_strategy(&owner, &a, &strategy_id, &10_000);
+
+    let other = env.register(MockYieldStrategy, ());
+    client.register_strategy(&admin, &other, &1u32);
+    client.route_lock_to_strategy(&owner, &a, &other, &4_000);
+
+    let positions = client.get_lock_strategy_positions(&a);
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions.get(0).unwrap().strategy, strategy_id);
+    assert_eq!(positions.get(1).unwrap().strategy, other);
+
+    // Emptying one position drops it from the plan's list
+    assert_eq!(client.withdraw_lock_strategy(&owner, &a, &other), 4_000);
+    assert!(client.get_lock_strategy_position(&a, &other).is_none());
+    assert_eq!(client.get_lock_strategy_positions(&a).len(), 1);
+}
+
+#[test]
+fn test_routing_again_tops_up_existing_position() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    client.register_strategy(&admin, &strategy_id, &1u32);
+    let owner = Address::generate(&env);
+    client.initialize_user(&owner);
+    let a = client.create_lock_save(&owner, &15_000, &(30 * 86400));
+
+    client.route_lock_to_strategy(&owner, &a, &strategy_id, &10_000);
+    client.route_lock_to_strategy(&owner, &a, &strategy_id, &5_000);
+    assert_eq!(
+        client.try_route_lock_to_strategy(&owner, &a, &strategy_id, &1),
+        Err(Ok(SavingsError::InsufficientBalance))
+    );
+
+    let positions = client.get_lock_strategy_positions(&a);
+    assert_eq!(positions.len(), 1);
+    let position = positions.get(0).unwrap();
+    assert_eq!(position.principal_deposited, 15_000);
+    assert_eq!(position.shares, 15_000);
+    assert_eq!(position.strategy_shares, 15_000);
+}
+
+#[test]
+fn test_partial_withdrawal_by_amount() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (owner, [a, _]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000]);
+
+    assert_eq!(
+        client.withdraw_lock_strategy_amount(&owner, &a, &strategy_id, &4_000),
+        4_000
+    );
+    let position = client.get_lock_strategy_position(&a, &strategy_id).unwrap();
+    assert_eq!(position.principal_deposited, 6_000);
+    assert_eq!(position.shares, 6_000);
+
+    let principal = env.as_contract(&client.address, || {
+        let key = Nestera::DataKey::StrategyTotalPrincipal(strategy_id.clone());
+        env.storage().persistent().get::<_, i128>(&key).unwrap_or(0)
+    });
+    assert_eq!(principal, 6_000);
+
+    assert_eq!(
+        client.try_withdraw_lock_strategy_amount(&owner, &a, &strategy_id, &6_001),
+        Err(Ok(SavingsError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_partial_withdrawal_harvests_profit_first() {
+    let (env, client, admin, _user1, _treasury, strategy_id) = setup_env();
+    let (owner, [a, _]) = route_locks(&env, &client, &admin, &strategy_id, &[10_000]);
+
+    // The 2,000 profit is harvested as yield, so shares redeem at 1:1
+    MockYieldStrategyClient::new(&env, &strategy_id).simulate_yield(&2_000);
+    client.withdraw_lock_strategy_amount(&owner, &a, &strategy_id, &6_000);
+
+    let value = client.get_lock_strategy_value(&a, &strategy_id).unwrap();
+    assert_eq!(value.shares, 4_000);
+    assert_eq!(value.principal_deposited, 4_000);
+    assert_eq!(value.current_value, 4_000);
+    assert_eq!(value.pending_yield, 1_800);
+}
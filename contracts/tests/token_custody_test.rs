@@ -0,0 +1,543 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String, Symbol,
+};
+
+use Nestera::strategy::interface::YieldStrategy;
+use Nestera::{NesteraContract, NesteraContractClient, SavingsError};
+
+/// Strategy holding real tokens: deposits arrive before `strategy_deposit`
+/// and anything above the principal is yield.
+#[contract]
+pub struct TokenStrategy;
+
+fn principal(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, "principal"))
+        .unwrap_or(0)
+}
+
+fn set_principal(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, "principal"), &amount);
+}
+
+fn strategy_token(env: &Env) -> TokenClient<'_> {
+    let token: Address = env
+        .storage()
+        .instance()
+        .get(&Symbol::new(env, "token"))
+        .unwrap();
+    TokenClient::new(env, &token)
+}
+
+#[contractimpl]
+impl TokenStrategy {
+    pub fn __constructor(env: Env, token: Address) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "token"), &token);
+    }
+
+    /// Burns `amount` of the held tokens, as a strategy loss
+    pub fn lose(env: Env, amount: i128) {
+        set_principal(&env, principal(&env) - amount);
+        strategy_token(&env).burn(&env.current_contract_address(), &amount);
+    }
+}
+
+#[contractimpl]
+impl YieldStrategy for TokenStrategy {
+    fn strategy_deposit(env: Env, _from: Address, amount: i128) -> i128 {
+        let held = strategy_token(&env).balance(&env.current_contract_address());
+        assert!(held >= principal(&env) + amount, "deposit not transferred");
+        set_principal(&env, principal(&env) + amount);
+        amount
+    }
+
+    fn strategy_withdraw(env: Env, to: Address, amount: i128) -> i128 {
+        set_principal(&env, principal(&env) - amount);
+        strategy_token(&env).transfer(&env.current_contract_address(), &to, &amount);
+        amount
+    }
+
+    fn strategy_harvest(env: Env, to: Address) -> i128 {
+        let token = strategy_token(&env);
+        let profit = token.balance(&env.current_contract_address()) - principal(&env);
+        if profit > 0 {
+            token.transfer(&env.current_contract_address(), &to, &profit);
+        }
+        profit.max(0)
+    }
+
+    fn strategy_balance(env: Env, _addr: Address) -> i128 {
+        strategy_token(&env).balance(&env.current_contract_address())
+    }
+}
+
+fn setup_env() -> (
+    Env,
+    NesteraContractClient<'static>,
+    Address,
+    TokenClient<'static>,
+    StellarAssetClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
+    client.initialize(&admin, &admin_pk);
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &sac.address());
+    let token_admin = StellarAssetClient::new(&env, &sac.address());
+    client.set_underlying_asset(&admin, &sac.address());
+    // A catch-all lock rate tier so locks of any duration can open
+    client.set_lock_rate(&admin, &0, &500);
+
+    (env, client, admin, token, token_admin)
+}
+
+fn funded_user(
+    env: &Env,
+    client: &NesteraContractClient<'_>,
+    token_admin: &StellarAssetClient<'_>,
+    amount: i128,
+) -> Address {
+    let user = Address::generate(env);
+    client.initialize_user(&user);
+    token_admin.mint(&user, &amount);
+    user
+}
+
+/// Lets the contract pull `amount` from `user` for scheduled deposits.
+fn approve(
+    env: &Env,
+    client: &NesteraContractClient<'_>,
+    token: &TokenClient<'_>,
+    user: &Address,
+    amount: i128,
+) {
+    let expiry = env.ledger().sequence() + 100_000;
+    token.approve(user, &client.address, &amount, &expiry);
+}
+
+#[test]
+fn test_underlying_asset_is_exposed_in_config() {
+    let (env, client, admin, token, _) = setup_env();
+    let treasury = Address::generate(&env);
+    client.initialize_config(&admin, &treasury, &0, &0, &0);
+
+    assert_eq!(client.get_underlying_asset(), Some(token.address.clone()));
+    assert_eq!(client.get_config().underlying_asset, Some(token.address));
+}
+
+#[test]
+fn test_underlying_asset_can_only_be_set_once() {
+    let (env, client, admin, _, _) = setup_env();
+    let other = env.register_stellar_asset_contract_v2(admin.clone());
+
+    let result = client.try_set_underlying_asset(&admin, &other.address());
+    assert_eq!(result, Err(Ok(SavingsError::ConfigAlreadyInitialized)));
+}
+
+#[test]
+fn test_underlying_asset_cannot_replace_accounting_balances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(NesteraContract, ());
+    let client = NesteraContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+
+    // Deposits made in accounting-only mode are not backed by any token
+    let user = Address::generate(&env);
+    client.initialize_user(&user);
+    client.deposit_flexi(&user, &1_000);
+
+    let token = env.register_stellar_asset_contract_v2(admin.clone());
+    let result = client.try_set_underlying_asset(&admin, &token.address());
+    assert_eq!(result, Err(Ok(SavingsError::ConfigAlreadyInitialized)));
+    assert_eq!(client.get_underlying_asset(), None);
+}
+
+#[test]
+fn test_flexi_deposit_and_withdraw_move_tokens() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 10_000);
+
+    client.deposit_flexi(&user, &4_000);
+    assert_eq!(token.balance(&user), 6_000);
+    assert_eq!(token.balance(&client.address), 4_000);
+    assert_eq!(client.get_flexi_balance(&user), 4_000);
+
+    client.withdraw_flexi(&user, &1_500);
+    assert_eq!(token.balance(&user), 7_500);
+    assert_eq!(token.balance(&client.address), 2_500);
+    assert_eq!(client.get_flexi_balance(&user), 2_500);
+}
+
+#[test]
+fn test_flexi_withdraw_fee_stays_in_custody() {
+    let (env, client, admin, token, token_admin) = setup_env();
+    let treasury = Address::generate(&env);
+    client.initialize_config(&admin, &treasury, &0, &1_000, &0); // 10% withdrawal fee
+    client.set_fee_recipient(&treasury);
+    let user = funded_user(&env, &client, &token_admin, 10_000);
+
+    client.deposit_flexi(&user, &10_000);
+    client.withdraw_flexi(&user, &10_000);
+
+    assert_eq!(token.balance(&user), 9_000);
+    assert_eq!(token.balance(&client.address), 1_000);
+    assert_eq!(client.get_protocol_fee_balance(&treasury), 1_000);
+}
+
+#[test]
+fn test_flexi_deposit_without_funds_fails() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 100);
+
+    assert!(client.try_deposit_flexi(&user, &500).is_err());
+    assert_eq!(token.balance(&user), 100);
+    assert_eq!(client.get_flexi_balance(&user), 0);
+}
+
+#[test]
+fn test_lock_save_custody_round_trip() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 10_000);
+    let duration = 30 * 86_400;
+
+    let lock_id = client.create_lock_save(&user, &10_000, &duration);
+    assert_eq!(token.balance(&user), 0);
+    assert_eq!(token.balance(&client.address), 10_000);
+
+    // Interest is paid only as far as the reserve covers it
+    env.ledger().with_mut(|li| li.timestamp += duration);
+    assert_eq!(client.withdraw_lock_save(&user, &lock_id), 10_000);
+    assert_eq!(token.balance(&user), 10_000);
+    assert_eq!(token.balance(&client.address), 0);
+    let unpaid = client.get_unpaid_lock_interest(&lock_id);
+    assert!(unpaid > 0);
+    assert_eq!(client.claim_lock_interest(&user, &lock_id), 0);
+
+    let funder = funded_user(&env, &client, &token_admin, 1_000);
+    client.fund_reserve(&funder, &1_000);
+    assert_eq!(client.claim_lock_interest(&user, &lock_id), unpaid);
+    assert_eq!(client.get_unpaid_lock_interest(&lock_id), 0);
+    assert_eq!(token.balance(&user), 10_000 + unpaid);
+    assert_eq!(token.balance(&client.address), 1_000 - unpaid);
+    assert_eq!(client.get_reserve_balance(), 1_000 - unpaid);
+}
+
+#[test]
+fn test_lock_interest_is_drawn_from_the_reserve() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let funder = funded_user(&env, &client, &token_admin, 1_000);
+    client.fund_reserve(&funder, &1_000);
+    let user = funded_user(&env, &client, &token_admin, 10_000);
+    let duration = 30 * 86_400;
+    let lock_id = client.create_lock_save(&user, &10_000, &duration);
+
+    env.ledger().with_mut(|li| li.timestamp += duration);
+    let paid = client.withdraw_lock_save(&user, &lock_id);
+
+    assert!(paid > 10_000);
+    assert_eq!(client.get_unpaid_lock_interest(&lock_id), 0);
+    assert_eq!(client.get_reserve_balance(), 11_000 - paid);
+    assert_eq!(token.balance(&client.address), 11_000 - paid);
+}
+
+#[test]
+fn test_goal_save_custody_on_withdraw() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 5_000);
+
+    let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "laptop"), &5_000, &2_000);
+    client.deposit_to_goal_save(&user, &goal_id, &3_000);
+    assert_eq!(token.balance(&user), 0);
+    assert_eq!(token.balance(&client.address), 5_000);
+
+    let paid = client.withdraw_completed_goal_save(&user, &goal_id);
+    assert_eq!(paid, 5_000);
+    assert_eq!(token.balance(&user), 5_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_goal_save_break_pays_net_of_penalty() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 4_000);
+    client.set_early_break_fee_bps(&500); // 5%
+
+    let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "trip"), &10_000, &4_000);
+    let paid = client.break_goal_save(&user, &goal_id);
+
+    assert_eq!(paid, 3_800);
+    assert_eq!(token.balance(&user), 3_800);
+    assert_eq!(token.balance(&client.address), 200);
+}
+
+#[test]
+fn test_group_contribution_and_break_refund() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let creator = funded_user(&env, &client, &token_admin, 1_000);
+    let member = funded_user(&env, &client, &token_admin, 1_000);
+
+    let group_id = client.create_group_save(
+        &creator,
+        &String::from_str(&env, "Savers"),
+        &String::from_str(&env, "Pool"),
+        &String::from_str(&env, "general"),
+        &10_000,
+        &1,
+        &100,
+        &true,
+        &0,
+        &1_000_000,
+    );
+    client.join_group_save(&member, &group_id);
+
+    client.contribute_to_group_save(&creator, &group_id, &600);
+    client.contribute_to_group_save(&member, &group_id, &400);
+    assert_eq!(token.balance(&client.address), 1_000);
+
+    client.break_group_save(&member, &group_id);
+    assert_eq!(token.balance(&member), 1_000);
+    assert_eq!(token.balance(&creator), 400);
+    assert_eq!(token.balance(&client.address), 600);
+}
+
+#[test]
+fn test_autosave_batch_skips_underfunded_user() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let funded = funded_user(&env, &client, &token_admin, 1_000);
+    let broke = funded_user(&env, &client, &token_admin, 10);
+    let unapproved = funded_user(&env, &client, &token_admin, 1_000);
+    approve(&env, &client, &token, &funded, 1_000);
+    approve(&env, &client, &token, &broke, 1_000);
+
+    let now = env.ledger().timestamp();
+    let s1 = client.create_autosave(&funded, &500, &86_400, &now);
+    let s2 = client.create_autosave(&broke, &500, &86_400, &now);
+    let s3 = client.create_autosave(&unapproved, &500, &86_400, &now);
+
+    let results = client.execute_due_autosaves(&soroban_sdk::vec![&env, s1, s2, s3]);
+    assert!(results.get(0).unwrap());
+    assert!(!results.get(1).unwrap());
+    assert!(!results.get(2).unwrap());
+
+    assert_eq!(token.balance(&funded), 500);
+    assert_eq!(token.balance(&broke), 10);
+    assert_eq!(token.balance(&unapproved), 1_000);
+    assert_eq!(token.balance(&client.address), 500);
+}
+
+#[test]
+fn test_keeper_fee_paid_from_funded_balance() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 1_000);
+    approve(&env, &client, &token, &user, 1_000);
+    let keeper = Address::generate(&env);
+
+    let now = env.ledger().timestamp();
+    let id = client.create_autosave(&user, &300, &86_400, &now);
+    client.set_autosave_keeper_fee(&user, &id, &15);
+    assert_eq!(client.fund_autosave_keeper(&user, &id, &20), 20);
+
+    let results = client.execute_autosaves_as_keeper(&keeper, &soroban_sdk::vec![&env, id]);
+    assert!(results.get(0).unwrap());
+    assert_eq!(token.balance(&keeper), 15);
+    assert_eq!(client.get_autosave(&id).unwrap().keeper_balance, 5);
+
+    // Balance no longer covers the fee: the schedule still runs, unpaid
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    let results = client.execute_autosaves_as_keeper(&keeper, &soroban_sdk::vec![&env, id]);
+    assert!(results.get(0).unwrap());
+    assert_eq!(token.balance(&keeper), 15);
+    assert_eq!(client.get_flexi_balance(&user), 600);
+
+    // Cancelling refunds the unspent keeper balance
+    client.cancel_autosave(&user, &id);
+    assert_eq!(token.balance(&user), 1_000 - 600 - 15);
+    assert_eq!(client.get_autosave(&id).unwrap().keeper_balance, 0);
+}
+
+#[test]
+fn test_keeper_fee_rejects_foreign_schedule_and_bad_amounts() {
+    let (env, client, _, _, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 1_000);
+    let other = funded_user(&env, &client, &token_admin, 1_000);
+    let id = client.create_autosave(&user, &300, &86_400, &env.ledger().timestamp());
+
+    assert_eq!(
+        client.try_set_autosave_keeper_fee(&other, &id, &10),
+        Err(Ok(SavingsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_autosave_keeper_fee(&user, &id, &-1),
+        Err(Ok(SavingsError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_fund_autosave_keeper(&user, &id, &0),
+        Err(Ok(SavingsError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_keeper_runs_schedule_without_saver_signature() {
+    let (env, client, _, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 1_000);
+    approve(&env, &client, &token, &user, 1_000);
+    let keeper = Address::generate(&env);
+
+    let now = env.ledger().timestamp();
+    let id = client.create_autosave(&user, &300, &86_400, &now);
+    client.set_autosave_keeper_fee(&user, &id, &10);
+    client.fund_autosave_keeper(&user, &id, &50);
+
+    // Nobody signs from here on
+    env.set_auths(&[]);
+    assert!(client.try_fund_autosave_keeper(&user, &id, &1).is_err());
+    let results = client.execute_due_autosaves(&soroban_sdk::vec![&env, id]);
+    assert!(results.get(0).unwrap());
+    assert_eq!(client.get_flexi_balance(&user), 300);
+    assert_eq!(token.balance(&user), 1_000 - 50 - 300);
+
+    // Running outside the keeper entry point draws no fee
+    assert_eq!(client.get_autosave(&id).unwrap().keeper_balance, 50);
+    assert_eq!(token.balance(&keeper), 0);
+}
+
+#[test]
+fn test_strategy_routing_moves_real_tokens() {
+    let (env, client, admin, token, token_admin) = setup_env();
+    let treasury = Address::generate(&env);
+    client.initialize_config(&admin, &treasury, &0, &0, &0);
+    let user = funded_user(&env, &client, &token_admin, 10_000);
+    let lock_id = client.create_lock_save(&user, &10_000, &3600);
+
+    let strategy = env.register(TokenStrategy, (token.address.clone(),));
+    client.register_strategy(&admin, &strategy, &1);
+    client.route_lock_to_strategy(&user, &lock_id, &strategy, &6_000);
+    assert_eq!(token.balance(&strategy), 6_000);
+    assert_eq!(token.balance(&client.address), 4_000);
+
+    // Yield comes into custody on harvest and is paid out on claim
+    token_admin.mint(&strategy, &500);
+    assert_eq!(client.harvest_strategy(&admin, &strategy), 500);
+    // The per-share yield index rounds down
+    assert_eq!(
+        client.claim_lock_strategy_yield(&user, &lock_id, &user),
+        499
+    );
+    assert_eq!(token.balance(&user), 499);
+
+    // Redemptions come back into custody, not to the saver
+    assert_eq!(
+        client.withdraw_lock_strategy(&user, &lock_id, &strategy),
+        6_000
+    );
+    assert_eq!(token.balance(&strategy), 0);
+    assert_eq!(token.balance(&client.address), 10_001);
+    assert_eq!(token.balance(&user), 499);
+}
+
+#[test]
+fn test_strategy_only_takes_plans_in_its_asset() {
+    let (env, client, admin, token, token_admin) = setup_env();
+    let other_sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let other = TokenClient::new(&env, &other_sac.address());
+    client.add_supported_asset(&admin, &other.address);
+    client.set_lock_rate_for(&admin, &other.address, &0, &500);
+
+    let user = funded_user(&env, &client, &token_admin, 1_000);
+    StellarAssetClient::new(&env, &other.address).mint(&user, &1_000);
+    let base_lock = client.create_lock_save(&user, &1_000, &3600);
+    let other_lock = client.create_asset_lock_save(&user, &other.address, &1_000, &3600);
+
+    let base_strategy = env.register(TokenStrategy, (token.address.clone(),));
+    let other_strategy = env.register(TokenStrategy, (other.address.clone(),));
+    client.register_strategy(&admin, &base_strategy, &1);
+    client.register_asset_strategy(&admin, &other_strategy, &1, &other.address);
+    assert_eq!(client.get_strategy_asset(&base_strategy), token.address);
+    assert_eq!(client.get_strategy_asset(&other_strategy), other.address);
+
+    assert_eq!(
+        client.try_route_lock_to_strategy(&user, &other_lock, &base_strategy, &500),
+        Err(Ok(SavingsError::UnsupportedAsset))
+    );
+    assert_eq!(
+        client.try_route_lock_to_strategy(&user, &base_lock, &other_strategy, &500),
+        Err(Ok(SavingsError::UnsupportedAsset))
+    );
+
+    client.route_lock_to_strategy(&user, &other_lock, &other_strategy, &500);
+    assert_eq!(other.balance(&other_strategy), 500);
+    assert_eq!(other.balance(&client.address), 500);
+}
+
+#[test]
+fn test_strategy_migration_moves_real_tokens() {
+    let (env, client, admin, token, token_admin) = setup_env();
+    let user = funded_user(&env, &client, &token_admin, 10_000);
+    let lock_id = client.create_lock_save(&user, &10_000, &3600);
+
+    let from = env.register(TokenStrategy, (token.address.clone(),));
+    let to = env.register(TokenStrategy, (token.address.clone(),));
+    client.register_strategy(&admin, &from, &1);
+    client.register_strategy(&admin, &to, &1);
+    client.route_lock_to_strategy(&user, &lock_id, &from, &6_000);
+    client.disable_strategy(&admin, &from);
+
+    assert!(
+        client
+            .migrate_strategy(&admin, &from, &to, &0, &5)
+            .completed
+    );
+    assert_eq!(token.balance(&from), 0);
+    assert_eq!(token.balance(&to), 6_000);
+    assert_eq!(token.balance(&client.address), 4_000);
+}
+
+#[test]
+fn test_reserve_loss_cover_moves_real_tokens() {
+    let (env, client, admin, token, token_admin) = setup_env();
+    let treasury = Address::generate(&env);
+    client.initialize_config(&admin, &treasury, &0, &1_000, &0); // 10% withdrawal fee
+    let saver = funded_user(&env, &client, &token_admin, 10_000);
+    client.deposit_flexi(&saver, &10_000);
+    client.withdraw_flexi(&saver, &10_000);
+    client.allocate_treasury(&admin, &10_000, &0, &0);
+    assert_eq!(client.get_reserve_balance(), 1_000);
+
+    let user = funded_user(&env, &client, &token_admin, 6_000);
+    let lock_id = client.create_lock_save(&user, &6_000, &3600);
+    let strategy = env.register(TokenStrategy, (token.address.clone(),));
+    client.register_strategy(&admin, &strategy, &1);
+    client.route_lock_to_strategy(&user, &lock_id, &strategy, &6_000);
+    client.set_loss_reserve_cover(&admin, &true);
+
+    TokenStrategyClient::new(&env, &strategy).lose(&400);
+    assert_eq!(client.realize_strategy_loss(&admin, &strategy), 400);
+    assert_eq!(client.get_reserve_balance(), 600);
+    assert_eq!(token.balance(&strategy), 6_000);
+    assert_eq!(token.balance(&client.address), 600);
+
+    assert_eq!(
+        client.withdraw_lock_strategy(&user, &lock_id, &strategy),
+        6_000
+    );
+    assert_eq!(token.balance(&client.address), 6_600);
+}